@@ -0,0 +1,57 @@
+//! Sequencer flow: pack raw data into a blob, commit to it, prove the commitment and check the
+//! result the way a verifier would, then derive the versioned hash that goes into the
+//! transaction.
+//!
+//! ```text
+//! cargo run --release --features examples --example blob_roundtrip [FILE]
+//! ```
+//!
+//! The data is read from `FILE`, or from standard input without one, and must fit in one blob.
+
+use kzg_rs::{kzg_to_versioned_hash, Blob, KzgError, KzgProof, KzgSettings};
+use std::io::Read;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let data = match std::env::args_os().nth(1) {
+        Some(path) => std::fs::read(path)?,
+        None => {
+            let mut data = Vec::new();
+            std::io::stdin().read_to_end(&mut data)?;
+            data
+        }
+    };
+
+    let kzg_settings = KzgSettings::load_trusted_setup_file().map_err(display)?;
+
+    // Each field element carries 31 bytes of data, so the blob is built on the heap
+    let blob = Blob::boxed_from_payload(&data).map_err(display)?;
+    let commitment = KzgProof::blob_to_kzg_commitment(&blob, &kzg_settings).map_err(display)?;
+    let proof =
+        KzgProof::compute_blob_kzg_proof(&blob, &commitment, &kzg_settings).map_err(display)?;
+
+    let versioned_hash = kzg_to_versioned_hash(&commitment);
+    let valid = KzgProof::verify_blob_kzg_proof_with_versioned_hash(
+        &blob,
+        &commitment,
+        &proof,
+        &versioned_hash,
+        &kzg_settings,
+    )
+    .map_err(display)?;
+    if !valid {
+        return Err("the computed proof does not verify".into());
+    }
+
+    println!("payload bytes:  {}", blob.payload_len());
+    println!("commitment:     0x{}", hex::encode(commitment.as_slice()));
+    println!("proof:          0x{}", hex::encode(proof.as_slice()));
+    println!(
+        "versioned hash: 0x{}",
+        hex::encode(versioned_hash.as_slice())
+    );
+    Ok(())
+}
+
+fn display(error: KzgError) -> String {
+    error.to_string()
+}
@@ -0,0 +1,88 @@
+//! Verifier flow: check the blob sidecars of a block as served by a beacon node, with one batched
+//! verification, and print the versioned hash of each blob.
+//!
+//! ```text
+//! curl -s http://localhost:5052/eth/v1/beacon/blob_sidecars/head \
+//!     | cargo run --release --features examples --example verify_blob_sidecars
+//! ```
+//!
+//! The JSON response is read from the file given as argument, or from standard input. Only the
+//! KZG fields of the sidecars are used.
+
+use kzg_rs::{kzg_to_versioned_hash, Blob, Bytes48, KzgError, KzgProof, KzgSettings};
+use serde_derive::Deserialize;
+use std::{io::Read, process::ExitCode};
+
+#[derive(Deserialize)]
+struct Response {
+    data: Vec<Sidecar>,
+}
+
+#[derive(Deserialize)]
+struct Sidecar {
+    index: String,
+    blob: String,
+    kzg_commitment: String,
+    kzg_proof: String,
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run() -> Result<bool, Box<dyn std::error::Error>> {
+    let json = match std::env::args_os().nth(1) {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => {
+            let mut json = String::new();
+            std::io::stdin().read_to_string(&mut json)?;
+            json
+        }
+    };
+    // JSON is a subset of YAML
+    let response: Response = serde_yaml::from_str(&json)?;
+
+    let mut blobs = Vec::with_capacity(response.data.len());
+    let mut commitments = Vec::with_capacity(response.data.len());
+    let mut proofs = Vec::with_capacity(response.data.len());
+    for sidecar in &response.data {
+        blobs.push(*Blob::boxed_from_slice(&decode(&sidecar.blob)?).map_err(display)?);
+        commitments.push(Bytes48::from_slice(&decode(&sidecar.kzg_commitment)?).map_err(display)?);
+        proofs.push(Bytes48::from_slice(&decode(&sidecar.kzg_proof)?).map_err(display)?);
+    }
+
+    let kzg_settings = KzgSettings::load_trusted_setup_file().map_err(display)?;
+    let valid =
+        KzgProof::verify_blob_kzg_proof_batch(blobs, commitments.clone(), proofs, &kzg_settings)
+            .map_err(display)?;
+
+    for (sidecar, commitment) in response.data.iter().zip(&commitments) {
+        let versioned_hash = kzg_to_versioned_hash(commitment);
+        println!(
+            "blob {}: 0x{}",
+            sidecar.index,
+            hex::encode(versioned_hash.as_slice())
+        );
+    }
+    println!(
+        "{} sidecars {}",
+        response.data.len(),
+        if valid { "valid" } else { "INVALID" }
+    );
+    Ok(valid)
+}
+
+fn decode(hex: &str) -> Result<Vec<u8>, hex::FromHexError> {
+    hex::decode(hex.strip_prefix("0x").unwrap_or(hex))
+}
+
+fn display(error: KzgError) -> String {
+    error.to_string()
+}
@@ -0,0 +1,267 @@
+//! Incremental batch verification whose state can be saved and restored.
+//!
+//! [`BatchAccumulator`] folds openings into two running G1 sums one at a time, so a large batch
+//! can be verified across several zkVM execution segments: serialize the state with
+//! [`BatchAccumulator::to_bytes`] at the end of a segment and resume it with
+//! [`BatchAccumulator::from_bytes`] in the next one. The state records the
+//! [`KzgSettings::digest`] of the setup it was created with, and is only restored under that
+//! setup.
+//!
+//! Unlike [`KzgProof::verify_blob_kzg_proof_batch`](crate::KzgProof), which derives a single
+//! random challenge from the whole batch, each opening here is weighted by a challenge derived
+//! from a running transcript of every opening absorbed so far, including itself.
+
+use crate::enums::KzgError;
+use crate::generators::g1_generator_mul;
+use crate::kzg_proof::{
+    prepare_blob_opening, safe_scalar_affine_from_bytes, scalar_from_bytes_unchecked,
+};
+use crate::msm::g1_mul;
+use crate::trusted_setup::{pairings_verify_cached, KzgSettings};
+use crate::{dtypes::*, BYTES_PER_G1_POINT, RANDOM_CHALLENGE_KZG_STREAM_DOMAIN};
+
+use alloc::string::ToString;
+use bls12_381::{G1Affine, G1Projective, G2Affine, Scalar};
+use sha2::{Digest, Sha256};
+
+/// Length of a serialized [`BatchAccumulator`]
+pub const BATCH_ACCUMULATOR_BYTES: usize = 8 + 32 + 32 + 2 * BYTES_PER_G1_POINT;
+
+/// Running state of an incremental batch verification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchAccumulator {
+    /// Number of openings absorbed so far
+    count: u64,
+    /// [`KzgSettings::digest`] of the setup the openings are verified with
+    settings_digest: [u8; 32],
+    /// Hash chain over every absorbed opening
+    transcript: [u8; 32],
+    /// `Σ r_i π_i`
+    proof_lincomb: G1Projective,
+    /// `Σ r_i (C_i - [y_i]G1 + z_i π_i)`
+    rhs_lincomb: G1Projective,
+}
+
+impl BatchAccumulator {
+    /// Starts an empty batch verified with `kzg_settings`, which every later call must also use
+    pub fn new(kzg_settings: &KzgSettings) -> Self {
+        Self {
+            count: 0,
+            settings_digest: kzg_settings.digest(),
+            transcript: [0u8; 32],
+            proof_lincomb: G1Projective::identity(),
+            rhs_lincomb: G1Projective::identity(),
+        }
+    }
+
+    /// Number of openings absorbed so far
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Absorbs the claim that the polynomial committed to by `commitment_bytes` evaluates to
+    /// `y_bytes` at `z_bytes`
+    pub fn add_kzg_proof(
+        &mut self,
+        commitment_bytes: &Bytes48,
+        z_bytes: &Bytes32,
+        y_bytes: &Bytes32,
+        proof_bytes: &Bytes48,
+        kzg_settings: &KzgSettings,
+    ) -> Result<(), KzgError> {
+        let commitment = kzg_settings
+            .subgroup_check
+            .g1_from_bytes(commitment_bytes)?;
+        let z = safe_scalar_affine_from_bytes(z_bytes)?;
+        let y = safe_scalar_affine_from_bytes(y_bytes)?;
+        let proof = kzg_settings.subgroup_check.g1_from_bytes(proof_bytes)?;
+        self.absorb(commitment, z, y, proof);
+        Ok(())
+    }
+
+    /// Absorbs a blob opening, evaluating the blob at its Fiat-Shamir challenge
+    pub fn add_blob_kzg_proof(
+        &mut self,
+        blob: &Blob,
+        commitment_bytes: &Bytes48,
+        proof_bytes: &Bytes48,
+        kzg_settings: &KzgSettings,
+    ) -> Result<(), KzgError> {
+        let (commitment, z, y, proof) =
+            prepare_blob_opening(blob, commitment_bytes, proof_bytes, kzg_settings)?;
+        self.absorb(commitment, z, y, proof);
+        Ok(())
+    }
+
+    fn absorb(&mut self, commitment: G1Affine, z: Scalar, y: Scalar, proof: G1Affine) {
+        let mut hasher = Sha256::new();
+        hasher.update(RANDOM_CHALLENGE_KZG_STREAM_DOMAIN.as_bytes());
+        hasher.update(self.transcript);
+        hasher.update(self.count.to_be_bytes());
+        hasher.update(commitment.to_compressed());
+        hasher.update(z.to_bytes());
+        hasher.update(y.to_bytes());
+        hasher.update(proof.to_compressed());
+        self.transcript = hasher.finalize().into();
+        self.count += 1;
+
+        let r = scalar_from_bytes_unchecked(self.transcript);
+        self.proof_lincomb += g1_mul(&proof, &r);
+        self.rhs_lincomb +=
+            g1_mul(&commitment, &r) - g1_generator_mul(&(r * y)) + g1_mul(&proof, &(r * z));
+    }
+
+    /// Checks every absorbed opening at once. An empty accumulator verifies trivially.
+    pub fn verify(&self, kzg_settings: &KzgSettings) -> bool {
+        pairings_verify_cached(
+            self.proof_lincomb.into(),
+            kzg_settings.g2_points[1],
+            self.rhs_lincomb.into(),
+            G2Affine::generator(),
+        )
+    }
+
+    /// Serializes the state as
+    /// `count || settings digest || transcript || proof_lincomb || rhs_lincomb`, with the count
+    /// big-endian and both points compressed
+    pub fn to_bytes(&self) -> [u8; BATCH_ACCUMULATOR_BYTES] {
+        let mut bytes = [0u8; BATCH_ACCUMULATOR_BYTES];
+        bytes[..8].copy_from_slice(&self.count.to_be_bytes());
+        bytes[8..40].copy_from_slice(&self.settings_digest);
+        bytes[40..72].copy_from_slice(&self.transcript);
+        bytes[72..120].copy_from_slice(&G1Affine::from(self.proof_lincomb).to_compressed());
+        bytes[120..].copy_from_slice(&G1Affine::from(self.rhs_lincomb).to_compressed());
+        bytes
+    }
+
+    /// Restores a state serialized with [`BatchAccumulator::to_bytes`], parsing its points with
+    /// the subgroup check of `kzg_settings`. Fails if the state was created with another setup.
+    pub fn from_bytes(bytes: &[u8], kzg_settings: &KzgSettings) -> Result<Self, KzgError> {
+        if bytes.len() != BATCH_ACCUMULATOR_BYTES {
+            return Err(KzgError::InvalidBytesLength(
+                "Invalid batch accumulator length".to_string(),
+            ));
+        }
+
+        let mut count = [0u8; 8];
+        count.copy_from_slice(&bytes[..8]);
+        let settings_digest: [u8; 32] = bytes[8..40].try_into().unwrap();
+        if settings_digest != kzg_settings.digest() {
+            return Err(KzgError::InvalidTrustedSetup(
+                "The batch accumulator was created with another trusted setup".to_string(),
+            ));
+        }
+        let mut transcript = [0u8; 32];
+        transcript.copy_from_slice(&bytes[40..72]);
+
+        let point = |bytes: &[u8]| {
+            kzg_settings
+                .subgroup_check
+                .g1_from_bytes(&Bytes48::from_slice(bytes)?)
+                .map(G1Projective::from)
+        };
+        Ok(Self {
+            count: u64::from_be_bytes(count),
+            settings_digest,
+            transcript,
+            proof_lincomb: point(&bytes[72..120])?,
+            rhs_lincomb: point(&bytes[120..])?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg_proof::tests::{valid_blob_cases, valid_kzg_proof_cases};
+    use crate::subgroup::{tests::g1_not_in_subgroup, SubgroupCheck};
+
+    #[test]
+    fn test_batch_accumulator() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let mut accumulator = BatchAccumulator::new(&kzg_settings);
+        assert!(accumulator.verify(&kzg_settings));
+
+        let cases = valid_kzg_proof_cases();
+        for case in cases.iter().filter(|case| case.output == Some(true)) {
+            accumulator
+                .add_kzg_proof(
+                    &case.commitment,
+                    &case.z,
+                    &case.y,
+                    &case.proof,
+                    &kzg_settings,
+                )
+                .unwrap();
+
+            // Round-trip through bytes after every opening, as a new segment would
+            accumulator =
+                BatchAccumulator::from_bytes(&accumulator.to_bytes(), &kzg_settings).unwrap();
+        }
+
+        let (blobs, commitments, proofs) = valid_blob_cases();
+        for ((blob, commitment), proof) in blobs.iter().zip(&commitments).zip(&proofs) {
+            accumulator
+                .add_blob_kzg_proof(blob, commitment, proof, &kzg_settings)
+                .unwrap();
+        }
+        assert!(accumulator.count() > 2);
+        assert!(accumulator.verify(&kzg_settings));
+
+        let restored =
+            BatchAccumulator::from_bytes(&accumulator.to_bytes(), &kzg_settings).unwrap();
+        assert_eq!(restored, accumulator);
+
+        let invalid = cases
+            .iter()
+            .find(|case| case.output == Some(false))
+            .unwrap();
+        accumulator
+            .add_kzg_proof(
+                &invalid.commitment,
+                &invalid.z,
+                &invalid.y,
+                &invalid.proof,
+                &kzg_settings,
+            )
+            .unwrap();
+        assert!(!accumulator.verify(&kzg_settings));
+
+        // A state is not resumed under another setup
+        assert!(
+            BatchAccumulator::from_bytes(&accumulator.to_bytes(), KzgSettings::minimal()).is_err()
+        );
+
+        assert!(
+            BatchAccumulator::from_bytes(&[0u8; BATCH_ACCUMULATOR_BYTES - 1], &kzg_settings)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_batch_accumulator_subgroup_check() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let unchecked = kzg_settings
+            .clone()
+            .with_subgroup_check(SubgroupCheck::Skip);
+        let case = valid_kzg_proof_cases()
+            .into_iter()
+            .find(|case| case.output == Some(true))
+            .unwrap();
+        let outside = g1_not_in_subgroup();
+
+        let mut accumulator = BatchAccumulator::new(&kzg_settings);
+        assert!(accumulator
+            .add_kzg_proof(&outside, &case.z, &case.y, &case.proof, &kzg_settings)
+            .is_err());
+        accumulator
+            .add_kzg_proof(&outside, &case.z, &case.y, &case.proof, &unchecked)
+            .unwrap();
+
+        // Restored points go through the same check
+        let mut bytes = accumulator.to_bytes();
+        bytes[72..120].copy_from_slice(outside.as_slice());
+        assert!(BatchAccumulator::from_bytes(&bytes, &kzg_settings).is_err());
+        assert!(BatchAccumulator::from_bytes(&bytes, &unchecked).is_ok());
+    }
+}
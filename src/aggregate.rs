@@ -0,0 +1,151 @@
+//! Aggregated blob proofs from the pre-Deneb EIP-4844 drafts.
+//!
+//! A single proof covers a whole list of blobs: the blobs and their commitments are combined
+//! with powers of a challenge `r` into one polynomial and one commitment, which is opened at a
+//! second challenge. Both challenges come from the transcript
+//!
+//! ```text
+//! h = sha256(FIAT_SHAMIR_PROTOCOL_DOMAIN || degree (8 bytes) || count (8 bytes) || blobs || commitments)
+//! r = sha256(h || 0x00) mod p,  z = sha256(h || 0x01) mod p
+//! ```
+//!
+//! with integers and field elements encoded big-endian. The scheme was replaced by per-blob proofs
+//! and is only provided for tooling that still handles aggregated proofs.
+
+use crate::compression::{compress_g1_projective_batch, decompress_g1_batch};
+use crate::dtypes::*;
+use crate::enums::KzgError;
+use crate::kzg_proof::{
+    compute_kzg_proof_impl, polynomial_to_commitment_projective, scalar_from_bytes_unchecked,
+    verify_kzg_proof_impl,
+};
+use crate::msm::g1_msm;
+use crate::polynomial::Polynomial;
+use crate::trusted_setup::KzgSettings;
+use crate::utils::compute_powers;
+use crate::{FIAT_SHAMIR_PROTOCOL_DOMAIN, NUM_FIELD_ELEMENTS_PER_BLOB};
+
+use alloc::{string::ToString, vec::Vec};
+use bls12_381::{G1Affine, G1Projective, Scalar};
+use sha2::{Digest, Sha256};
+
+/// Returns the powers of `r` used to combine the blobs and the evaluation challenge
+fn compute_aggregation_challenges(
+    blobs: &[Blob],
+    commitments_bytes: &[Bytes48],
+) -> (Vec<Scalar>, Scalar) {
+    let mut hasher = Sha256::new();
+    hasher.update(FIAT_SHAMIR_PROTOCOL_DOMAIN.as_bytes());
+    hasher.update((NUM_FIELD_ELEMENTS_PER_BLOB as u64).to_be_bytes());
+    hasher.update((blobs.len() as u64).to_be_bytes());
+    for blob in blobs {
+        hasher.update(blob.as_slice());
+    }
+    for commitment in commitments_bytes {
+        hasher.update(commitment.as_slice());
+    }
+    let transcript: [u8; 32] = hasher.finalize().into();
+
+    let challenge = |suffix: u8| {
+        let hash: [u8; 32] = Sha256::new()
+            .chain_update(transcript)
+            .chain_update([suffix])
+            .finalize()
+            .into();
+        scalar_from_bytes_unchecked(hash)
+    };
+    (compute_powers(&challenge(0), blobs.len()), challenge(1))
+}
+
+/// Combines the blobs with `r_powers` into a single polynomial in evaluation form
+fn aggregate_polynomial(blobs: &[Blob], r_powers: &[Scalar]) -> Result<Polynomial, KzgError> {
+    let mut aggregated = Polynomial::Evaluations(vec![Scalar::zero(); NUM_FIELD_ELEMENTS_PER_BLOB]);
+    for (blob, r) in blobs.iter().zip(r_powers) {
+        aggregated = aggregated.checked_add(&(blob.to_polynomial()? * *r))?;
+    }
+    Ok(aggregated)
+}
+
+/// Computes the aggregated proof for `blobs`, committing to each blob first
+pub fn compute_aggregate_kzg_proof(
+    blobs: &[Blob],
+    kzg_settings: &KzgSettings,
+) -> Result<Bytes48, KzgError> {
+    let commitments = blobs
+        .iter()
+        .map(|blob| polynomial_to_commitment_projective(&blob.to_polynomial()?, kzg_settings))
+        .collect::<Result<Vec<_>, _>>()?;
+    let commitments_bytes = compress_g1_projective_batch(&commitments);
+
+    let (r_powers, z) = compute_aggregation_challenges(blobs, &commitments_bytes);
+    let polynomial = aggregate_polynomial(blobs, &r_powers)?;
+    let (proof, _) = compute_kzg_proof_impl(&polynomial, z, kzg_settings)?;
+    Ok(Bytes48::from(proof.to_compressed()))
+}
+
+/// Verifies an aggregated proof from [`compute_aggregate_kzg_proof`] against the expected
+/// commitment of each blob
+pub fn verify_aggregate_kzg_proof(
+    blobs: &[Blob],
+    commitments_bytes: &[Bytes48],
+    aggregated_proof_bytes: &Bytes48,
+    kzg_settings: &KzgSettings,
+) -> Result<bool, KzgError> {
+    if blobs.len() != commitments_bytes.len() {
+        return Err(KzgError::InvalidBytesLength(
+            "Invalid commitments length".to_string(),
+        ));
+    }
+
+    let commitments = decompress_g1_batch(commitments_bytes, kzg_settings.subgroup_check)?
+        .into_iter()
+        .map(G1Projective::from)
+        .collect::<Vec<_>>();
+    let proof = kzg_settings
+        .subgroup_check
+        .g1_from_bytes(aggregated_proof_bytes)?;
+
+    let (r_powers, z) = compute_aggregation_challenges(blobs, commitments_bytes);
+    let polynomial = aggregate_polynomial(blobs, &r_powers)?;
+    let commitment = G1Affine::from(g1_msm(&commitments, &r_powers));
+    let y = polynomial.evaluate(z, kzg_settings)?;
+    verify_kzg_proof_impl(commitment, z, y, proof, kzg_settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg_proof::tests::{BlobInput, Test};
+    use crate::test_files::VERIFY_BLOB_KZG_PROOF_TESTS;
+
+    #[test]
+    fn test_aggregate_kzg_proof() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let (blobs, mut commitments): (Vec<_>, Vec<_>) = VERIFY_BLOB_KZG_PROOF_TESTS
+            .iter()
+            .filter_map(|(_, data)| {
+                let test: Test<BlobInput> = serde_yaml::from_str(data).unwrap();
+                match (
+                    test.input.get_blob(),
+                    test.input.get_commitment(),
+                    test.get_output(),
+                ) {
+                    (Ok(blob), Ok(commitment), Some(true)) => Some((blob, commitment)),
+                    _ => None,
+                }
+            })
+            .take(3)
+            .unzip();
+        assert_eq!(blobs.len(), 3);
+
+        let proof = compute_aggregate_kzg_proof(&blobs, &kzg_settings).unwrap();
+        assert!(verify_aggregate_kzg_proof(&blobs, &commitments, &proof, &kzg_settings).unwrap());
+
+        // The commitments are bound to the blobs in order
+        commitments.swap(0, 1);
+        assert!(!verify_aggregate_kzg_proof(&blobs, &commitments, &proof, &kzg_settings).unwrap());
+        assert!(
+            verify_aggregate_kzg_proof(&blobs, &commitments[1..], &proof, &kzg_settings).is_err()
+        );
+    }
+}
@@ -0,0 +1,226 @@
+//! Framed on-disk format for storing blob openings and re-verifying them later.
+//!
+//! An archive starts with a 44-byte header:
+//!
+//! ```text
+//! magic "KZGARCH\0" (8) | version u16 BE (2) | reserved (2) | settings digest (32)
+//! ```
+//!
+//! followed by any number of entries, each `blob || commitment || proof || checksum`, where the
+//! checksum is the SHA-256 of the blob, commitment and proof. The settings digest identifies the
+//! trusted setup the openings were made with, see [`settings_digest`].
+
+use crate::dtypes::*;
+use crate::enums::KzgError;
+use crate::kzg_proof::verify_blob_kzg_proof_impl;
+use crate::trusted_setup::KzgSettings;
+use crate::{BYTES_PER_BLOB, BYTES_PER_COMMITMENT, BYTES_PER_PROOF};
+
+use sha2::{Digest, Sha256};
+use std::io::{self, Read, Write};
+
+pub const ARCHIVE_MAGIC: [u8; 8] = *b"KZGARCH\0";
+pub const ARCHIVE_VERSION: u16 = 1;
+pub const ARCHIVE_HEADER_SIZE: usize = 44;
+pub const ARCHIVE_ENTRY_SIZE: usize = BYTES_PER_BLOB + BYTES_PER_COMMITMENT + BYTES_PER_PROOF + 32;
+
+/// Returns the digest of the trusted setup, see [`KzgSettings::digest`]
+pub fn settings_digest(kzg_settings: &KzgSettings) -> [u8; 32] {
+    kzg_settings.digest()
+}
+
+/// A blob opening stored in an archive
+#[derive(Debug, Clone)]
+pub struct ArchiveEntry {
+    pub blob: Box<Blob>,
+    pub commitment: Bytes48,
+    pub proof: Bytes48,
+}
+
+impl ArchiveEntry {
+    pub fn verify(&self, kzg_settings: &KzgSettings) -> Result<bool, KzgError> {
+        verify_blob_kzg_proof_impl(&self.blob, &self.commitment, &self.proof, kzg_settings)
+    }
+}
+
+/// Writes an archive entry by entry
+#[derive(Debug)]
+pub struct ArchiveWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> ArchiveWriter<W> {
+    /// Writes the header for openings made with `kzg_settings`
+    pub fn new(mut writer: W, kzg_settings: &KzgSettings) -> io::Result<Self> {
+        writer.write_all(&ARCHIVE_MAGIC)?;
+        writer.write_all(&ARCHIVE_VERSION.to_be_bytes())?;
+        writer.write_all(&[0; 2])?;
+        writer.write_all(&settings_digest(kzg_settings))?;
+        Ok(Self { writer })
+    }
+
+    pub fn append(&mut self, blob: &Blob, commitment: &Bytes48, proof: &Bytes48) -> io::Result<()> {
+        let checksum = Sha256::new()
+            .chain_update(blob.as_slice())
+            .chain_update(commitment.as_slice())
+            .chain_update(proof.as_slice())
+            .finalize();
+        self.writer.write_all(blob.as_slice())?;
+        self.writer.write_all(commitment.as_slice())?;
+        self.writer.write_all(proof.as_slice())?;
+        self.writer.write_all(&checksum)
+    }
+
+    /// Flushes the archive and returns the underlying writer
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Reads an archive, yielding its entries in order. An entry whose checksum does not match, or
+/// that is cut short, is reported as an [`io::ErrorKind::InvalidData`] error and ends the
+/// iteration.
+#[derive(Debug)]
+pub struct ArchiveReader<R: Read> {
+    reader: R,
+    settings_digest: [u8; 32],
+    done: bool,
+}
+
+impl<R: Read> ArchiveReader<R> {
+    /// Reads and checks the header
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut header = [0u8; ARCHIVE_HEADER_SIZE];
+        reader.read_exact(&mut header)?;
+        if header[..8] != ARCHIVE_MAGIC {
+            return Err(invalid_data("Not a blob archive"));
+        }
+        let version = u16::from_be_bytes([header[8], header[9]]);
+        if version != ARCHIVE_VERSION {
+            return Err(invalid_data(&format!(
+                "Unsupported archive version {}",
+                version
+            )));
+        }
+
+        Ok(Self {
+            reader,
+            settings_digest: header[12..].try_into().unwrap(),
+            done: false,
+        })
+    }
+
+    /// Returns the digest of the trusted setup the openings were made with
+    pub fn settings_digest(&self) -> [u8; 32] {
+        self.settings_digest
+    }
+
+    /// Returns `true` if the openings were made with `kzg_settings`
+    pub fn matches_settings(&self, kzg_settings: &KzgSettings) -> bool {
+        self.settings_digest == settings_digest(kzg_settings)
+    }
+
+    fn read_entry(&mut self) -> io::Result<Option<ArchiveEntry>> {
+        let mut buffer = vec![0u8; ARCHIVE_ENTRY_SIZE];
+        let mut filled = 0;
+        while filled < buffer.len() {
+            match self.reader.read(&mut buffer[filled..]) {
+                Ok(0) => break,
+                Ok(read) => filled += read,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        if filled == 0 {
+            return Ok(None);
+        }
+        if filled < buffer.len() {
+            return Err(invalid_data("Truncated archive entry"));
+        }
+
+        let (data, checksum) = buffer.split_at(ARCHIVE_ENTRY_SIZE - 32);
+        if Sha256::digest(data).as_slice() != checksum {
+            return Err(invalid_data("Archive entry checksum mismatch"));
+        }
+        let (blob, rest) = data.split_at(BYTES_PER_BLOB);
+        let (commitment, proof) = rest.split_at(BYTES_PER_COMMITMENT);
+        let entry = (|| {
+            Ok::<_, KzgError>(ArchiveEntry {
+                blob: Blob::boxed_from_slice(blob)?,
+                commitment: Bytes48::from_slice(commitment)?,
+                proof: Bytes48::from_slice(proof)?,
+            })
+        })()
+        .map_err(|e| invalid_data(&e.to_string()))?;
+        Ok(Some(entry))
+    }
+}
+
+impl<R: Read> Iterator for ArchiveReader<R> {
+    type Item = io::Result<ArchiveEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let entry = self.read_entry().transpose();
+        if !matches!(entry, Some(Ok(_))) {
+            self.done = true;
+        }
+        entry
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg_proof::tests::valid_blob_cases;
+
+    #[test]
+    fn test_archive_round_trip() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let (blobs, commitments, proofs) = valid_blob_cases();
+
+        let mut writer = ArchiveWriter::new(Vec::new(), &kzg_settings).unwrap();
+        for ((blob, commitment), proof) in blobs.iter().zip(&commitments).zip(&proofs) {
+            writer.append(blob, commitment, proof).unwrap();
+        }
+        let mut archive = writer.finish().unwrap();
+        assert_eq!(
+            archive.len(),
+            ARCHIVE_HEADER_SIZE + blobs.len() * ARCHIVE_ENTRY_SIZE
+        );
+
+        let reader = ArchiveReader::new(archive.as_slice()).unwrap();
+        assert!(reader.matches_settings(&kzg_settings));
+        let entries = reader.collect::<io::Result<Vec<_>>>().unwrap();
+        assert_eq!(entries.len(), blobs.len());
+        for (entry, blob) in entries.iter().zip(&blobs) {
+            assert_eq!(entry.blob.as_slice(), blob.as_slice());
+            assert!(entry.verify(&kzg_settings).unwrap());
+        }
+
+        // A flipped bit in the second entry is caught by its checksum and ends the iteration
+        archive[ARCHIVE_HEADER_SIZE + ARCHIVE_ENTRY_SIZE + 100] ^= 1;
+        let results = ArchiveReader::new(archive.as_slice())
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        assert_eq!(
+            results[1].as_ref().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+
+        let truncated = &archive[..ARCHIVE_HEADER_SIZE + 10];
+        let results = ArchiveReader::new(truncated).unwrap().collect::<Vec<_>>();
+        assert!(results.len() == 1 && results[0].is_err());
+
+        assert!(ArchiveReader::new(&archive[1..]).is_err());
+    }
+}
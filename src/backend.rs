@@ -0,0 +1,191 @@
+//! Runtime-selectable verification backends.
+//!
+//! [`KzgBackend`] is object safe, so a binary can pick an implementation from configuration with
+//! [`backend_by_name`] and use it through `&dyn KzgBackend` without being generic over it.
+
+use crate::dtypes::*;
+use crate::enums::KzgError;
+use crate::kzg_proof::KzgProof;
+use crate::trusted_setup::KzgSettings;
+
+#[cfg(not(feature = "prover"))]
+use alloc::string::ToString;
+
+/// A KZG verification implementation
+pub trait KzgBackend: Send + Sync {
+    /// Name used to select the backend at runtime
+    fn name(&self) -> &'static str;
+
+    fn verify_kzg_proof(
+        &self,
+        commitment_bytes: &Bytes48,
+        z_bytes: &Bytes32,
+        y_bytes: &Bytes32,
+        proof_bytes: &Bytes48,
+        kzg_settings: &KzgSettings,
+    ) -> Result<bool, KzgError>;
+
+    /// Fails without the `prover` feature, which compiles blob handling
+    fn verify_blob_kzg_proof(
+        &self,
+        blob: &Blob,
+        commitment_bytes: &Bytes48,
+        proof_bytes: &Bytes48,
+        kzg_settings: &KzgSettings,
+    ) -> Result<bool, KzgError>;
+
+    /// Fails without the `prover` feature, which compiles blob handling
+    fn verify_blob_kzg_proof_batch(
+        &self,
+        blobs: &[Blob],
+        commitments_bytes: &[Bytes48],
+        proofs_bytes: &[Bytes48],
+        kzg_settings: &KzgSettings,
+    ) -> Result<bool, KzgError>;
+}
+
+/// The pure-Rust backend built on `bls12_381`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RustBackend;
+
+impl KzgBackend for RustBackend {
+    fn name(&self) -> &'static str {
+        "rust"
+    }
+
+    fn verify_kzg_proof(
+        &self,
+        commitment_bytes: &Bytes48,
+        z_bytes: &Bytes32,
+        y_bytes: &Bytes32,
+        proof_bytes: &Bytes48,
+        kzg_settings: &KzgSettings,
+    ) -> Result<bool, KzgError> {
+        KzgProof::verify_kzg_proof(
+            commitment_bytes,
+            z_bytes,
+            y_bytes,
+            proof_bytes,
+            kzg_settings,
+        )
+    }
+
+    #[cfg(feature = "prover")]
+    fn verify_blob_kzg_proof(
+        &self,
+        blob: &Blob,
+        commitment_bytes: &Bytes48,
+        proof_bytes: &Bytes48,
+        kzg_settings: &KzgSettings,
+    ) -> Result<bool, KzgError> {
+        crate::kzg_proof::verify_blob_kzg_proof_impl(
+            blob,
+            commitment_bytes,
+            proof_bytes,
+            kzg_settings,
+        )
+    }
+
+    #[cfg(not(feature = "prover"))]
+    fn verify_blob_kzg_proof(
+        &self,
+        _blob: &Blob,
+        _commitment_bytes: &Bytes48,
+        _proof_bytes: &Bytes48,
+        _kzg_settings: &KzgSettings,
+    ) -> Result<bool, KzgError> {
+        Err(blob_support_missing())
+    }
+
+    #[cfg(feature = "prover")]
+    fn verify_blob_kzg_proof_batch(
+        &self,
+        blobs: &[Blob],
+        commitments_bytes: &[Bytes48],
+        proofs_bytes: &[Bytes48],
+        kzg_settings: &KzgSettings,
+    ) -> Result<bool, KzgError> {
+        crate::kzg_proof::verify_blob_kzg_proof_batch_controlled(
+            blobs,
+            commitments_bytes,
+            proofs_bytes,
+            kzg_settings,
+            &mut |_| {},
+            None,
+        )
+    }
+
+    #[cfg(not(feature = "prover"))]
+    fn verify_blob_kzg_proof_batch(
+        &self,
+        _blobs: &[Blob],
+        _commitments_bytes: &[Bytes48],
+        _proofs_bytes: &[Bytes48],
+        _kzg_settings: &KzgSettings,
+    ) -> Result<bool, KzgError> {
+        Err(blob_support_missing())
+    }
+}
+
+#[cfg(not(feature = "prover"))]
+fn blob_support_missing() -> KzgError {
+    KzgError::BadArgs("Blob verification requires the prover feature".to_string())
+}
+
+/// Backends compiled into this build, in order of preference
+pub fn available_backends() -> &'static [&'static dyn KzgBackend] {
+    &[&RustBackend]
+}
+
+/// Returns the compiled-in backend called `name`, if any
+pub fn backend_by_name(name: &str) -> Option<&'static dyn KzgBackend> {
+    available_backends()
+        .iter()
+        .copied()
+        .find(|backend| backend.name() == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "prover")]
+    #[test]
+    fn test_backend_by_name() {
+        use crate::kzg_proof::tests::valid_kzg_proof_cases;
+
+        assert!(backend_by_name("unknown").is_none());
+
+        let backend = backend_by_name("rust").unwrap();
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        for case in valid_kzg_proof_cases() {
+            assert_eq!(
+                backend
+                    .verify_kzg_proof(
+                        &case.commitment,
+                        &case.z,
+                        &case.y,
+                        &case.proof,
+                        &kzg_settings
+                    )
+                    .ok(),
+                case.output
+            );
+        }
+    }
+
+    #[cfg(not(feature = "prover"))]
+    #[test]
+    fn test_blob_verification_requires_prover() {
+        let blob = Blob::from_slice(&vec![0u8; crate::BYTES_PER_BLOB]).unwrap();
+        let commitment = Bytes48::from([0u8; 48]);
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let backend = backend_by_name("rust").unwrap();
+        assert!(backend
+            .verify_blob_kzg_proof(&blob, &commitment, &commitment, &kzg_settings)
+            .is_err());
+        assert!(backend
+            .verify_blob_kzg_proof_batch(&[], &[], &[], &kzg_settings)
+            .is_err());
+    }
+}
@@ -0,0 +1,201 @@
+//! Re-verifies stored blob openings in bulk and prints a JSON report.
+//!
+//! ```text
+//! kzg-revalidate <DIR | INDEX_FILE>
+//! ```
+//!
+//! A directory is searched recursively for `<name>.blob` files, each next to a
+//! `<name>.commitment` and a `<name>.proof`. An index file lists one opening per line as
+//! `<blob path> <commitment hex> <proof hex>`, with blob paths relative to the index file.
+//! Blobs, commitments and proofs may be stored as raw bytes or as hex text.
+//!
+//! The report is one JSON object per line: one for every opening that failed, then a summary.
+//! The process exits with status 1 if any opening failed. Verification uses every available
+//! core unless `RAYON_NUM_THREADS` says otherwise.
+
+use kzg_rs::{Blob, Bytes48, KzgProof, KzgSettings, BYTES_PER_BLOB};
+use rayon::prelude::*;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Instant,
+};
+
+struct Entry {
+    name: String,
+    blob: PathBuf,
+    commitment: Source,
+    proof: Source,
+}
+
+enum Source {
+    File(PathBuf),
+    Hex(String),
+}
+
+enum Outcome {
+    Valid,
+    Invalid,
+    Error(String),
+}
+
+fn main() -> ExitCode {
+    let Some(input) = std::env::args_os().nth(1).map(PathBuf::from) else {
+        eprintln!("usage: kzg-revalidate <DIR | INDEX_FILE>");
+        return ExitCode::from(2);
+    };
+
+    let entries = if input.is_dir() {
+        let mut entries = Vec::new();
+        collect_dir(&input, &input, &mut entries)
+            .map(|_| entries)
+            .map_err(|e| e.to_string())
+    } else {
+        read_index(&input)
+    };
+    let entries = match entries {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("failed to read {}: {}", input.display(), e);
+            return ExitCode::from(2);
+        }
+    };
+
+    let kzg_settings = KzgSettings::load_trusted_setup_file().expect("embedded trusted setup");
+    let start = Instant::now();
+    let outcomes = entries
+        .par_iter()
+        .map(|entry| verify_entry(entry, &kzg_settings))
+        .collect::<Vec<_>>();
+    let elapsed = start.elapsed();
+
+    let (mut valid, mut invalid, mut errors) = (0, 0, 0);
+    for (entry, outcome) in entries.iter().zip(&outcomes) {
+        match outcome {
+            Outcome::Valid => valid += 1,
+            Outcome::Invalid => {
+                invalid += 1;
+                println!(
+                    "{{\"name\":{},\"status\":\"invalid\"}}",
+                    json_string(&entry.name)
+                );
+            }
+            Outcome::Error(e) => {
+                errors += 1;
+                println!(
+                    "{{\"name\":{},\"status\":\"error\",\"error\":{}}}",
+                    json_string(&entry.name),
+                    json_string(e)
+                );
+            }
+        }
+    }
+    println!(
+        "{{\"total\":{},\"valid\":{},\"invalid\":{},\"errors\":{},\"threads\":{},\"elapsed_ms\":{}}}",
+        entries.len(),
+        valid,
+        invalid,
+        errors,
+        rayon::current_num_threads(),
+        elapsed.as_millis()
+    );
+
+    if invalid + errors > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn collect_dir(root: &Path, dir: &Path, entries: &mut Vec<Entry>) -> std::io::Result<()> {
+    let mut paths = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    paths.sort();
+
+    for path in paths {
+        if path.is_dir() {
+            collect_dir(root, &path, entries)?;
+        } else if path.extension().is_some_and(|ext| ext == "blob") {
+            let name = path.strip_prefix(root).unwrap_or(&path).with_extension("");
+            entries.push(Entry {
+                name: name.display().to_string(),
+                commitment: Source::File(path.with_extension("commitment")),
+                proof: Source::File(path.with_extension("proof")),
+                blob: path,
+            });
+        }
+    }
+    Ok(())
+}
+
+fn read_index(path: &Path) -> Result<Vec<Entry>, String> {
+    let base = path.parent().unwrap_or(Path::new("."));
+    fs::read_to_string(path)
+        .map_err(|e| e.to_string())?
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(number, line)| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let [blob, commitment, proof] = fields[..] else {
+                return Err(format!("line {}: expected 3 fields", number + 1));
+            };
+            Ok(Entry {
+                name: blob.to_string(),
+                blob: base.join(blob),
+                commitment: Source::Hex(commitment.to_string()),
+                proof: Source::Hex(proof.to_string()),
+            })
+        })
+        .collect()
+}
+
+fn verify_entry(entry: &Entry, kzg_settings: &KzgSettings) -> Outcome {
+    let result = (|| {
+        let blob = Blob::from_slice(&load(&Source::File(entry.blob.clone()), BYTES_PER_BLOB)?)
+            .map_err(|e| e.to_string())?;
+        let commitment =
+            Bytes48::from_slice(&load(&entry.commitment, 48)?).map_err(|e| e.to_string())?;
+        let proof = Bytes48::from_slice(&load(&entry.proof, 48)?).map_err(|e| e.to_string())?;
+        KzgProof::verify_blob_kzg_proof(blob, &commitment, &proof, kzg_settings)
+            .map_err(|e| e.to_string())
+    })();
+
+    match result {
+        Ok(true) => Outcome::Valid,
+        Ok(false) => Outcome::Invalid,
+        Err(e) => Outcome::Error(e),
+    }
+}
+
+/// Loads a value of `len` bytes stored either raw or as hex text
+fn load(source: &Source, len: usize) -> Result<Vec<u8>, String> {
+    let bytes = match source {
+        Source::File(path) => fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?,
+        Source::Hex(hex) => hex.as_bytes().to_vec(),
+    };
+    if bytes.len() == len {
+        return Ok(bytes);
+    }
+
+    let text = std::str::from_utf8(&bytes).map_err(|_| "neither raw bytes nor hex".to_string())?;
+    let text = text.trim();
+    hex::decode(text.strip_prefix("0x").unwrap_or(text)).map_err(|e| e.to_string())
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
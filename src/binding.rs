@@ -0,0 +1,129 @@
+//! Proofs that a served blob matches a known commitment.
+//!
+//! A light client holding a commitment (for example from a block header) can check a blob it was
+//! served against that commitment with one evaluation and one pairing, instead of recomputing the
+//! commitment with a 4096-point MSM. The server opens the commitment at a point derived from a
+//! caller-chosen context, such as a block identifier, together with the commitment and the blob.
+//!
+//! The blob must be part of the derivation: were the point known before the blob is fixed, a
+//! server could serve any other blob with the same evaluation at that point and reuse the honest
+//! proof.
+
+use crate::dtypes::*;
+use crate::enums::KzgError;
+use crate::kzg_proof::{
+    compute_kzg_proof_impl, scalar_from_bytes_unchecked, verify_kzg_proof_impl,
+};
+use crate::trusted_setup::KzgSettings;
+use crate::BLOB_BINDING_DOMAIN;
+
+use bls12_381::Scalar;
+use sha2::{Digest, Sha256};
+
+/// Derives the evaluation point binding `blob` to `commitment_bytes` under `context`
+pub fn compute_binding_challenge(
+    context: &[u8],
+    blob: &Blob,
+    commitment_bytes: &Bytes48,
+) -> Scalar {
+    let mut hasher = Sha256::new();
+    hasher.update(BLOB_BINDING_DOMAIN.as_bytes());
+    hasher.update((context.len() as u64).to_be_bytes());
+    hasher.update(context);
+    hasher.update(commitment_bytes.as_slice());
+    hasher.update(blob.as_slice());
+    scalar_from_bytes_unchecked(hasher.finalize().into())
+}
+
+/// Computes the proof binding `blob` to `commitment_bytes` under `context`
+pub fn compute_blob_binding_proof(
+    context: &[u8],
+    blob: &Blob,
+    commitment_bytes: &Bytes48,
+    kzg_settings: &KzgSettings,
+) -> Result<Bytes48, KzgError> {
+    kzg_settings
+        .subgroup_check
+        .g1_from_bytes(commitment_bytes)?;
+    let polynomial = blob.to_polynomial()?;
+    let z = compute_binding_challenge(context, blob, commitment_bytes);
+    let (proof, _) = compute_kzg_proof_impl(&polynomial, z, kzg_settings)?;
+    Ok(Bytes48::from(proof.to_compressed()))
+}
+
+/// Checks that `blob` is the blob committed to by `commitment_bytes`, using a proof from
+/// [`compute_blob_binding_proof`] with the same `context`
+pub fn verify_blob_binding_proof(
+    context: &[u8],
+    blob: &Blob,
+    commitment_bytes: &Bytes48,
+    proof_bytes: &Bytes48,
+    kzg_settings: &KzgSettings,
+) -> Result<bool, KzgError> {
+    let commitment = kzg_settings
+        .subgroup_check
+        .g1_from_bytes(commitment_bytes)?;
+    let proof = kzg_settings.subgroup_check.g1_from_bytes(proof_bytes)?;
+    let polynomial = blob.to_polynomial()?;
+    let z = compute_binding_challenge(context, blob, commitment_bytes);
+    let y = polynomial.evaluate(z, kzg_settings)?;
+    verify_kzg_proof_impl(commitment, z, y, proof, kzg_settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg_proof::tests::{BlobInput, Test};
+    use crate::test_files::VERIFY_BLOB_KZG_PROOF_TESTS;
+
+    #[test]
+    fn test_blob_binding_proof() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        // Constant blobs have an identity proof at every point, so skip them
+        let identity = Bytes48::from(bls12_381::G1Affine::identity().to_compressed());
+        let mut blobs = VERIFY_BLOB_KZG_PROOF_TESTS.iter().filter_map(|(_, data)| {
+            let test: Test<BlobInput> = serde_yaml::from_str(data).unwrap();
+            match (
+                test.input.get_blob(),
+                test.input.get_commitment(),
+                test.input.get_proof(),
+                test.get_output(),
+            ) {
+                (Ok(blob), Ok(commitment), Ok(proof), Some(true))
+                    if proof.as_slice() != identity.as_slice() =>
+                {
+                    Some((Blob::boxed_from_slice(blob.as_slice()).unwrap(), commitment))
+                }
+                _ => None,
+            }
+        });
+        let (blob, commitment) = blobs.next().unwrap();
+        let (other_blob, _) = blobs
+            .find(|(b, _)| b.as_slice() != blob.as_slice())
+            .unwrap();
+
+        let context = b"block 0x1234";
+        let proof = compute_blob_binding_proof(context, &blob, &commitment, &kzg_settings).unwrap();
+        assert!(
+            verify_blob_binding_proof(context, &blob, &commitment, &proof, &kzg_settings).unwrap()
+        );
+
+        // A different context, or a different blob served for the same commitment, is rejected
+        assert!(!verify_blob_binding_proof(
+            b"block 0x1235",
+            &blob,
+            &commitment,
+            &proof,
+            &kzg_settings
+        )
+        .unwrap());
+        assert!(!verify_blob_binding_proof(
+            context,
+            &other_blob,
+            &commitment,
+            &proof,
+            &kzg_settings
+        )
+        .unwrap());
+    }
+}
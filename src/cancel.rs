@@ -0,0 +1,41 @@
+use crate::enums::KzgError;
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Cooperative cancellation flag checked by long-running batch operations between chunks.
+///
+/// Cancelling does not interrupt the chunk currently being processed; the operation returns
+/// [`KzgError::Cancelled`] as soon as it reaches the next check.
+#[derive(Debug, Default)]
+#[repr(transparent)]
+pub struct CancelToken(AtomicBool);
+
+impl CancelToken {
+    pub const fn new() -> Self {
+        Self(AtomicBool::new(false))
+    }
+
+    /// Requests cancellation of every operation observing this token
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Returns [`KzgError::Cancelled`] if cancellation has been requested
+    pub fn check(&self) -> Result<(), KzgError> {
+        if self.is_cancelled() {
+            return Err(KzgError::Cancelled);
+        }
+        Ok(())
+    }
+}
+
+impl From<&AtomicBool> for &CancelToken {
+    fn from(flag: &AtomicBool) -> Self {
+        // SAFETY: `CancelToken` is a transparent wrapper around `AtomicBool`
+        unsafe { &*(flag as *const AtomicBool as *const CancelToken) }
+    }
+}
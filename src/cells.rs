@@ -0,0 +1,767 @@
+//! EIP-7594 (PeerDAS) cells.
+//!
+//! A blob is extended to twice its size by evaluating its polynomial over the roots of unity of
+//! order [`FIELD_ELEMENTS_PER_EXT_BLOB`]. In bit-reversed order these evaluations split into
+//! [`CELLS_PER_EXT_BLOB`](crate::CELLS_PER_EXT_BLOB) cosets of [`FIELD_ELEMENTS_PER_CELL`] points, one per cell, and the
+//! first half of the cells holds the blob itself.
+//!
+//! The proof for a cell opens the blob's polynomial at all points of its coset at once: it is the
+//! commitment to the quotient of the polynomial by `X^64 - h^64`, the vanishing polynomial of the
+//! coset `h·H`. [`verify_cell_kzg_proof_batch`] checks many such proofs with a single pairing
+//! check on a random linear combination.
+//!
+//! Any half of the cells determines the blob, since its polynomial has degree below half the
+//! size of the extended domain. [`recover_cells_and_kzg_proofs`] rebuilds the rest from them.
+
+use crate::cancel::CancelToken;
+use crate::compression::{decompress_g1_batch, normalize_g1_batch};
+use crate::dtypes::*;
+use crate::enums::KzgError;
+use crate::fft::{
+    bit_reversal_permutation, coset_inverse_fft, extend_coefficients, EvaluationDomain,
+};
+use crate::fk20::compute_fk20_cell_proofs;
+use crate::kzg_proof::{
+    safe_scalar_affine_from_bytes, scalar_from_bytes_unchecked, scalar_to_bytes32,
+};
+use crate::msm::g1_msm_affine;
+use crate::multiproof::vanishing_polynomial;
+use crate::progress::Progress;
+use crate::recovery::recover_with_vanishing;
+use crate::trusted_setup::{pairings_verify_cached, KzgSettings};
+use crate::utils::compute_powers;
+use crate::{
+    BYTES_PER_BLOB, BYTES_PER_CELL, BYTES_PER_COMMITMENT, BYTES_PER_FIELD_ELEMENT, BYTES_PER_PROOF,
+    CELLS_PER_EXT_BLOB, FIELD_ELEMENTS_PER_CELL, FIELD_ELEMENTS_PER_EXT_BLOB,
+    NUM_FIELD_ELEMENTS_PER_BLOB,
+};
+
+use alloc::{boxed::Box, string::ToString, vec::Vec};
+use bls12_381::Scalar;
+use sha2::{Digest, Sha256};
+
+/// Splits `blob` into the [`CELLS_PER_EXT_BLOB`](crate::CELLS_PER_EXT_BLOB) cells of its extension
+pub fn compute_cells(blob: &Blob, kzg_settings: &KzgSettings) -> Result<Vec<Cell>, KzgError> {
+    check_settings(kzg_settings)?;
+    cells_from_coefficients(&blob_to_coefficients(blob)?)
+}
+
+/// Splits `blob` into the cells of its extension and computes the proof of each cell
+pub fn compute_cells_and_kzg_proofs(
+    blob: &Blob,
+    kzg_settings: &KzgSettings,
+) -> Result<(Vec<Cell>, Vec<Bytes48>), KzgError> {
+    check_settings(kzg_settings)?;
+    let coefficients = blob_to_coefficients(blob)?;
+    let cells = cells_from_coefficients(&coefficients)?;
+    let proofs = compute_cell_proofs(&coefficients, kzg_settings, &mut |_| {}, None)?;
+    Ok((cells, proofs))
+}
+
+/// Assembles the blob whose extension starts with `cells`, the first
+/// [`CELLS_PER_EXT_BLOB`](crate::CELLS_PER_EXT_BLOB)` / 2` cells of the extension or all of them.
+/// In bit-reversed order the first half of the extension is the blob itself, so this only copies
+/// bytes, without checking that the remaining cells match.
+pub fn cells_to_blob(cells: &[Cell]) -> Result<Box<Blob>, KzgError> {
+    if cells.len() != CELLS_PER_EXT_BLOB / 2 && cells.len() != CELLS_PER_EXT_BLOB {
+        return Err(KzgError::InvalidBytesLength(format!(
+            "A blob is assembled from its first {} cells, got {}",
+            CELLS_PER_EXT_BLOB / 2,
+            cells.len()
+        )));
+    }
+    let mut bytes = Vec::with_capacity(BYTES_PER_BLOB);
+    for cell in &cells[..CELLS_PER_EXT_BLOB / 2] {
+        bytes.extend_from_slice(cell.as_slice());
+    }
+    Blob::boxed_from_slice(&bytes)
+}
+
+/// Splits an extended blob, its [`FIELD_ELEMENTS_PER_EXT_BLOB`] field elements in bit-reversed
+/// order like a blob, into its cells
+pub fn extended_blob_to_cells(extended: &[u8]) -> Result<Vec<Cell>, KzgError> {
+    if extended.len() != CELLS_PER_EXT_BLOB * BYTES_PER_CELL {
+        return Err(KzgError::InvalidBytesLength(format!(
+            "An extended blob is {} bytes, got {}",
+            CELLS_PER_EXT_BLOB * BYTES_PER_CELL,
+            extended.len()
+        )));
+    }
+    extended
+        .chunks(BYTES_PER_CELL)
+        .map(Cell::from_slice)
+        .collect()
+}
+
+/// Splits the evaluations of a polynomial over the extended domain, in natural order, into
+/// cells, bit-reversing them first
+pub fn extended_evaluations_to_cells(evaluations: &[Scalar]) -> Result<Vec<Cell>, KzgError> {
+    if evaluations.len() != FIELD_ELEMENTS_PER_EXT_BLOB {
+        return Err(KzgError::InvalidBytesLength(format!(
+            "The extended domain has {} points, got {} evaluations",
+            FIELD_ELEMENTS_PER_EXT_BLOB,
+            evaluations.len()
+        )));
+    }
+    Ok(bit_reversed_evaluations_to_cells(
+        &bit_reversal_permutation(evaluations),
+    ))
+}
+
+/// Returns the points of the extended domain where the cell at `index` holds the evaluations,
+/// in the order of its field elements. They are the coset `h·H` of the roots of unity `H` of
+/// order [`FIELD_ELEMENTS_PER_CELL`], with `h` the first point.
+pub fn cell_coset(index: CellIndex) -> Result<&'static [Scalar], KzgError> {
+    let start = Cell::check_index(index)? * FIELD_ELEMENTS_PER_CELL;
+    Ok(&extended_roots_of_unity()[start..start + FIELD_ELEMENTS_PER_CELL])
+}
+
+/// Rebuilds every cell of an extended blob and their proofs from at least half of the cells,
+/// given with their indices in any order
+pub fn recover_cells_and_kzg_proofs(
+    cell_indices: &[CellIndex],
+    cells: &[Cell],
+    kzg_settings: &KzgSettings,
+) -> Result<(Vec<Cell>, Vec<Bytes48>), KzgError> {
+    recover_cells_and_kzg_proofs_controlled(cell_indices, cells, kzg_settings, &mut |_| {}, None)
+}
+
+/// Same as [`recover_cells_and_kzg_proofs`], calling `on_progress` as the proofs are computed,
+/// [`CELLS_PER_EXT_BLOB`](crate::CELLS_PER_EXT_BLOB) times in all.
+pub fn recover_cells_and_kzg_proofs_with_progress(
+    cell_indices: &[CellIndex],
+    cells: &[Cell],
+    kzg_settings: &KzgSettings,
+    mut on_progress: impl FnMut(Progress),
+) -> Result<(Vec<Cell>, Vec<Bytes48>), KzgError> {
+    recover_cells_and_kzg_proofs_controlled(
+        cell_indices,
+        cells,
+        kzg_settings,
+        &mut on_progress,
+        None,
+    )
+}
+
+/// Same as [`recover_cells_and_kzg_proofs`], returning [`KzgError::Cancelled`] once `cancel`
+/// is triggered. The token is checked before the recovery and before each proof.
+pub fn recover_cells_and_kzg_proofs_with_cancel(
+    cell_indices: &[CellIndex],
+    cells: &[Cell],
+    kzg_settings: &KzgSettings,
+    cancel: &CancelToken,
+) -> Result<(Vec<Cell>, Vec<Bytes48>), KzgError> {
+    recover_cells_and_kzg_proofs_controlled(
+        cell_indices,
+        cells,
+        kzg_settings,
+        &mut |_| {},
+        Some(cancel),
+    )
+}
+
+fn recover_cells_and_kzg_proofs_controlled(
+    cell_indices: &[CellIndex],
+    cells: &[Cell],
+    kzg_settings: &KzgSettings,
+    on_progress: &mut dyn FnMut(Progress),
+    cancel: Option<&CancelToken>,
+) -> Result<(Vec<Cell>, Vec<Bytes48>), KzgError> {
+    if cell_indices.len() != cells.len() {
+        return Err(KzgError::InvalidBytesLength(
+            "The numbers of cell indices and cells differ".to_string(),
+        ));
+    }
+    kzg_settings
+        .limits
+        .check_cells(cells.len(), cells.len().saturating_mul(BYTES_PER_CELL))?;
+    check_settings(kzg_settings)?;
+    if let Some(cancel) = cancel {
+        cancel.check()?;
+    }
+
+    let mut present = [false; CELLS_PER_EXT_BLOB];
+    for &index in cell_indices {
+        let index = Cell::check_index(index)?;
+        if core::mem::replace(&mut present[index], true) {
+            return Err(KzgError::BadArgs(format!("Cell {} is given twice", index)));
+        }
+    }
+    if cells.len() < CELLS_PER_EXT_BLOB / 2 {
+        return Err(KzgError::BadArgs(format!(
+            "At least {} cells are needed for recovery, got {}",
+            CELLS_PER_EXT_BLOB / 2,
+            cells.len()
+        )));
+    }
+
+    // Missing cells are left at zero in the extended evaluations
+    let mut extended = vec![Scalar::zero(); FIELD_ELEMENTS_PER_EXT_BLOB];
+    for (&index, cell) in cell_indices.iter().zip(cells) {
+        let start = index as usize * FIELD_ELEMENTS_PER_CELL;
+        for (value, bytes) in extended[start..].iter_mut().zip(cell.field_elements()) {
+            *value = safe_scalar_affine_from_bytes(&Bytes32::from_slice(bytes)?)?;
+        }
+    }
+    let missing = (0..CELLS_PER_EXT_BLOB)
+        .filter(|&index| !present[index])
+        .collect::<Vec<_>>();
+
+    let coefficients = recover_coefficients(&bit_reversal_permutation(&extended), &missing)?;
+    let cells = cells_from_coefficients(&coefficients)?;
+    let proofs = compute_cell_proofs(&coefficients, kzg_settings, on_progress, cancel)?;
+    Ok((cells, proofs))
+}
+
+/// Returns the coefficients of the blob polynomial whose evaluations over the extended domain,
+/// in natural order, are `extended` except on the cosets of the `missing` cells, where `extended`
+/// is zero. Same as [`crate::recovery::recover_polynomial`] with a vanishing polynomial built
+/// from the cosets instead of the points.
+fn recover_coefficients(extended: &[Scalar], missing: &[usize]) -> Result<Vec<Scalar>, KzgError> {
+    // Z(X) = Π (X^64 - h_i^64) over the missing cosets h_i·H, where h_i^64 are 128-th roots of
+    // unity, so Z is a polynomial in X^64
+    let cell_roots = &EvaluationDomain::get(CELLS_PER_EXT_BLOB)?.bit_reversed_roots;
+    let short_vanishing = vanishing_polynomial(
+        &missing
+            .iter()
+            .map(|&index| cell_roots[index])
+            .collect::<Vec<_>>(),
+    );
+    let mut vanishing = vec![Scalar::zero(); FIELD_ELEMENTS_PER_EXT_BLOB];
+    for (i, coefficient) in short_vanishing.into_iter().enumerate() {
+        vanishing[i * FIELD_ELEMENTS_PER_CELL] = coefficient;
+    }
+
+    // The cells of a blob extend a polynomial of degree below NUM_FIELD_ELEMENTS_PER_BLOB
+    recover_with_vanishing(extended, &vanishing, NUM_FIELD_ELEMENTS_PER_BLOB).map_err(|_| {
+        KzgError::BadArgs("The cells do not belong to the extension of a blob".to_string())
+    })
+}
+
+/// Computes the proof of every cell of the extension of the polynomial with the given
+/// coefficients, with FK20. `on_progress` reports the FK20 MSMs, one per cell.
+fn compute_cell_proofs(
+    coefficients: &[Scalar],
+    kzg_settings: &KzgSettings,
+    on_progress: &mut dyn FnMut(Progress),
+    cancel: Option<&CancelToken>,
+) -> Result<Vec<Bytes48>, KzgError> {
+    compute_fk20_cell_proofs(
+        coefficients,
+        FIELD_ELEMENTS_PER_CELL,
+        kzg_settings,
+        on_progress,
+        cancel,
+    )
+}
+
+/// Verifies that each cell, at its index in the extension of the blob committed to by the
+/// matching commitment, is opened by the matching proof.
+///
+/// All proofs are checked at once: with random `r`, the proofs `π_k` of cells with coset shifts
+/// `h_k` and interpolation polynomials `I_k` must satisfy
+/// `e(Σ r^k π_k, [τ^64]₂) = e(Σ r^k (C_k - [I_k(τ)]₁ + h_k^64 π_k), G2)`.
+pub fn verify_cell_kzg_proof_batch(
+    commitments_bytes: &[Bytes48],
+    cell_indices: &[CellIndex],
+    cells: &[Cell],
+    proofs_bytes: &[Bytes48],
+    kzg_settings: &KzgSettings,
+) -> Result<bool, KzgError> {
+    let n = cells.len();
+    if commitments_bytes.len() != n || cell_indices.len() != n || proofs_bytes.len() != n {
+        return Err(KzgError::InvalidBytesLength(
+            "The numbers of commitments, cell indices, cells and proofs differ".to_string(),
+        ));
+    }
+    let total_bytes = n.saturating_mul(BYTES_PER_CELL + BYTES_PER_COMMITMENT + BYTES_PER_PROOF);
+    kzg_settings.limits.check_cells(n, total_bytes)?;
+    check_settings(kzg_settings)?;
+    if kzg_settings.g2_points.len() <= FIELD_ELEMENTS_PER_CELL {
+        return Err(KzgError::BadArgs(format!(
+            "Cell proofs need {} G2 points",
+            FIELD_ELEMENTS_PER_CELL + 1
+        )));
+    }
+    if n == 0 {
+        return Ok(true);
+    }
+
+    let cell_indices = cell_indices
+        .iter()
+        .map(|&index| Cell::check_index(index))
+        .collect::<Result<Vec<_>, _>>()?;
+    let evaluations = cells
+        .iter()
+        .map(|cell| {
+            cell.field_elements()
+                .map(|bytes| safe_scalar_affine_from_bytes(&Bytes32::from_slice(bytes)?))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let proofs = decompress_g1_batch(proofs_bytes, kzg_settings.subgroup_check)?;
+
+    // Commitments are deduplicated, keeping the order in which they first appear
+    let mut unique_commitments: Vec<Bytes48> = Vec::new();
+    let commitment_indices = commitments_bytes
+        .iter()
+        .map(|commitment| {
+            match unique_commitments
+                .iter()
+                .position(|unique| unique.as_slice() == commitment.as_slice())
+            {
+                Some(position) => position,
+                None => {
+                    unique_commitments.push(commitment.clone());
+                    unique_commitments.len() - 1
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+    let commitments = decompress_g1_batch(&unique_commitments, kzg_settings.subgroup_check)?;
+
+    // Fiat-Shamir challenge over every input
+    let mut hasher = Sha256::new();
+    hasher.update(kzg_settings.domain_separators.cell_batch);
+    hasher.update((NUM_FIELD_ELEMENTS_PER_BLOB as u64).to_be_bytes());
+    hasher.update((FIELD_ELEMENTS_PER_CELL as u64).to_be_bytes());
+    hasher.update((commitments.len() as u64).to_be_bytes());
+    hasher.update((n as u64).to_be_bytes());
+    for commitment in &unique_commitments {
+        hasher.update(commitment.as_slice());
+    }
+    for k in 0..n {
+        hasher.update((commitment_indices[k] as u64).to_be_bytes());
+        hasher.update((cell_indices[k] as u64).to_be_bytes());
+        hasher.update(cells[k].as_slice());
+        hasher.update(proofs_bytes[k].as_slice());
+    }
+    let r = scalar_from_bytes_unchecked(hasher.finalize().into());
+    let r_powers = compute_powers(&r, n);
+
+    let roots = extended_roots_of_unity();
+    let mut commitment_weights = vec![Scalar::zero(); commitments.len()];
+    let mut interpolation = vec![Scalar::zero(); FIELD_ELEMENTS_PER_CELL];
+    let mut proof_weights = Vec::with_capacity(n);
+    for k in 0..n {
+        commitment_weights[commitment_indices[k]] += r_powers[k];
+
+        let shift = roots[cell_indices[k] * FIELD_ELEMENTS_PER_CELL];
+        for (sum, coefficient) in interpolation
+            .iter_mut()
+            .zip(interpolate_coset(&evaluations[k], shift)?)
+        {
+            *sum += r_powers[k] * coefficient;
+        }
+
+        let shift_power = shift.pow_vartime(&[FIELD_ELEMENTS_PER_CELL as u64, 0, 0, 0]);
+        proof_weights.push(r_powers[k] * shift_power);
+    }
+
+    let monomial = &kzg_settings.g1_monomial()?[..FIELD_ELEMENTS_PER_CELL];
+
+    let lhs = g1_msm_affine(&proofs, &r_powers);
+    let rhs = g1_msm_affine(&commitments, &commitment_weights)
+        - g1_msm_affine(monomial, &interpolation)
+        + g1_msm_affine(&proofs, &proof_weights);
+
+    let sides = normalize_g1_batch(&[lhs, rhs]);
+    Ok(pairings_verify_cached(
+        sides[0],
+        kzg_settings.g2_points[FIELD_ELEMENTS_PER_CELL],
+        sides[1],
+        kzg_settings.g2_points[0],
+    ))
+}
+
+/// Returns the roots of unity of order [`FIELD_ELEMENTS_PER_EXT_BLOB`] in bit-reversed order, the
+/// points of the cells one after the other
+pub(crate) fn extended_roots_of_unity() -> &'static [Scalar] {
+    &EvaluationDomain::get(FIELD_ELEMENTS_PER_EXT_BLOB)
+        .expect("the order is a power of two")
+        .bit_reversed_roots
+}
+
+pub(crate) fn check_settings(kzg_settings: &KzgSettings) -> Result<(), KzgError> {
+    if kzg_settings.field_elements_per_blob() != NUM_FIELD_ELEMENTS_PER_BLOB {
+        return Err(KzgError::BadArgs(format!(
+            "Cells need a {}-point setup, got {} points",
+            NUM_FIELD_ELEMENTS_PER_BLOB,
+            kzg_settings.field_elements_per_blob()
+        )));
+    }
+    Ok(())
+}
+
+fn cells_from_coefficients(coefficients: &[Scalar]) -> Result<Vec<Cell>, KzgError> {
+    Ok(bit_reversed_evaluations_to_cells(&extend_coefficients(
+        coefficients,
+    )?))
+}
+
+fn bit_reversed_evaluations_to_cells(evaluations: &[Scalar]) -> Vec<Cell> {
+    evaluations
+        .chunks(FIELD_ELEMENTS_PER_CELL)
+        .map(|chunk| {
+            let mut bytes = [0u8; BYTES_PER_CELL];
+            for (element, value) in bytes.chunks_mut(BYTES_PER_FIELD_ELEMENT).zip(chunk) {
+                element.copy_from_slice(scalar_to_bytes32(value).as_slice());
+            }
+            Cell::from(bytes)
+        })
+        .collect()
+}
+
+/// Returns the monomial coefficients of the polynomial of `blob`, lowest degree first
+pub(crate) fn blob_to_coefficients(blob: &Blob) -> Result<Vec<Scalar>, KzgError> {
+    Ok(blob.to_polynomial()?.to_coefficient_form()?.into_values())
+}
+
+/// Returns the coefficients of the polynomial of degree below [`FIELD_ELEMENTS_PER_CELL`] that
+/// takes the given values on the coset `shift·H`, listed in bit-reversed order like a cell
+fn interpolate_coset(evaluations: &[Scalar], shift: Scalar) -> Result<Vec<Scalar>, KzgError> {
+    coset_inverse_fft(&bit_reversal_permutation(evaluations), shift)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fft::expand_root_of_unity;
+    use crate::field::FieldElement;
+    use crate::kzg_proof::tests::{BlobInput, Test};
+    use crate::kzg_proof::{evaluate_polynomial_in_evaluation_form, safe_scalar_affine_from_bytes};
+    use crate::msm::g1_msm;
+    use crate::multiproof::verify_kzg_multiproof;
+    use bls12_381::{G1Affine, G1Projective};
+
+    /// Computes the proof of the cell on the coset `shift·H` directly, by dividing by the
+    /// coset's vanishing polynomial
+    fn compute_cell_proof(
+        coefficients: &[Scalar],
+        shift: Scalar,
+        monomial: &[G1Projective],
+    ) -> Bytes48 {
+        let shift = shift.pow_vartime(&[FIELD_ELEMENTS_PER_CELL as u64, 0, 0, 0]);
+        let quotient = divide_by_coset_vanishing(coefficients, shift);
+        let proof = g1_msm(&monomial[..quotient.len()], &quotient);
+        Bytes48::from(G1Affine::from(proof).to_compressed())
+    }
+
+    /// Returns the quotient of the polynomial with the given coefficients by
+    /// `X^FIELD_ELEMENTS_PER_CELL - shift`, dropping the remainder
+    fn divide_by_coset_vanishing(coefficients: &[Scalar], shift: Scalar) -> Vec<Scalar> {
+        let m = FIELD_ELEMENTS_PER_CELL;
+        if coefficients.len() <= m {
+            return Vec::new();
+        }
+        // p[k + m] = q[k] - shift * q[k + m], from the highest coefficient down
+        let mut quotient = vec![Scalar::zero(); coefficients.len() - m];
+        for k in (0..quotient.len()).rev() {
+            let carry = quotient.get(k + m).map_or(Scalar::zero(), |q| shift * q);
+            quotient[k] = coefficients[k + m] + carry;
+        }
+        quotient
+    }
+
+    #[test]
+    fn test_compute_cells() {
+        let data = include_str!("../tests/verify_blob_kzg_proof/verify_blob_kzg_proof_case_correct_proof_fb324bc819407148/data.yaml");
+        let test: Test<BlobInput> = serde_yaml::from_str(data).unwrap();
+        let blob = test.input.get_blob().unwrap();
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+
+        let cells = compute_cells(&blob, &kzg_settings).unwrap();
+        assert_eq!(cells.len(), CELLS_PER_EXT_BLOB);
+
+        // The first half of the extension is the blob itself
+        let original = cells[..CELLS_PER_EXT_BLOB / 2]
+            .iter()
+            .flat_map(|cell| cell.as_slice().iter().copied())
+            .collect::<Vec<_>>();
+        assert_eq!(original, blob.as_slice());
+
+        // The second half holds evaluations outside the blob's domain
+        let roots =
+            bit_reversal_permutation(&expand_root_of_unity(FIELD_ELEMENTS_PER_EXT_BLOB).unwrap());
+        let polynomial = blob.as_polynomial().unwrap();
+        for index in [CELLS_PER_EXT_BLOB / 2, CELLS_PER_EXT_BLOB - 1] {
+            let position = index * FIELD_ELEMENTS_PER_CELL + 3;
+            let expected =
+                evaluate_polynomial_in_evaluation_form(&polynomial, roots[position], &kzg_settings)
+                    .unwrap();
+            let actual = cells[index].field_elements().nth(3).unwrap();
+            assert_eq!(
+                safe_scalar_affine_from_bytes(&Bytes32::from_slice(actual).unwrap()).unwrap(),
+                expected
+            );
+        }
+
+        let mut small = kzg_settings.clone();
+        small.roots_of_unity = &kzg_settings.roots_of_unity[..8];
+        assert!(compute_cells(&blob, &small).is_err());
+    }
+
+    #[test]
+    fn test_cells_blob_conversion() {
+        let data = include_str!("../tests/verify_blob_kzg_proof/verify_blob_kzg_proof_case_correct_proof_fb324bc819407148/data.yaml");
+        let test: Test<BlobInput> = serde_yaml::from_str(data).unwrap();
+        let blob = test.input.get_blob().unwrap();
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let cells = compute_cells(&blob, &kzg_settings).unwrap();
+
+        let half = &cells[..CELLS_PER_EXT_BLOB / 2];
+        assert_eq!(cells_to_blob(half).unwrap().as_slice(), blob.as_slice());
+        assert_eq!(cells_to_blob(&cells).unwrap().as_slice(), blob.as_slice());
+        assert!(cells_to_blob(&cells[..10]).is_err());
+
+        let extended = cells
+            .iter()
+            .flat_map(|cell| cell.as_slice().iter().copied())
+            .collect::<Vec<_>>();
+        let split = extended_blob_to_cells(&extended).unwrap();
+        assert_eq!(split[77].as_slice(), cells[77].as_slice());
+        assert!(extended_blob_to_cells(&extended[1..]).is_err());
+
+        // Natural order evaluations of the same extension
+        let mut coefficients = blob_to_coefficients(&blob).unwrap();
+        coefficients.resize(FIELD_ELEMENTS_PER_EXT_BLOB, Scalar::zero());
+        let evaluations = crate::fft::fft(&coefficients).unwrap();
+        let from_evaluations = extended_evaluations_to_cells(&evaluations).unwrap();
+        assert_eq!(from_evaluations[100].as_slice(), cells[100].as_slice());
+
+        // The cell holds the evaluations at its coset's points
+        let coset = cell_coset(100).unwrap();
+        let polynomial = blob.as_polynomial().unwrap();
+        let expected =
+            evaluate_polynomial_in_evaluation_form(&polynomial, coset[5], &kzg_settings).unwrap();
+        let actual = cells[100].field_elements().nth(5).unwrap();
+        assert_eq!(
+            safe_scalar_affine_from_bytes(&Bytes32::from_slice(actual).unwrap()).unwrap(),
+            expected
+        );
+        assert!(cell_coset(CELLS_PER_EXT_BLOB as CellIndex).is_err());
+    }
+
+    #[test]
+    fn test_compute_cells_and_kzg_proofs() {
+        let data = include_str!("../tests/verify_blob_kzg_proof/verify_blob_kzg_proof_case_correct_proof_fb324bc819407148/data.yaml");
+        let test: Test<BlobInput> = serde_yaml::from_str(data).unwrap();
+        let blob = test.input.get_blob().unwrap();
+        let commitment = test.input.get_commitment().unwrap();
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+
+        let (cells, proofs) = compute_cells_and_kzg_proofs(&blob, &kzg_settings).unwrap();
+        assert_eq!(proofs.len(), CELLS_PER_EXT_BLOB);
+        assert!(verify_cell_kzg_proof_batch(
+            &vec![commitment.clone(); CELLS_PER_EXT_BLOB],
+            &(0..CELLS_PER_EXT_BLOB as CellIndex).collect::<Vec<_>>(),
+            &cells,
+            &proofs,
+            &kzg_settings
+        )
+        .unwrap());
+        assert_eq!(
+            cells[5].as_slice(),
+            compute_cells(&blob, &kzg_settings).unwrap()[5].as_slice()
+        );
+
+        // The FK20 proofs are the commitments to the quotients by the cosets' vanishing
+        // polynomials
+        let coefficients = blob_to_coefficients(&blob).unwrap();
+        let monomial = kzg_settings
+            .g1_monomial()
+            .unwrap()
+            .iter()
+            .map(G1Projective::from)
+            .collect::<Vec<_>>();
+        let extended = extended_roots_of_unity();
+        for index in [1, 64, CELLS_PER_EXT_BLOB - 2] {
+            let shift = extended[index * FIELD_ELEMENTS_PER_CELL];
+            assert_eq!(
+                proofs[index].as_slice(),
+                compute_cell_proof(&coefficients, shift, &monomial).as_slice()
+            );
+        }
+
+        // Each proof opens the blob at the whole coset of its cell
+        let roots =
+            bit_reversal_permutation(&expand_root_of_unity(FIELD_ELEMENTS_PER_EXT_BLOB).unwrap());
+        for index in [0, 77, CELLS_PER_EXT_BLOB - 1] {
+            let points = roots[index * FIELD_ELEMENTS_PER_CELL..][..FIELD_ELEMENTS_PER_CELL]
+                .iter()
+                .map(|&root| FieldElement::from(root))
+                .collect::<Vec<_>>();
+            let values = cells[index]
+                .field_elements()
+                .map(|bytes| {
+                    FieldElement::from_bytes(&Bytes32::from_slice(bytes).unwrap()).unwrap()
+                })
+                .collect::<Vec<_>>();
+            assert!(verify_kzg_multiproof(
+                &commitment,
+                &points,
+                &values,
+                &proofs[index],
+                &kzg_settings
+            )
+            .unwrap());
+            assert!(!verify_kzg_multiproof(
+                &commitment,
+                &points,
+                &values,
+                &proofs[(index + 1) % CELLS_PER_EXT_BLOB],
+                &kzg_settings
+            )
+            .unwrap());
+        }
+    }
+
+    #[test]
+    fn test_verify_cell_kzg_proof_batch() {
+        let data = include_str!("../tests/verify_blob_kzg_proof/verify_blob_kzg_proof_case_correct_proof_fb324bc819407148/data.yaml");
+        let test: Test<BlobInput> = serde_yaml::from_str(data).unwrap();
+        let blob = test.input.get_blob().unwrap();
+        let commitment = test.input.get_commitment().unwrap();
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+
+        // Cells from the first half of the extension are the blob itself, and are opened by
+        // proofs computed here from the quotient by the coset's vanishing polynomial
+        let coefficients = blob_to_coefficients(&blob).unwrap();
+        let cells = cells_from_coefficients(&coefficients).unwrap();
+        let monomial = kzg_settings
+            .g1_monomial()
+            .unwrap()
+            .iter()
+            .map(G1Projective::from)
+            .collect::<Vec<_>>();
+        let roots =
+            bit_reversal_permutation(&expand_root_of_unity(FIELD_ELEMENTS_PER_EXT_BLOB).unwrap());
+        let indices: [CellIndex; 3] = [3, 3, 100];
+        let proofs = indices
+            .iter()
+            .map(|&index| {
+                let shift = roots[index as usize * FIELD_ELEMENTS_PER_CELL];
+                compute_cell_proof(&coefficients, shift, &monomial)
+            })
+            .collect::<Vec<_>>();
+        let selected = indices
+            .iter()
+            .map(|&index| cells[index as usize].clone())
+            .collect::<Vec<_>>();
+        let commitments = vec![commitment; indices.len()];
+
+        assert!(verify_cell_kzg_proof_batch(
+            &commitments,
+            &indices,
+            &selected,
+            &proofs,
+            &kzg_settings
+        )
+        .unwrap());
+        assert!(verify_cell_kzg_proof_batch(&[], &[], &[], &[], &kzg_settings).unwrap());
+
+        // A cell checked at another index fails
+        assert!(!verify_cell_kzg_proof_batch(
+            &commitments,
+            &[3, 4, 100],
+            &selected,
+            &proofs,
+            &kzg_settings
+        )
+        .unwrap());
+
+        let mut tampered = selected.clone();
+        let mut bytes: [u8; BYTES_PER_CELL] = tampered[2].clone().into();
+        bytes[31] ^= 1;
+        tampered[2] = Cell::from(bytes);
+        assert!(!verify_cell_kzg_proof_batch(
+            &commitments,
+            &indices,
+            &tampered,
+            &proofs,
+            &kzg_settings
+        )
+        .unwrap());
+
+        assert!(verify_cell_kzg_proof_batch(
+            &commitments,
+            &[3, 3, CELLS_PER_EXT_BLOB as CellIndex],
+            &selected,
+            &proofs,
+            &kzg_settings
+        )
+        .is_err());
+        assert!(verify_cell_kzg_proof_batch(
+            &commitments[1..],
+            &indices,
+            &selected,
+            &proofs,
+            &kzg_settings
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_recover_cells_and_kzg_proofs() {
+        let data = include_str!("../tests/verify_blob_kzg_proof/verify_blob_kzg_proof_case_correct_proof_fb324bc819407148/data.yaml");
+        let test: Test<BlobInput> = serde_yaml::from_str(data).unwrap();
+        let blob = test.input.get_blob().unwrap();
+        let commitment = test.input.get_commitment().unwrap();
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let expected = compute_cells(&blob, &kzg_settings).unwrap();
+
+        // Every other cell, given out of order
+        let indices = (0..CELLS_PER_EXT_BLOB as CellIndex)
+            .rev()
+            .filter(|index| index % 2 == 1)
+            .collect::<Vec<_>>();
+        let cells = indices
+            .iter()
+            .map(|&index| expected[index as usize].clone())
+            .collect::<Vec<_>>();
+        let mut updates = 0;
+        let (recovered, proofs) = recover_cells_and_kzg_proofs_with_progress(
+            &indices,
+            &cells,
+            &kzg_settings,
+            |progress| {
+                updates += 1;
+                assert_eq!(progress.total, CELLS_PER_EXT_BLOB);
+            },
+        )
+        .unwrap();
+        assert_eq!(updates, CELLS_PER_EXT_BLOB);
+        for (cell, expected) in recovered.iter().zip(&expected) {
+            assert_eq!(cell.as_slice(), expected.as_slice());
+        }
+        let checked: [CellIndex; 3] = [0, 64, 126];
+        assert!(verify_cell_kzg_proof_batch(
+            &[commitment.clone(), commitment.clone(), commitment],
+            &checked,
+            &checked.map(|index| recovered[index as usize].clone()),
+            &checked.map(|index| proofs[index as usize].clone()),
+            &kzg_settings
+        )
+        .unwrap());
+
+        assert!(recover_cells_and_kzg_proofs(&indices[1..], &cells[1..], &kzg_settings).is_err());
+        let mut duplicated = indices.clone();
+        duplicated[1] = duplicated[0];
+        assert!(recover_cells_and_kzg_proofs(&duplicated, &cells, &kzg_settings).is_err());
+        assert!(recover_cells_and_kzg_proofs(&indices, &cells[1..], &kzg_settings).is_err());
+
+        // With more than half of the cells, cells that do not extend a blob are rejected
+        let mut bytes: [u8; BYTES_PER_CELL] = expected[0].clone().into();
+        bytes[31] ^= 1;
+        let corrupted = [cells.as_slice(), &[bytes.into()]].concat();
+        let extra_indices = [indices.as_slice(), &[0]].concat();
+        assert!(recover_cells_and_kzg_proofs(&extra_indices, &corrupted, &kzg_settings).is_err());
+
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        assert!(matches!(
+            recover_cells_and_kzg_proofs_with_cancel(&indices, &cells, &kzg_settings, &cancel),
+            Err(KzgError::Cancelled)
+        ));
+    }
+}
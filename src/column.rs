@@ -0,0 +1,127 @@
+//! EIP-7594 (PeerDAS) data column sidecars.
+//!
+//! Consensus clients sample and gossip columns rather than single cells: a column holds, for each
+//! blob of a block, its cell at the column's index together with the blob's commitment and the
+//! cell's proof. [`verify_data_column_sidecar`] checks a whole column with one call to
+//! [`verify_cell_kzg_proof_batch`], and [`verify_data_column_sidecars`] merges several columns
+//! into a single batch.
+
+use crate::cells::verify_cell_kzg_proof_batch;
+use crate::dtypes::*;
+use crate::enums::KzgError;
+use crate::trusted_setup::KzgSettings;
+
+use alloc::{string::ToString, vec::Vec};
+
+/// The KZG part of a data column sidecar: the cells at `index` of every blob of a block, with
+/// the blobs' commitments and the cells' proofs, all in blob order
+#[derive(Debug, Clone)]
+pub struct DataColumnSidecar {
+    /// Index of the column, shared by all its cells
+    pub index: CellIndex,
+    pub column: Vec<Cell>,
+    pub kzg_commitments: Vec<Bytes48>,
+    pub kzg_proofs: Vec<Bytes48>,
+}
+
+impl DataColumnSidecar {
+    /// Checks that the sidecar is well formed: a valid column index and one commitment and proof
+    /// per cell
+    pub fn validate(&self) -> Result<(), KzgError> {
+        Cell::check_index(self.index)?;
+        if self.kzg_commitments.len() != self.column.len()
+            || self.kzg_proofs.len() != self.column.len()
+        {
+            return Err(KzgError::InvalidBytesLength(
+                "The numbers of cells, commitments and proofs of the column differ".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Verifies every cell of the column against its blob's commitment, see
+    /// [`verify_data_column_sidecar`]
+    pub fn verify(&self, kzg_settings: &KzgSettings) -> Result<bool, KzgError> {
+        verify_data_column_sidecar(self, kzg_settings)
+    }
+}
+
+/// Verifies every cell of `sidecar` against its blob's commitment with a single batched check.
+/// A malformed sidecar is an error, and a column without blobs is valid.
+pub fn verify_data_column_sidecar(
+    sidecar: &DataColumnSidecar,
+    kzg_settings: &KzgSettings,
+) -> Result<bool, KzgError> {
+    verify_data_column_sidecars(core::slice::from_ref(sidecar), kzg_settings)
+}
+
+/// Verifies the cells of all `sidecars` with a single batched check, which is cheaper than
+/// verifying each column on its own. Fails if any sidecar is malformed.
+pub fn verify_data_column_sidecars(
+    sidecars: &[DataColumnSidecar],
+    kzg_settings: &KzgSettings,
+) -> Result<bool, KzgError> {
+    for sidecar in sidecars {
+        sidecar.validate()?;
+    }
+
+    let total = sidecars.iter().map(|sidecar| sidecar.column.len()).sum();
+    let mut commitments = Vec::with_capacity(total);
+    let mut cell_indices = Vec::with_capacity(total);
+    let mut cells = Vec::with_capacity(total);
+    let mut proofs = Vec::with_capacity(total);
+    for sidecar in sidecars {
+        commitments.extend_from_slice(&sidecar.kzg_commitments);
+        cell_indices.extend(core::iter::repeat_n(sidecar.index, sidecar.column.len()));
+        cells.extend_from_slice(&sidecar.column);
+        proofs.extend_from_slice(&sidecar.kzg_proofs);
+    }
+    verify_cell_kzg_proof_batch(&commitments, &cell_indices, &cells, &proofs, kzg_settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cells::compute_cells_and_kzg_proofs;
+    use crate::kzg_proof::tests::{BlobInput, Test};
+
+    #[test]
+    fn test_verify_data_column_sidecar() {
+        let data = include_str!("../tests/verify_blob_kzg_proof/verify_blob_kzg_proof_case_correct_proof_fb324bc819407148/data.yaml");
+        let test: Test<BlobInput> = serde_yaml::from_str(data).unwrap();
+        let blob = test.input.get_blob().unwrap();
+        let commitment = test.input.get_commitment().unwrap();
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let (cells, proofs) = compute_cells_and_kzg_proofs(&blob, &kzg_settings).unwrap();
+
+        // A block with the same blob twice
+        let sidecar = |index: usize| DataColumnSidecar {
+            index: index as CellIndex,
+            column: vec![cells[index].clone(); 2],
+            kzg_commitments: vec![commitment.clone(); 2],
+            kzg_proofs: vec![proofs[index].clone(); 2],
+        };
+        assert!(sidecar(3).verify(&kzg_settings).unwrap());
+        assert!(verify_data_column_sidecars(&[sidecar(0), sidecar(127)], &kzg_settings).unwrap());
+
+        let mut wrong_index = sidecar(3);
+        wrong_index.index = 4;
+        assert!(!wrong_index.verify(&kzg_settings).unwrap());
+        assert!(!verify_data_column_sidecars(&[sidecar(0), wrong_index], &kzg_settings).unwrap());
+
+        let mut out_of_range = sidecar(3);
+        out_of_range.index = 128;
+        assert!(out_of_range.verify(&kzg_settings).is_err());
+        let mut missing_proof = sidecar(3);
+        missing_proof.kzg_proofs.pop();
+        assert!(missing_proof.verify(&kzg_settings).is_err());
+
+        let empty = DataColumnSidecar {
+            index: 0,
+            column: Vec::new(),
+            kzg_commitments: Vec::new(),
+            kzg_proofs: Vec::new(),
+        };
+        assert!(empty.verify(&kzg_settings).unwrap());
+    }
+}
@@ -0,0 +1,121 @@
+//! Bulk conversions between curve points and their compressed encodings.
+//!
+//! Projective points are normalized together with [`G1Projective::batch_normalize`], which
+//! shares a single field inversion across the whole batch, before being compressed. With the
+//! `parallel` feature the points are compressed and decompressed on the rayon thread pool, which
+//! matters most for decompression, dominated by a square root and the subgroup check per point.
+
+use crate::dtypes::Bytes48;
+use crate::enums::KzgError;
+use crate::subgroup::SubgroupCheck;
+use crate::BYTES_PER_G2_POINT;
+
+use alloc::vec::Vec;
+use bls12_381::{G1Affine, G1Projective, G2Affine, G2Projective};
+
+/// Compresses G1 points to their 48-byte encodings
+pub fn compress_g1_batch(points: &[G1Affine]) -> Vec<Bytes48> {
+    map_points(points, |point| Bytes48::from(point.to_compressed()))
+}
+
+/// Same as [`compress_g1_batch`] for projective points, normalized with a single inversion
+pub fn compress_g1_projective_batch(points: &[G1Projective]) -> Vec<Bytes48> {
+    compress_g1_batch(&normalize_g1_batch(points))
+}
+
+/// Converts projective points to affine with a single inversion, rather than one per point
+pub fn normalize_g1_batch(points: &[G1Projective]) -> Vec<G1Affine> {
+    let mut affine = vec![G1Affine::identity(); points.len()];
+    G1Projective::batch_normalize(points, &mut affine);
+    affine
+}
+
+/// Decompresses 48-byte encodings to G1 points, checking each with `subgroup_check`. Fails on the
+/// first invalid encoding.
+pub fn decompress_g1_batch(
+    bytes: &[Bytes48],
+    subgroup_check: SubgroupCheck,
+) -> Result<Vec<G1Affine>, KzgError> {
+    map_points(bytes, |bytes| subgroup_check.g1_from_bytes(bytes))
+        .into_iter()
+        .collect()
+}
+
+/// Compresses G2 points to their 96-byte encodings
+pub fn compress_g2_batch(points: &[G2Affine]) -> Vec<[u8; BYTES_PER_G2_POINT]> {
+    map_points(points, G2Affine::to_compressed)
+}
+
+/// Same as [`compress_g2_batch`] for projective points, normalized with a single inversion
+pub fn compress_g2_projective_batch(points: &[G2Projective]) -> Vec<[u8; BYTES_PER_G2_POINT]> {
+    let mut affine = vec![G2Affine::identity(); points.len()];
+    G2Projective::batch_normalize(points, &mut affine);
+    compress_g2_batch(&affine)
+}
+
+/// Decompresses 96-byte encodings to G2 points, checking each with `subgroup_check`. Fails on the
+/// first invalid encoding.
+pub fn decompress_g2_batch(
+    bytes: &[[u8; BYTES_PER_G2_POINT]],
+    subgroup_check: SubgroupCheck,
+) -> Result<Vec<G2Affine>, KzgError> {
+    map_points(bytes, |bytes| subgroup_check.g2_from_compressed(bytes))
+        .into_iter()
+        .collect()
+}
+
+/// Applies `f` to every item, on the rayon thread pool with the `parallel` feature
+fn map_points<T: Sync, U: Send>(items: &[T], f: impl Fn(&T) -> U + Sync + Send) -> Vec<U> {
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        items.par_iter().map(f).collect()
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    items.iter().map(f).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bls12_381::Scalar;
+
+    #[test]
+    fn test_compression_batches() {
+        let g1 = (1..=5u64)
+            .map(|i| G1Projective::generator() * Scalar::from(i))
+            .chain([G1Projective::identity()])
+            .collect::<Vec<_>>();
+        let bytes = compress_g1_projective_batch(&g1);
+        assert_eq!(normalize_g1_batch(&g1)[4], G1Affine::from(g1[4]));
+        assert_eq!(
+            bytes[2].as_slice(),
+            G1Affine::from(g1[2]).to_compressed().as_slice()
+        );
+        let points = decompress_g1_batch(&bytes, SubgroupCheck::default()).unwrap();
+        assert_eq!(
+            points.iter().map(G1Projective::from).collect::<Vec<_>>(),
+            g1
+        );
+        assert_eq!(
+            compress_g1_batch(&points)[5].as_slice(),
+            bytes[5].as_slice()
+        );
+
+        let mut invalid = bytes.clone();
+        invalid[3] = Bytes48::from([0xff; 48]);
+        assert!(decompress_g1_batch(&invalid, SubgroupCheck::default()).is_err());
+
+        let g2 = (1..=3u64)
+            .map(|i| G2Projective::generator() * Scalar::from(i))
+            .collect::<Vec<_>>();
+        let bytes = compress_g2_projective_batch(&g2);
+        let points = decompress_g2_batch(&bytes, SubgroupCheck::Skip).unwrap();
+        assert_eq!(
+            points.iter().map(G2Projective::from).collect::<Vec<_>>(),
+            g2
+        );
+        assert_eq!(compress_g2_batch(&points), bytes);
+    }
+}
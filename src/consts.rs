@@ -6,13 +6,31 @@ pub const NUM_G2_POINTS: usize = 65;
 pub const NUM_ROOTS_OF_UNITY: usize = 4096;
 pub const NUM_FIELD_ELEMENTS_PER_BLOB: usize = 4096;
 pub const BYTES_PER_BLOB: usize = NUM_FIELD_ELEMENTS_PER_BLOB * BYTES_PER_FIELD_ELEMENT;
+pub const PAYLOAD_BYTES_PER_FIELD_ELEMENT: usize = 31;
+pub const MAX_BLOB_PAYLOAD_LEN: usize =
+    NUM_FIELD_ELEMENTS_PER_BLOB * PAYLOAD_BYTES_PER_FIELD_ELEMENT;
+pub const FIELD_ELEMENTS_PER_EXT_BLOB: usize = 2 * NUM_FIELD_ELEMENTS_PER_BLOB;
+pub const FIELD_ELEMENTS_PER_CELL: usize = 64;
+pub const BYTES_PER_CELL: usize = FIELD_ELEMENTS_PER_CELL * BYTES_PER_FIELD_ELEMENT;
+pub const CELLS_PER_EXT_BLOB: usize = FIELD_ELEMENTS_PER_EXT_BLOB / FIELD_ELEMENTS_PER_CELL;
+/// Generator of the multiplicative group of the scalar field, used to shift FFT domains onto a
+/// disjoint coset
+pub const PRIMITIVE_ROOT_OF_UNITY: u64 = 7;
 pub const BYTES_PER_COMMITMENT: usize = 48;
 pub const BYTES_PER_PROOF: usize = 48;
 pub const DOMAIN_STR_LENGTH: usize = 16;
 pub const CHALLENGE_INPUT_SIZE: usize =
     DOMAIN_STR_LENGTH + 16 + BYTES_PER_BLOB + BYTES_PER_COMMITMENT;
+/// Stack budget of the public calls that take blobs by reference in optimized builds, enforced by
+/// the tests of the `bounded-stack` feature
+#[cfg(feature = "bounded-stack")]
+pub const MAX_STACK_USAGE: usize = 64 * 1024;
 pub const FIAT_SHAMIR_PROTOCOL_DOMAIN: &str = "FSBLOBVERIFY_V1_";
 pub const RANDOM_CHALLENGE_KZG_BATCH_DOMAIN: &str = "RCKZGBATCH___V1_";
+pub const RANDOM_CHALLENGE_KZG_CELL_BATCH_DOMAIN: &str = "RCKZGCBATCH__V1_";
+pub const RANDOM_CHALLENGE_KZG_STREAM_DOMAIN: &str = "RCKZGSTREAM__V1_";
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+pub const BLOB_BINDING_DOMAIN: &str = "BLOBBINDING__V1_";
 
 pub const SCALE2_ROOT_OF_UNITY: [[u64; 4]; 32] = [
     [
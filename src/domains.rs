@@ -0,0 +1,194 @@
+//! Fiat-Shamir domain separators, versioned by fork.
+//!
+//! Every challenge starts by hashing a 16-byte domain tag. The tags used by a [`KzgSettings`]
+//! are its [`KzgSettings::domain_separators`], those of the latest fork unless configured
+//! otherwise. A spec change to the tags becomes a new entry of [`DOMAIN_SEPARATORS`], which nodes
+//! select with [`KzgSettings::with_fork`] once the fork activates, and tags not yet in the
+//! registry can be set directly with [`KzgSettings::with_domain_separators`].
+
+use crate::enums::KzgError;
+use crate::trusted_setup::KzgSettings;
+use crate::{
+    DOMAIN_STR_LENGTH, FIAT_SHAMIR_PROTOCOL_DOMAIN, RANDOM_CHALLENGE_KZG_BATCH_DOMAIN,
+    RANDOM_CHALLENGE_KZG_CELL_BATCH_DOMAIN,
+};
+
+use core::str::FromStr;
+
+/// Network upgrades that define the domain separators of the challenges
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Fork {
+    /// EIP-4844 blobs, which introduced the blob proof and batch tags
+    Deneb,
+    /// PeerDAS, which introduced the cell batch tag
+    #[default]
+    Fulu,
+}
+
+impl Fork {
+    /// Every fork of the registry, oldest first
+    pub const ALL: [Fork; 2] = [Fork::Deneb, Fork::Fulu];
+
+    /// Returns the lowercase name of the fork, as accepted by [`Fork::from_str`]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Deneb => "deneb",
+            Self::Fulu => "fulu",
+        }
+    }
+
+    /// Returns the domain separators registered for this fork
+    pub fn domain_separators(self) -> DomainSeparators {
+        DOMAIN_SEPARATORS
+            .iter()
+            .find(|(fork, _)| *fork == self)
+            .map(|(_, separators)| *separators)
+            .expect("every fork is registered")
+    }
+}
+
+impl FromStr for Fork {
+    type Err = KzgError;
+
+    /// Parses a fork name, ignoring case
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|fork| fork.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| KzgError::BadArgs(format!("Unknown fork {}", name)))
+    }
+}
+
+/// The domain tags hashed first into each Fiat-Shamir challenge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DomainSeparators {
+    /// Tag of the evaluation challenge of a blob proof
+    pub blob_proof: [u8; DOMAIN_STR_LENGTH],
+    /// Tag of the random linear combination of a batch of KZG proofs
+    pub batch: [u8; DOMAIN_STR_LENGTH],
+    /// Tag of the random linear combination of a batch of cell proofs
+    pub cell_batch: [u8; DOMAIN_STR_LENGTH],
+}
+
+impl DomainSeparators {
+    /// The `V1` tags of the consensus specs
+    pub const V1: Self = Self {
+        blob_proof: tag(FIAT_SHAMIR_PROTOCOL_DOMAIN),
+        batch: tag(RANDOM_CHALLENGE_KZG_BATCH_DOMAIN),
+        cell_batch: tag(RANDOM_CHALLENGE_KZG_CELL_BATCH_DOMAIN),
+    };
+
+    /// Creates domain separators from tags read from configuration, each of which must be
+    /// exactly [`DOMAIN_STR_LENGTH`] bytes long
+    pub fn new(blob_proof: &str, batch: &str, cell_batch: &str) -> Result<Self, KzgError> {
+        let parse = |tag: &str| {
+            <[u8; DOMAIN_STR_LENGTH]>::try_from(tag.as_bytes()).map_err(|_| {
+                KzgError::InvalidBytesLength(format!(
+                    "Domain separator {:?} is {} bytes long, expected {}",
+                    tag,
+                    tag.len(),
+                    DOMAIN_STR_LENGTH
+                ))
+            })
+        };
+        Ok(Self {
+            blob_proof: parse(blob_proof)?,
+            batch: parse(batch)?,
+            cell_batch: parse(cell_batch)?,
+        })
+    }
+}
+
+impl Default for DomainSeparators {
+    fn default() -> Self {
+        Fork::default().domain_separators()
+    }
+}
+
+/// Domain separators of each fork. Deneb has no cells, so it registers the tag of the fork that
+/// introduced them.
+pub const DOMAIN_SEPARATORS: &[(Fork, DomainSeparators)] = &[
+    (Fork::Deneb, DomainSeparators::V1),
+    (Fork::Fulu, DomainSeparators::V1),
+];
+
+const fn tag(domain: &str) -> [u8; DOMAIN_STR_LENGTH] {
+    let bytes = domain.as_bytes();
+    assert!(bytes.len() == DOMAIN_STR_LENGTH);
+    let mut tag = [0; DOMAIN_STR_LENGTH];
+    let mut i = 0;
+    while i < DOMAIN_STR_LENGTH {
+        tag[i] = bytes[i];
+        i += 1;
+    }
+    tag
+}
+
+impl KzgSettings {
+    /// Returns these settings with the domain separators registered for `fork`
+    pub fn with_fork(self, fork: Fork) -> Self {
+        self.with_domain_separators(fork.domain_separators())
+    }
+
+    /// Returns these settings with the given domain separators, for tags not in the registry
+    pub fn with_domain_separators(mut self, domain_separators: DomainSeparators) -> Self {
+        self.domain_separators = domain_separators;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_separators() {
+        for fork in Fork::ALL {
+            assert_eq!(fork.name().parse::<Fork>().unwrap(), fork);
+        }
+        assert_eq!("FULU".parse::<Fork>().unwrap(), Fork::Fulu);
+        assert!("electra".parse::<Fork>().is_err());
+
+        let separators = Fork::Deneb.domain_separators();
+        assert_eq!(separators.blob_proof, *b"FSBLOBVERIFY_V1_");
+        assert_eq!(separators.batch, *b"RCKZGBATCH___V1_");
+        assert_eq!(DomainSeparators::default().cell_batch, *b"RCKZGCBATCH__V1_");
+
+        let custom =
+            DomainSeparators::new("FSBLOBVERIFY_V2_", "RCKZGBATCH___V2_", "RCKZGCBATCH__V2_");
+        assert_eq!(custom.unwrap().batch, *b"RCKZGBATCH___V2_");
+        assert!(DomainSeparators::new("FSBLOBVERIFY_V2", "", "").is_err());
+    }
+
+    #[cfg(feature = "prover")]
+    #[test]
+    fn test_domain_separators_select_challenge() {
+        use crate::kzg_proof::tests::{BlobInput, Test};
+        use crate::kzg_proof::KzgProof;
+
+        let data = include_str!("../tests/verify_blob_kzg_proof/verify_blob_kzg_proof_case_correct_proof_fb324bc819407148/data.yaml");
+        let test: Test<BlobInput> = serde_yaml::from_str(data).unwrap();
+        let (blob, commitment) = (
+            test.input.get_blob().unwrap(),
+            test.input.get_commitment().unwrap(),
+        );
+        let proof = test.input.get_proof().unwrap();
+
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let deneb = kzg_settings.clone().with_fork(Fork::Deneb);
+        assert!(
+            KzgProof::verify_blob_kzg_proof(blob.clone(), &commitment, &proof, &deneb).unwrap()
+        );
+
+        let separators = DomainSeparators {
+            blob_proof: *b"FSBLOBVERIFY_V2_",
+            ..DomainSeparators::V1
+        };
+        let v2 = kzg_settings.with_domain_separators(separators);
+        assert!(!KzgProof::verify_blob_kzg_proof(blob.clone(), &commitment, &proof, &v2).unwrap());
+        let proof_v2 = KzgProof::compute_blob_kzg_proof(&blob, &commitment, &v2).unwrap();
+        assert!(
+            KzgProof::verify_blob_kzg_proof(blob.clone(), &commitment, &proof_v2, &v2).unwrap()
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! Downloading trusted setup files, with the `net` feature.
+//!
+//! [`KzgSettings::download_trusted_setup`] fetches a setup file, such as the ceremony output
+//! published by c-kzg, checks its SHA-256 against the expected hash and keeps it in a cache
+//! directory, so later calls and later runs read the file instead of downloading it again.
+
+use crate::enums::KzgError;
+use crate::trusted_setup::KzgSettings;
+
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Environment variable overriding the cache directory of [`KzgSettings::download_trusted_setup`]
+pub const CACHE_DIR_ENV: &str = "KZG_RS_CACHE_DIR";
+
+impl KzgSettings {
+    /// Downloads the trusted setup file at `url`, in either format of
+    /// [`KzgSettings::load_trusted_setup_from_path`], and parses it if its SHA-256 is
+    /// `expected_hash`.
+    ///
+    /// The file is cached under the directory named by [`CACHE_DIR_ENV`], or `kzg-rs` in the
+    /// temporary directory, and read from there while its hash still matches.
+    pub fn download_trusted_setup(url: &str, expected_hash: &[u8; 32]) -> Result<Self, KzgError> {
+        let cache_dir = std::env::var_os(CACHE_DIR_ENV)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| std::env::temp_dir().join("kzg-rs"));
+        Self::download_trusted_setup_to(url, expected_hash, cache_dir)
+    }
+
+    /// Same as [`KzgSettings::download_trusted_setup`], caching the file in `cache_dir`. Failing
+    /// to write the cache does not fail the download.
+    pub fn download_trusted_setup_to(
+        url: &str,
+        expected_hash: &[u8; 32],
+        cache_dir: impl AsRef<Path>,
+    ) -> Result<Self, KzgError> {
+        let cached = cache_dir.as_ref().join(hex::encode(expected_hash));
+        if let Ok(contents) = std::fs::read(&cached) {
+            if Sha256::digest(&contents)[..] == expected_hash[..] {
+                return parse(&contents);
+            }
+        }
+
+        let contents = ureq::get(url)
+            .call()
+            .and_then(|mut response| response.body_mut().read_to_vec())
+            .map_err(|e| {
+                KzgError::InvalidTrustedSetup(format!(
+                    "Failed to download trusted setup from {}: {}",
+                    url, e
+                ))
+            })?;
+        let hash = Sha256::digest(&contents);
+        if hash[..] != expected_hash[..] {
+            return Err(KzgError::InvalidTrustedSetup(format!(
+                "The trusted setup from {} has SHA-256 {}, expected {}",
+                url,
+                hex::encode(hash),
+                hex::encode(expected_hash)
+            )));
+        }
+        let settings = parse(&contents)?;
+
+        // Written next to the final path and renamed, so readers never see a partial file
+        let partial = cached.with_extension("partial");
+        let _ = std::fs::create_dir_all(cache_dir.as_ref())
+            .and_then(|_| std::fs::write(&partial, &contents))
+            .and_then(|_| std::fs::rename(&partial, &cached));
+        Ok(settings)
+    }
+}
+
+fn parse(contents: &[u8]) -> Result<KzgSettings, KzgError> {
+    let contents = core::str::from_utf8(contents).map_err(|_| {
+        KzgError::InvalidTrustedSetup("The trusted setup is not valid UTF-8".to_string())
+    })?;
+    KzgSettings::parse_trusted_setup_file(contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_download_trusted_setup_from_cache() {
+        let contents = include_bytes!("trusted_setup.txt");
+        let hash: [u8; 32] = Sha256::digest(contents).into();
+        let cache_dir = std::env::temp_dir().join(format!("kzg-rs-test-{}", std::process::id()));
+        std::fs::create_dir_all(&cache_dir).unwrap();
+        std::fs::write(cache_dir.join(hex::encode(hash)), contents).unwrap();
+
+        // Nothing listens on the discard port, so the settings come from the cache
+        let url = "http://127.0.0.1:9/trusted_setup.txt";
+        let settings = KzgSettings::download_trusted_setup_to(url, &hash, &cache_dir).unwrap();
+        assert_eq!(settings, KzgSettings::load_trusted_setup_file().unwrap());
+
+        // A cached file with another hash is not used
+        let mut other = hash;
+        other[0] ^= 1;
+        std::fs::write(cache_dir.join(hex::encode(other)), contents).unwrap();
+        assert!(KzgSettings::download_trusted_setup_to(url, &other, &cache_dir).is_err());
+
+        std::fs::remove_dir_all(cache_dir).unwrap();
+    }
+}
@@ -1,13 +1,27 @@
 use crate::enums::KzgError;
-use crate::kzg_proof::safe_scalar_affine_from_bytes;
-use crate::{BYTES_PER_BLOB, BYTES_PER_FIELD_ELEMENT};
+use crate::{BYTES_PER_BLOB, BYTES_PER_CELL, BYTES_PER_FIELD_ELEMENT, CELLS_PER_EXT_BLOB};
 
-use alloc::{string::ToString, vec::Vec};
+use alloc::string::ToString;
+
+#[cfg(feature = "prover")]
+use crate::{
+    kzg_proof::safe_scalar_affine_from_bytes, kzg_to_versioned_hash, polynomial::Polynomial,
+    precompile::PointEvaluationInput, FieldElement, MAX_BLOB_PAYLOAD_LEN,
+    NUM_FIELD_ELEMENTS_PER_BLOB, PAYLOAD_BYTES_PER_FIELD_ELEMENT,
+};
+#[cfg(feature = "prover")]
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+#[cfg(feature = "prover")]
 use bls12_381::Scalar;
 
+/// Size of the item count at the start of a contiguous buffer
+pub const CONTIGUOUS_COUNT_BYTES: usize = 4;
+
 macro_rules! define_bytes_type {
     ($name:ident, $size:expr) => {
         #[derive(Debug, Clone)]
+        #[repr(transparent)]
         pub struct $name([u8; $size]);
 
         impl $name {
@@ -25,6 +39,70 @@ macro_rules! define_bytes_type {
             pub fn as_slice(&self) -> &[u8] {
                 &self.0
             }
+
+            /// Length of the hex encoding, without `0x` prefix
+            pub const HEX_LEN: usize = 2 * $size;
+
+            /// Writes the lowercase hex encoding, without `0x` prefix, to the start of `out` and
+            /// returns it. `out` must hold at least [`Self::HEX_LEN`] bytes.
+            pub fn encode_hex_into<'a>(&self, out: &'a mut [u8]) -> Result<&'a str, KzgError> {
+                if out.len() < Self::HEX_LEN {
+                    return Err(KzgError::InvalidBytesLength(format!(
+                        "Hex output needs {} bytes, got {}",
+                        Self::HEX_LEN,
+                        out.len()
+                    )));
+                }
+                let out = &mut out[..Self::HEX_LEN];
+                hex::encode_to_slice(self.0, out).unwrap();
+                Ok(core::str::from_utf8(out).unwrap())
+            }
+
+            /// Overwrites these bytes with the decoding of `hex`, with or without `0x` prefix, in
+            /// either case. On error the bytes may be partially overwritten.
+            pub fn decode_hex_into(&mut self, hex: impl AsRef<[u8]>) -> Result<(), KzgError> {
+                let hex = hex.as_ref();
+                let hex = hex.strip_prefix(b"0x").unwrap_or(hex);
+                hex::decode_to_slice(hex, &mut self.0)
+                    .map_err(|e| KzgError::InvalidHexFormat(format!("Failed to decode hex: {}", e)))
+            }
+
+            /// Reinterprets a slice of byte arrays as a slice of this type without copying
+            pub fn from_arrays(arrays: &[[u8; $size]]) -> &[Self] {
+                // SAFETY: the type is a transparent wrapper around the array
+                unsafe { core::slice::from_raw_parts(arrays.as_ptr() as *const Self, arrays.len()) }
+            }
+
+            /// Serializes `items` into one buffer holding their count as a 4-byte big-endian
+            /// integer followed by the items
+            pub fn to_contiguous(items: &[Self]) -> Vec<u8> {
+                let mut buffer = Vec::with_capacity(CONTIGUOUS_COUNT_BYTES + items.len() * $size);
+                buffer.extend_from_slice(&(items.len() as u32).to_be_bytes());
+                for item in items {
+                    buffer.extend_from_slice(&item.0);
+                }
+                buffer
+            }
+
+            /// Parses a buffer written by `to_contiguous` without copying, checking that its
+            /// length matches the count
+            pub fn from_contiguous(buffer: &[u8]) -> Result<&[Self], KzgError> {
+                if buffer.len() < CONTIGUOUS_COUNT_BYTES {
+                    return Err(KzgError::InvalidBytesLength(
+                        "Missing item count".to_string(),
+                    ));
+                }
+                let (count, items) = buffer.split_at(CONTIGUOUS_COUNT_BYTES);
+                let count = u32::from_be_bytes(count.try_into().unwrap()) as usize;
+                if count.checked_mul($size) != Some(items.len()) {
+                    return Err(KzgError::InvalidBytesLength(
+                        "Buffer length does not match the item count".to_string(),
+                    ));
+                }
+                // SAFETY: the type is a transparent wrapper around a byte array, so it has no
+                // alignment requirement, and the length was checked above
+                Ok(unsafe { core::slice::from_raw_parts(items.as_ptr() as *const Self, count) })
+            }
         }
 
         impl From<$name> for [u8; $size] {
@@ -32,22 +110,264 @@ macro_rules! define_bytes_type {
                 value.0
             }
         }
+
+        impl From<[u8; $size]> for $name {
+            fn from(value: [u8; $size]) -> $name {
+                $name(value)
+            }
+        }
+
+        impl<'a> From<&'a [u8; $size]> for &'a $name {
+            fn from(value: &'a [u8; $size]) -> &'a $name {
+                // SAFETY: the type is a transparent wrapper around the array
+                unsafe { &*(value as *const [u8; $size] as *const $name) }
+            }
+        }
     };
 }
 
 define_bytes_type!(Bytes32, 32);
 define_bytes_type!(Bytes48, 48);
+define_bytes_type!(Cell, BYTES_PER_CELL);
+
+/// EIP-4844 versioned hash of a commitment, see [`crate::kzg_to_versioned_hash`]
+pub type VersionedHash = Bytes32;
+
+/// Position of a cell in the extended blob, below [`CELLS_PER_EXT_BLOB`]
+pub type CellIndex = u64;
+
 define_bytes_type!(Blob, BYTES_PER_BLOB);
 
+/// Field elements parsed per task by [`bytes_to_scalars`], enough to outweigh the scheduling
+#[cfg(all(feature = "parallel", feature = "prover"))]
+const SCALARS_PER_TASK: usize = 256;
+
+/// Parses concatenated big-endian field elements, failing on the first one that is not canonical
+#[cfg(feature = "prover")]
+pub(crate) fn bytes_to_scalars(bytes: &[u8]) -> Result<Vec<Scalar>, KzgError> {
+    let parse = |slice: &[u8]| {
+        Bytes32::from_slice(slice).and_then(|bytes| safe_scalar_affine_from_bytes(&bytes))
+    };
+
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        bytes
+            .par_chunks(BYTES_PER_FIELD_ELEMENT)
+            .with_min_len(SCALARS_PER_TASK)
+            .map(parse)
+            .collect()
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    bytes.chunks(BYTES_PER_FIELD_ELEMENT).map(parse).collect()
+}
+
+#[cfg(feature = "prover")]
 impl Blob {
+    /// Parses the field elements of the blob, in parallel chunks with the `parallel` feature
     pub fn as_polynomial(&self) -> Result<Vec<Scalar>, KzgError> {
+        bytes_to_scalars(&self.0)
+    }
+
+    /// Returns the blob as a polynomial in evaluation form, see [`Blob::as_polynomial`]
+    pub fn to_polynomial(&self) -> Result<Polynomial, KzgError> {
+        self.as_polynomial().map(Polynomial::Evaluations)
+    }
+
+    /// Encodes `payload` with the standard codec: each field element carries 31 payload bytes
+    /// after a zero high byte, and the blob is padded with zero bytes after the payload
+    pub fn from_payload(payload: &[u8]) -> Result<Self, KzgError> {
+        if payload.len() > MAX_BLOB_PAYLOAD_LEN {
+            return Err(KzgError::InvalidBytesLength(format!(
+                "The payload is {} bytes, but a blob holds at most {}",
+                payload.len(),
+                MAX_BLOB_PAYLOAD_LEN
+            )));
+        }
+
+        let mut bytes = [0u8; BYTES_PER_BLOB];
+        encode_payload(payload, &mut bytes);
+        Ok(Self(bytes))
+    }
+
+    /// Same as [`Blob::from_slice`], but builds the blob on the heap without a blob-sized
+    /// temporary on the stack
+    pub fn boxed_from_slice(slice: &[u8]) -> Result<Box<Self>, KzgError> {
+        if slice.len() != BYTES_PER_BLOB {
+            return Err(KzgError::InvalidBytesLength(
+                "Invalid slice length".to_string(),
+            ));
+        }
+        Ok(Self::from_boxed_bytes(slice.into()))
+    }
+
+    /// Same as [`Blob::from_payload`], but builds the blob on the heap without a blob-sized
+    /// temporary on the stack
+    pub fn boxed_from_payload(payload: &[u8]) -> Result<Box<Self>, KzgError> {
+        if payload.len() > MAX_BLOB_PAYLOAD_LEN {
+            return Err(KzgError::InvalidBytesLength(format!(
+                "The payload is {} bytes, but a blob holds at most {}",
+                payload.len(),
+                MAX_BLOB_PAYLOAD_LEN
+            )));
+        }
+        let mut bytes = vec![0u8; BYTES_PER_BLOB].into_boxed_slice();
+        encode_payload(payload, &mut bytes);
+        Ok(Self::from_boxed_bytes(bytes))
+    }
+
+    fn from_boxed_bytes(bytes: Box<[u8]>) -> Box<Self> {
+        debug_assert_eq!(bytes.len(), BYTES_PER_BLOB);
+        // SAFETY: the slice has the length of the array the type transparently wraps, and both
+        // have an alignment of 1
+        unsafe { Box::from_raw(Box::into_raw(bytes) as *mut Self) }
+    }
+
+    /// Returns the payload bytes of the blob under the standard codec, up to
+    /// [`Blob::payload_len`]
+    pub fn payload(&self) -> Vec<u8> {
+        let mut payload = self
+            .0
+            .chunks(BYTES_PER_FIELD_ELEMENT)
+            .flat_map(|element| element[1..].iter().copied())
+            .collect::<Vec<_>>();
+        payload.truncate(self.payload_len());
+        payload
+    }
+
+    /// Returns the length of the payload under the standard codec, that is the number of payload
+    /// bytes up to and including the last non-zero one. Trailing zero bytes are padding.
+    pub fn payload_len(&self) -> usize {
         self.0
             .chunks(BYTES_PER_FIELD_ELEMENT)
-            .map(|slice| {
-                Bytes32::from_slice(slice).and_then(|bytes| safe_scalar_affine_from_bytes(&bytes))
+            .enumerate()
+            .flat_map(|(i, element)| {
+                element[1..]
+                    .iter()
+                    .enumerate()
+                    .map(move |(j, byte)| (i * PAYLOAD_BYTES_PER_FIELD_ELEMENT + j, *byte))
             })
+            .rfind(|(_, byte)| *byte != 0)
+            .map_or(0, |(position, _)| position + 1)
+    }
+
+    /// Returns `true` if the high byte of every field element is zero, as the standard codec
+    /// produces.
+    ///
+    /// Decoders that skip the high bytes map blobs differing only there to the same payload even
+    /// though their commitments differ, so such blobs should be rejected.
+    pub fn is_padded_canonically(&self) -> bool {
+        self.0
+            .chunks(BYTES_PER_FIELD_ELEMENT)
+            .all(|element| element[0] == 0)
+    }
+
+    /// Returns the indices of the field elements that differ between `self` and `other`
+    pub fn diff(&self, other: &Blob) -> Vec<usize> {
+        self.0
+            .chunks(BYTES_PER_FIELD_ELEMENT)
+            .zip(other.0.chunks(BYTES_PER_FIELD_ELEMENT))
+            .enumerate()
+            .filter(|(_, (a, b))| a != b)
+            .map(|(i, _)| i)
             .collect()
     }
+
+    /// Returns the field element at `index`
+    pub fn field_element(&self, index: usize) -> Result<FieldElement, KzgError> {
+        if index >= NUM_FIELD_ELEMENTS_PER_BLOB {
+            return Err(KzgError::BadArgs(format!(
+                "Field element index {} is out of range",
+                index
+            )));
+        }
+        let offset = index * BYTES_PER_FIELD_ELEMENT;
+        FieldElement::from_bytes(&Bytes32::from_slice(
+            &self.0[offset..offset + BYTES_PER_FIELD_ELEMENT],
+        )?)
+    }
+
+    /// Overwrites the field elements at the given indices, returning for each patch the
+    /// difference between the new and the old value.
+    ///
+    /// The differences can be passed to [`KzgProof::update_commitment`](crate::KzgProof) to update
+    /// the blob's commitment without recomputing it. The patches are applied in order, each
+    /// difference taken against the value left by the previous ones, so an index patched twice
+    /// ends at its last value and its differences add up to the total change. The blob is left
+    /// untouched if any index is out of range or any replaced element is not canonical.
+    pub fn patch(
+        &mut self,
+        patches: &[(usize, FieldElement)],
+    ) -> Result<Vec<(usize, FieldElement)>, KzgError> {
+        for &(index, _) in patches {
+            self.field_element(index)?;
+        }
+
+        let mut deltas = Vec::with_capacity(patches.len());
+        for &(index, value) in patches {
+            deltas.push((index, value - self.field_element(index)?));
+            let offset = index * BYTES_PER_FIELD_ELEMENT;
+            self.0[offset..offset + BYTES_PER_FIELD_ELEMENT]
+                .copy_from_slice(value.to_bytes().as_slice());
+        }
+        Ok(deltas)
+    }
+}
+
+impl Cell {
+    /// Checks that `index` addresses a cell of an extended blob and returns it as a `usize`
+    pub fn check_index(index: CellIndex) -> Result<usize, KzgError> {
+        usize::try_from(index)
+            .ok()
+            .filter(|&index| index < CELLS_PER_EXT_BLOB)
+            .ok_or_else(|| KzgError::BadArgs(format!("Cell index {} is out of range", index)))
+    }
+
+    /// Returns the field elements of the cell as 32-byte chunks
+    pub fn field_elements(&self) -> impl Iterator<Item = &[u8]> {
+        self.0.chunks(BYTES_PER_FIELD_ELEMENT)
+    }
+}
+
+/// Writes `payload` into `bytes` with the standard codec, leaving the padding untouched
+#[cfg(feature = "prover")]
+fn encode_payload(payload: &[u8], bytes: &mut [u8]) {
+    for (element, chunk) in bytes
+        .chunks_mut(BYTES_PER_FIELD_ELEMENT)
+        .zip(payload.chunks(PAYLOAD_BYTES_PER_FIELD_ELEMENT))
+    {
+        element[1..1 + chunk.len()].copy_from_slice(chunk);
+    }
+}
+
+/// A KZG proof together with the evaluation `y` it attests to
+#[cfg(feature = "prover")]
+#[derive(Debug, Clone)]
+pub struct ProofWithEvaluation {
+    pub proof: Bytes48,
+    pub y: Bytes32,
+}
+
+#[cfg(feature = "prover")]
+impl ProofWithEvaluation {
+    /// Returns the point evaluation precompile input for this opening of `commitment` at `z`
+    pub fn to_precompile_input(&self, commitment: &Bytes48, z: &Bytes32) -> PointEvaluationInput {
+        PointEvaluationInput {
+            versioned_hash: kzg_to_versioned_hash(commitment),
+            z: z.clone(),
+            y: self.y.clone(),
+            commitment: commitment.clone(),
+            proof: self.proof.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "prover")]
+impl From<ProofWithEvaluation> for (Bytes48, Bytes32) {
+    fn from(value: ProofWithEvaluation) -> Self {
+        (value.proof, value.y)
+    }
 }
 
 #[cfg(test)]
@@ -63,4 +383,112 @@ mod tests {
         let bytes = crate::dtypes::Bytes48::from_slice(&[0u8; 48]).unwrap();
         assert_eq!(bytes.0.len(), 48);
     }
+
+    #[test]
+    fn test_cell() {
+        use crate::dtypes::Cell;
+        use crate::{BYTES_PER_CELL, CELLS_PER_EXT_BLOB};
+
+        let mut bytes = [0u8; BYTES_PER_CELL];
+        bytes[32] = 1;
+        let cell = Cell::from(bytes);
+        assert_eq!(cell.field_elements().count(), 64);
+        assert_eq!(cell.field_elements().nth(1).unwrap()[0], 1);
+        assert!(Cell::from_slice(&bytes[1..]).is_err());
+
+        assert_eq!(Cell::check_index(127).unwrap(), CELLS_PER_EXT_BLOB - 1);
+        assert!(Cell::check_index(128).is_err());
+    }
+
+    #[test]
+    fn test_hex_into() {
+        use crate::dtypes::Bytes48;
+
+        let mut bytes = [0u8; 48];
+        bytes[0] = 0xc0;
+        bytes[47] = 0x0f;
+        let value = Bytes48::from(bytes);
+
+        let mut out = [0u8; 100];
+        let encoded = value.encode_hex_into(&mut out).unwrap();
+        assert_eq!(encoded.len(), Bytes48::HEX_LEN);
+        assert!(encoded.starts_with("c000") && encoded.ends_with("000f"));
+        assert!(value.encode_hex_into(&mut [0u8; 95]).is_err());
+
+        let mut decoded = Bytes48::from([0xff; 48]);
+        decoded.decode_hex_into(encoded).unwrap();
+        assert_eq!(decoded.as_slice(), value.as_slice());
+        let prefixed = format!("0x{}", encoded.to_uppercase());
+        decoded.decode_hex_into(&prefixed).unwrap();
+        assert_eq!(decoded.as_slice(), value.as_slice());
+
+        assert!(decoded.decode_hex_into(&encoded[2..]).is_err());
+        assert!(decoded.decode_hex_into(encoded.replace('c', "g")).is_err());
+    }
+
+    #[test]
+    fn test_contiguous_buffer() {
+        use crate::dtypes::Bytes48;
+
+        let items = [Bytes48::from([1; 48]), Bytes48::from([2; 48])];
+        let buffer = Bytes48::to_contiguous(&items);
+        assert_eq!(buffer.len(), 4 + 2 * 48);
+        assert_eq!(buffer[..4], [0, 0, 0, 2]);
+
+        let parsed = Bytes48::from_contiguous(&buffer).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[1].as_slice(), items[1].as_slice());
+        assert!(Bytes48::from_contiguous(&Bytes48::to_contiguous(&[]))
+            .unwrap()
+            .is_empty());
+
+        assert!(Bytes48::from_contiguous(&buffer[..buffer.len() - 1]).is_err());
+        assert!(Bytes48::from_contiguous(&buffer[..3]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "prover")]
+    fn test_blob_payload() {
+        use crate::dtypes::{Blob, Bytes32};
+        use crate::kzg_proof::safe_scalar_affine_from_bytes;
+        use crate::BYTES_PER_FIELD_ELEMENT;
+        use crate::MAX_BLOB_PAYLOAD_LEN;
+
+        let payload = (1..=100u8).collect::<Vec<_>>();
+        let mut blob = Blob::from_payload(&payload).unwrap();
+        assert_eq!(blob.payload_len(), payload.len());
+        assert_eq!(blob.payload(), payload);
+        assert!(blob.is_padded_canonically());
+        let polynomial = blob.as_polynomial().unwrap();
+        let serial = blob
+            .0
+            .chunks(BYTES_PER_FIELD_ELEMENT)
+            .map(|slice| safe_scalar_affine_from_bytes(&Bytes32::from_slice(slice).unwrap()))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(polynomial, serial);
+
+        // A non-canonical element anywhere in the blob is rejected
+        let mut invalid = Blob::from_payload(&payload).unwrap();
+        invalid.0[32 * 3000] = 0xff;
+        assert!(invalid.as_polynomial().is_err());
+
+        // Setting a high byte in the padding changes the blob but not the payload
+        blob.0[32 * 100] = 1;
+        assert_eq!(blob.payload(), payload);
+        assert!(!blob.is_padded_canonically());
+
+        assert_eq!(Blob::from_payload(&[]).unwrap().payload_len(), 0);
+        assert!(Blob::from_payload(&vec![1; MAX_BLOB_PAYLOAD_LEN]).is_ok());
+        assert!(Blob::from_payload(&vec![1; MAX_BLOB_PAYLOAD_LEN + 1]).is_err());
+
+        let boxed = Blob::boxed_from_payload(&payload).unwrap();
+        assert_eq!(
+            boxed.as_slice(),
+            Blob::from_payload(&payload).unwrap().as_slice()
+        );
+        let boxed = Blob::boxed_from_slice(boxed.as_slice()).unwrap();
+        assert_eq!(boxed.payload(), payload);
+        assert!(Blob::boxed_from_slice(&payload).is_err());
+    }
 }
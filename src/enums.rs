@@ -15,6 +15,31 @@ pub enum KzgError {
     InvalidHexFormat(String),
     /// The provided trusted setup params are invalid.
     InvalidTrustedSetup(String),
+    /// The operation was cancelled through its cancellation token.
+    Cancelled,
+    /// The input exceeds one of the limits configured in the settings.
+    LimitExceeded(LimitExceeded),
+}
+
+/// An input size bounded by the settings' limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit {
+    /// Number of blobs in one batch
+    BlobsPerBatch,
+    /// Number of cells in one call
+    CellsPerCall,
+    /// Total size in bytes of the blobs or cells, commitments and proofs of one call
+    TotalBytes,
+}
+
+/// Details of a [`KzgError::LimitExceeded`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LimitExceeded {
+    pub limit: Limit,
+    /// Size of the rejected input
+    pub requested: usize,
+    /// Largest size allowed
+    pub maximum: usize,
 }
 
 impl fmt::Display for KzgError {
@@ -25,6 +50,19 @@ impl fmt::Display for KzgError {
             | Self::InvalidHexFormat(s)
             | Self::InvalidTrustedSetup(s) => f.write_str(s),
             Self::InternalError => f.write_str("Internal error"),
+            Self::Cancelled => f.write_str("Operation cancelled"),
+            Self::LimitExceeded(exceeded) => {
+                let limit = match exceeded.limit {
+                    Limit::BlobsPerBatch => "blobs per batch",
+                    Limit::CellsPerCall => "cells per call",
+                    Limit::TotalBytes => "total input bytes",
+                };
+                write!(
+                    f,
+                    "Too many {}: {} exceeds the limit of {}",
+                    limit, exceeded.requested, exceeded.maximum
+                )
+            }
         }
     }
 }
@@ -0,0 +1,373 @@
+//! Radix-2 FFTs over the scalar field and G1, on the roots of unity and on their cosets.
+//!
+//! Evaluation vectors are in natural order unless stated otherwise: entry `i` is the value at
+//! `ω^i`, or `shift·ω^i` on a coset. Blobs and cells list their evaluations in bit-reversed
+//! order, see [`bit_reversal_permutation`].
+//!
+//! [`extend_evaluations`] doubles a blob's evaluations onto the extended domain of order
+//! [`FIELD_ELEMENTS_PER_EXT_BLOB`](crate::FIELD_ELEMENTS_PER_EXT_BLOB). The extended domain is
+//! the blob's domain `H` and its coset `ω₂ₙ·H`, and in bit-reversed order the second half of the
+//! extension is exactly the coset's evaluations, so it costs two FFTs of the blob's size.
+//!
+//! The roots of unity of each order, with their inverses and other derived values, are computed
+//! once and shared by all threads, see [`EvaluationDomain::get`].
+
+use crate::enums::KzgError;
+use crate::{MODULUS, PRIMITIVE_ROOT_OF_UNITY};
+
+use alloc::{boxed::Box, string::ToString, vec::Vec};
+use bls12_381::{G1Projective, Scalar};
+use spin::Mutex;
+
+/// Largest `k` for which the scalar field has primitive `2^k`-th roots of unity, its 2-adicity
+pub const MAX_ROOT_OF_UNITY_SCALE: u32 = 32;
+
+/// Returns a primitive `2^k`-th root of unity, `g^((r - 1) / 2^k)` for the generator
+/// [`PRIMITIVE_ROOT_OF_UNITY`] of the multiplicative group. `k` must be at most
+/// [`MAX_ROOT_OF_UNITY_SCALE`].
+pub fn root_of_unity(k: u32) -> Result<Scalar, KzgError> {
+    if k > MAX_ROOT_OF_UNITY_SCALE {
+        return Err(KzgError::BadArgs(format!(
+            "No primitive root of unity of order 2^{}",
+            k
+        )));
+    }
+
+    // r - 1 is divisible by 2^32, so shifting its limbs right is exact
+    let mut exponent = MODULUS;
+    exponent[0] -= 1;
+    if k > 0 {
+        for i in 0..4 {
+            let high = exponent.get(i + 1).map_or(0, |limb| limb << (64 - k));
+            exponent[i] = (exponent[i] >> k) | high;
+        }
+    }
+    Ok(Scalar::from(PRIMITIVE_ROOT_OF_UNITY).pow_vartime(&exponent))
+}
+
+/// Returns the `2^k` powers of [`root_of_unity(k)`](root_of_unity), in natural order
+pub fn roots_of_unity(k: u32) -> Result<Vec<Scalar>, KzgError> {
+    let n = 1usize.checked_shl(k).ok_or_else(|| {
+        KzgError::BadArgs(format!("A domain of order 2^{} does not fit in memory", k))
+    })?;
+    expand_root_of_unity(n)
+}
+
+/// Returns the `n` powers of a primitive `n`-th root of unity, in natural order
+pub fn expand_root_of_unity(n: usize) -> Result<Vec<Scalar>, KzgError> {
+    if !n.is_power_of_two() {
+        return Err(KzgError::BadArgs(format!(
+            "No primitive root of unity of order {}",
+            n
+        )));
+    }
+
+    let root = root_of_unity(n.trailing_zeros())?;
+    let mut roots = Vec::with_capacity(n);
+    let mut current = Scalar::one();
+    for _ in 0..n {
+        roots.push(current);
+        current *= root;
+    }
+    Ok(roots)
+}
+
+/// The roots of unity of a power-of-two order `n` and the values derived from them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvaluationDomain {
+    /// The order `n`
+    pub size: usize,
+    /// The roots of unity `ω^i`, in natural order
+    pub roots: Vec<Scalar>,
+    /// Their inverses `ω^-i`, in natural order
+    pub inverse_roots: Vec<Scalar>,
+    /// The roots of unity in bit-reversed order, as blobs and cells list their evaluations
+    pub bit_reversed_roots: Vec<Scalar>,
+    /// `1/n`
+    pub size_inverse: Scalar,
+}
+
+impl EvaluationDomain {
+    /// Computes the domain of order `n`, which must be a power of two
+    pub fn new(n: usize) -> Result<Self, KzgError> {
+        let roots = expand_root_of_unity(n)?;
+        Ok(Self {
+            size: n,
+            inverse_roots: (0..n).map(|i| roots[(n - i) % n]).collect(),
+            bit_reversed_roots: bit_reversal_permutation(&roots),
+            size_inverse: Scalar::from(n as u64).invert().unwrap(),
+            roots,
+        })
+    }
+
+    /// Returns the domain of order `n`, computing it on first use. Domains are kept for the
+    /// lifetime of the program and shared by all threads.
+    pub fn get(n: usize) -> Result<&'static Self, KzgError> {
+        static DOMAINS: Mutex<Vec<&'static EvaluationDomain>> = Mutex::new(Vec::new());
+        let cached = DOMAINS
+            .lock()
+            .iter()
+            .find(|domain| domain.size == n)
+            .copied();
+        if let Some(domain) = cached {
+            return Ok(domain);
+        }
+
+        let domain: &'static Self = Box::leak(Box::new(Self::new(n)?));
+        let mut domains = DOMAINS.lock();
+        // Another thread may have computed it meanwhile
+        if let Some(existing) = domains.iter().find(|domain| domain.size == n) {
+            return Ok(existing);
+        }
+        domains.push(domain);
+        Ok(domain)
+    }
+
+    /// Returns `x^n - 1`, the polynomial vanishing on the domain, with `log2(n)` squarings
+    pub fn vanishing_at(&self, x: Scalar) -> Scalar {
+        let mut power = x;
+        for _ in 0..self.size.trailing_zeros() {
+            power = power.square();
+        }
+        power - Scalar::one()
+    }
+}
+
+/// Reorders `values`, whose length must be a power of two, so that the element at index `i`
+/// moves to the index whose bits are those of `i` reversed
+pub fn bit_reversal_permutation<T: Copy>(values: &[T]) -> Vec<T> {
+    let bits = values.len().trailing_zeros();
+    if bits == 0 {
+        return values.to_vec();
+    }
+    (0..values.len())
+        .map(|i| values[i.reverse_bits() >> (usize::BITS - bits)])
+        .collect()
+}
+
+/// Evaluates the polynomial with the given coefficients over the roots of unity of order
+/// `coefficients.len()`
+pub fn fft(coefficients: &[Scalar]) -> Result<Vec<Scalar>, KzgError> {
+    let domain = EvaluationDomain::get(coefficients.len())?;
+    let mut evaluations = vec![Scalar::zero(); coefficients.len()];
+    fr_fft(&mut evaluations, coefficients, 1, &domain.roots, 1);
+    Ok(evaluations)
+}
+
+/// Returns the coefficients of the polynomial taking the given values on the roots of unity of
+/// order `evaluations.len()`
+pub fn inverse_fft(evaluations: &[Scalar]) -> Result<Vec<Scalar>, KzgError> {
+    let domain = EvaluationDomain::get(evaluations.len())?;
+    let mut coefficients = vec![Scalar::zero(); evaluations.len()];
+    fr_fft(&mut coefficients, evaluations, 1, &domain.inverse_roots, 1);
+    for coefficient in coefficients.iter_mut() {
+        *coefficient *= domain.size_inverse;
+    }
+    Ok(coefficients)
+}
+
+/// Evaluates the polynomial with the given coefficients over the coset `shift·H` of the roots of
+/// unity `H` of order `coefficients.len()`
+pub fn coset_fft(coefficients: &[Scalar], shift: Scalar) -> Result<Vec<Scalar>, KzgError> {
+    // P(shift·ω^i) is the DFT of P(shift·X), whose coefficients are scaled by shift^j
+    fft(&scale_coefficients(coefficients, shift))
+}
+
+/// Returns the coefficients of the polynomial taking the given values on the coset `shift·H`
+/// of the roots of unity `H` of order `evaluations.len()`. `shift` must not be zero.
+pub fn coset_inverse_fft(evaluations: &[Scalar], shift: Scalar) -> Result<Vec<Scalar>, KzgError> {
+    let shift_inv = Option::<Scalar>::from(shift.invert())
+        .ok_or_else(|| KzgError::BadArgs("The coset shift must not be zero".to_string()))?;
+    Ok(scale_coefficients(&inverse_fft(evaluations)?, shift_inv))
+}
+
+/// Extends the evaluations of a polynomial of degree below `n = evaluations.len()` over the
+/// roots of unity of order `n` to the roots of unity of order `2n`, both in bit-reversed order.
+/// The first half of the result is `evaluations` itself.
+pub fn extend_evaluations(evaluations: &[Scalar]) -> Result<Vec<Scalar>, KzgError> {
+    let coefficients = inverse_fft(&bit_reversal_permutation(evaluations))?;
+    extend_coefficients(&coefficients)
+}
+
+/// Evaluates the polynomial with the given coefficients, of degree below
+/// `n = coefficients.len()`, over the roots of unity of order `2n`, in bit-reversed order
+pub fn extend_coefficients(coefficients: &[Scalar]) -> Result<Vec<Scalar>, KzgError> {
+    // The odd powers of ω₂ₙ are the coset ω₂ₙ·H, and come second in bit-reversed order
+    let shift = EvaluationDomain::get(2 * coefficients.len())?.roots[1];
+    let mut extended = bit_reversal_permutation(&fft(coefficients)?);
+    extended.extend(bit_reversal_permutation(&coset_fft(coefficients, shift)?));
+    Ok(extended)
+}
+
+/// Evaluates the polynomial with the given G1 coefficients over the roots of unity of order
+/// `coefficients.len()`
+pub fn g1_fft(coefficients: &[G1Projective]) -> Result<Vec<G1Projective>, KzgError> {
+    let domain = EvaluationDomain::get(coefficients.len())?;
+    let mut evaluations = vec![G1Projective::identity(); coefficients.len()];
+    g1_fft_strided(&mut evaluations, coefficients, 1, &domain.roots, 1);
+    Ok(evaluations)
+}
+
+/// Returns the G1 coefficients of the polynomial taking the given values on the roots of unity
+/// of order `evaluations.len()`
+pub fn g1_inverse_fft(evaluations: &[G1Projective]) -> Result<Vec<G1Projective>, KzgError> {
+    let domain = EvaluationDomain::get(evaluations.len())?;
+    let mut coefficients = vec![G1Projective::identity(); evaluations.len()];
+    g1_fft_strided(&mut coefficients, evaluations, 1, &domain.inverse_roots, 1);
+    for point in coefficients.iter_mut() {
+        *point *= domain.size_inverse;
+    }
+    Ok(coefficients)
+}
+
+/// Returns the coefficients of `P(shift·X)` given those of `P`
+pub fn scale_coefficients(coefficients: &[Scalar], shift: Scalar) -> Vec<Scalar> {
+    let mut factor = Scalar::one();
+    coefficients
+        .iter()
+        .map(|coefficient| {
+            let scaled = coefficient * factor;
+            factor *= shift;
+            scaled
+        })
+        .collect()
+}
+
+/// Radix-2 DFT over the scalar field: `out[i] = Σ_j roots[i * j] * values[j]`, reading `values`
+/// and `roots` with the given strides
+fn fr_fft(
+    out: &mut [Scalar],
+    values: &[Scalar],
+    stride: usize,
+    roots: &[Scalar],
+    roots_stride: usize,
+) {
+    let n = out.len();
+    if n == 1 {
+        out[0] = values[0];
+        return;
+    }
+
+    let half = n / 2;
+    let (lo, hi) = out.split_at_mut(half);
+    fr_fft(lo, values, stride * 2, roots, roots_stride * 2);
+    fr_fft(hi, &values[stride..], stride * 2, roots, roots_stride * 2);
+    for i in 0..half {
+        let x = lo[i];
+        let y = hi[i] * roots[i * roots_stride];
+        lo[i] = x + y;
+        hi[i] = x - y;
+    }
+}
+
+/// Radix-2 DFT over G1: `out[i] = Σ_j roots[i * j] * values[j]`, reading `values` and `roots`
+/// with the given strides
+fn g1_fft_strided(
+    out: &mut [G1Projective],
+    values: &[G1Projective],
+    stride: usize,
+    roots: &[Scalar],
+    roots_stride: usize,
+) {
+    let n = out.len();
+    if n == 1 {
+        out[0] = values[0];
+        return;
+    }
+
+    let half = n / 2;
+    let (lo, hi) = out.split_at_mut(half);
+    g1_fft_strided(lo, values, stride * 2, roots, roots_stride * 2);
+    g1_fft_strided(hi, &values[stride..], stride * 2, roots, roots_stride * 2);
+    for i in 0..half {
+        let x = lo[i];
+        let y = hi[i] * roots[i * roots_stride];
+        lo[i] = x + y;
+        hi[i] = x - y;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fft() {
+        let coefficients = (0..16u64)
+            .map(|i| Scalar::from(i * i + 3))
+            .collect::<Vec<_>>();
+        let evaluate = |x: Scalar| {
+            coefficients
+                .iter()
+                .rev()
+                .fold(Scalar::zero(), |acc, coefficient| acc * x + coefficient)
+        };
+
+        let roots = expand_root_of_unity(16).unwrap();
+        let evaluations = fft(&coefficients).unwrap();
+        assert_eq!(evaluations[5], evaluate(roots[5]));
+        assert_eq!(inverse_fft(&evaluations).unwrap(), coefficients);
+
+        let shift = Scalar::from(7);
+        let on_coset = coset_fft(&coefficients, shift).unwrap();
+        assert_eq!(on_coset[3], evaluate(shift * roots[3]));
+        assert_eq!(coset_inverse_fft(&on_coset, shift).unwrap(), coefficients);
+        assert!(coset_inverse_fft(&on_coset, Scalar::zero()).is_err());
+
+        // A polynomial of degree below 8 extended from 8 to 16 points
+        let low = &coefficients[..8];
+        let blob = bit_reversal_permutation(&fft(low).unwrap());
+        let extended = extend_evaluations(&blob).unwrap();
+        let mut padded = low.to_vec();
+        padded.resize(16, Scalar::zero());
+        assert_eq!(extended, bit_reversal_permutation(&fft(&padded).unwrap()));
+        assert_eq!(extended[..8], blob[..]);
+
+        let points = (1..=8u64)
+            .map(|i| G1Projective::generator() * Scalar::from(i))
+            .collect::<Vec<_>>();
+        assert_eq!(g1_inverse_fft(&g1_fft(&points).unwrap()).unwrap(), points);
+        assert!(fft(&coefficients[..3]).is_err());
+    }
+
+    #[test]
+    fn test_roots_of_unity() {
+        for (k, root) in crate::SCALE2_ROOT_OF_UNITY.iter().enumerate() {
+            assert_eq!(root_of_unity(k as u32).unwrap(), Scalar::from_raw(*root));
+        }
+
+        // The largest root squares down to the others and has order exactly 2^32
+        let largest = root_of_unity(MAX_ROOT_OF_UNITY_SCALE).unwrap();
+        let half_turn = (0..MAX_ROOT_OF_UNITY_SCALE - 1).fold(largest, |x, _| x.square());
+        assert_eq!(half_turn, -Scalar::one());
+        assert_eq!(root_of_unity(1).unwrap(), -Scalar::one());
+        assert!(root_of_unity(MAX_ROOT_OF_UNITY_SCALE + 1).is_err());
+
+        let roots = roots_of_unity(6).unwrap();
+        assert_eq!(roots.len(), 64);
+        assert_eq!(roots[1], root_of_unity(6).unwrap());
+        assert_eq!(roots[32], -Scalar::one());
+        assert_eq!(roots_of_unity(0).unwrap(), vec![Scalar::one()]);
+        assert!(roots_of_unity(usize::BITS).is_err());
+    }
+
+    #[test]
+    fn test_evaluation_domain() {
+        let domain = EvaluationDomain::get(16).unwrap();
+        assert!(core::ptr::eq(domain, EvaluationDomain::get(16).unwrap()));
+        assert_eq!(domain, &EvaluationDomain::new(16).unwrap());
+        for (root, inverse) in domain.roots.iter().zip(&domain.inverse_roots) {
+            assert_eq!(root * inverse, Scalar::one());
+        }
+        assert_eq!(domain.bit_reversed_roots[1], domain.roots[8]);
+        assert_eq!(domain.size_inverse * Scalar::from(16), Scalar::one());
+
+        let x = Scalar::from(3);
+        assert_eq!(
+            domain.vanishing_at(x),
+            x.pow(&[16, 0, 0, 0]) - Scalar::one()
+        );
+        assert_eq!(domain.vanishing_at(domain.roots[5]), Scalar::zero());
+        assert!(EvaluationDomain::get(12).is_err());
+    }
+}
@@ -0,0 +1,211 @@
+//! Crate-owned scalar field type.
+//!
+//! [`FieldElement`] wraps `bls12_381::Scalar` so the public API does not expose the curve crate's
+//! field type directly. Its byte encoding is the canonical 32-byte big-endian form used by the
+//! consensus specs.
+
+use crate::dtypes::Bytes32;
+use crate::enums::KzgError;
+use crate::kzg_proof::{safe_scalar_affine_from_bytes, scalar_to_bytes32};
+
+use bls12_381::Scalar;
+use core::{
+    fmt,
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+/// An element of the BLS12-381 scalar field
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct FieldElement(Scalar);
+
+impl FieldElement {
+    pub const ZERO: Self = Self(Scalar::zero());
+    pub const ONE: Self = Self(Scalar::one());
+
+    /// Parses a canonical big-endian encoding, rejecting values not below the field modulus
+    pub fn from_bytes(bytes: &Bytes32) -> Result<Self, KzgError> {
+        safe_scalar_affine_from_bytes(bytes).map(Self)
+    }
+
+    /// Returns the canonical big-endian encoding
+    pub fn to_bytes(&self) -> Bytes32 {
+        scalar_to_bytes32(&self.0)
+    }
+
+    /// Returns the multiplicative inverse, or `None` for zero
+    pub fn invert(&self) -> Option<Self> {
+        Option::from(self.0.invert()).map(Self)
+    }
+
+    pub fn square(&self) -> Self {
+        Self(self.0.square())
+    }
+
+    pub fn pow(&self, exponent: u64) -> Self {
+        Self(self.0.pow_vartime(&[exponent, 0, 0, 0]))
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == Scalar::zero()
+    }
+
+    /// Reinterprets a slice of scalars as a slice of field elements without copying
+    pub fn from_scalars(scalars: &[Scalar]) -> &[Self] {
+        // SAFETY: `FieldElement` is a transparent wrapper around `Scalar`
+        unsafe { core::slice::from_raw_parts(scalars.as_ptr() as *const Self, scalars.len()) }
+    }
+
+    /// Reinterprets a slice of field elements as a slice of scalars without copying
+    pub fn as_scalars(elements: &[Self]) -> &[Scalar] {
+        // SAFETY: `FieldElement` is a transparent wrapper around `Scalar`
+        unsafe { core::slice::from_raw_parts(elements.as_ptr() as *const Scalar, elements.len()) }
+    }
+}
+
+impl fmt::Debug for FieldElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "FieldElement(0x{})",
+            hex::encode(self.to_bytes().as_slice())
+        )
+    }
+}
+
+impl From<Scalar> for FieldElement {
+    fn from(scalar: Scalar) -> Self {
+        Self(scalar)
+    }
+}
+
+impl From<FieldElement> for Scalar {
+    fn from(element: FieldElement) -> Self {
+        element.0
+    }
+}
+
+impl From<u64> for FieldElement {
+    fn from(value: u64) -> Self {
+        Self(Scalar::from(value))
+    }
+}
+
+impl TryFrom<&Bytes32> for FieldElement {
+    type Error = KzgError;
+
+    fn try_from(bytes: &Bytes32) -> Result<Self, KzgError> {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl From<FieldElement> for Bytes32 {
+    fn from(element: FieldElement) -> Self {
+        element.to_bytes()
+    }
+}
+
+macro_rules! impl_binary_op {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident) => {
+        impl $trait for FieldElement {
+            type Output = Self;
+
+            fn $method(self, rhs: Self) -> Self {
+                Self($trait::$method(self.0, rhs.0))
+            }
+        }
+
+        impl $assign_trait for FieldElement {
+            fn $assign_method(&mut self, rhs: Self) {
+                $assign_trait::$assign_method(&mut self.0, rhs.0);
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, AddAssign, add_assign);
+impl_binary_op!(Sub, sub, SubAssign, sub_assign);
+impl_binary_op!(Mul, mul, MulAssign, mul_assign);
+
+impl Neg for FieldElement {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+/// Serialized as a `0x`-prefixed hex string in human-readable formats and as 32 big-endian bytes
+/// otherwise
+#[cfg(feature = "serde")]
+impl serde::Serialize for FieldElement {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes = self.to_bytes();
+        if serializer.is_human_readable() {
+            let hex = format!("0x{}", hex::encode(bytes.as_slice()));
+            serializer.serialize_str(&hex)
+        } else {
+            serializer.serialize_bytes(bytes.as_slice())
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for FieldElement {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let bytes = if deserializer.is_human_readable() {
+            let hex: alloc::string::String = serde::Deserialize::deserialize(deserializer)?;
+            hex::decode(hex.strip_prefix("0x").unwrap_or(&hex)).map_err(D::Error::custom)?
+        } else {
+            serde::Deserialize::deserialize(deserializer)?
+        };
+        let bytes = Bytes32::from_slice(&bytes).map_err(D::Error::custom)?;
+        Self::from_bytes(&bytes).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_element() {
+        let a = FieldElement::from(7);
+        let b = FieldElement::from(5);
+        assert_eq!(a + b, FieldElement::from(12));
+        assert_eq!(a - b, FieldElement::from(2));
+        assert_eq!(a * b, FieldElement::from(35));
+        assert_eq!(b - a + FieldElement::from(2), FieldElement::ZERO);
+        assert_eq!(a * a.invert().unwrap(), FieldElement::ONE);
+        assert!(FieldElement::ZERO.invert().is_none());
+        assert_eq!(a.pow(3), a * a.square());
+
+        let bytes = (-FieldElement::ONE).to_bytes();
+        assert_eq!(
+            hex::encode(bytes.as_slice()),
+            "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000000"
+        );
+        assert_eq!(
+            FieldElement::from_bytes(&bytes).unwrap(),
+            -FieldElement::ONE
+        );
+        assert!(FieldElement::from_bytes(&Bytes32::from([0xff; 32])).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_field_element_serde() {
+        let element = FieldElement::from(0x1234);
+        let yaml = serde_yaml::to_string(&element).unwrap();
+        assert_eq!(
+            yaml.trim(),
+            "'0x0000000000000000000000000000000000000000000000000000000000001234'"
+        );
+        assert_eq!(
+            serde_yaml::from_str::<FieldElement>(&yaml).unwrap(),
+            element
+        );
+    }
+}
@@ -0,0 +1,195 @@
+//! Known-good and known-bad blob proofs for the integration tests of downstream crates, enabled
+//! by the `test-fixtures` feature.
+//!
+//! The blobs follow simple patterns, so the fixtures only embed a few bytes each and do not need
+//! the consensus spec test files. Their commitments and proofs are those of the mainnet trusted
+//! setup, [`KzgSettings::load_trusted_setup_file`](crate::KzgSettings::load_trusted_setup_file).
+
+use crate::dtypes::*;
+use crate::{BYTES_PER_BLOB, BYTES_PER_FIELD_ELEMENT};
+
+use alloc::{boxed::Box, vec::Vec};
+
+/// The field elements of a fixture blob
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pattern {
+    /// Every field element equals the value
+    Constant(u64),
+    /// Field element `i` is `i² + 1`
+    Squares,
+    /// Every byte is `0xff`, so no field element is canonical
+    NonCanonical,
+}
+
+/// A blob, commitment and proof with the outcome of verifying them
+#[derive(Debug, Clone)]
+pub struct BlobProofFixture {
+    /// Short description of the case
+    pub name: &'static str,
+    pattern: Pattern,
+    pub commitment: Bytes48,
+    pub proof: Bytes48,
+    /// `Some(valid)` if verification returns `valid`, `None` if it fails with an error
+    pub expected: Option<bool>,
+}
+
+impl BlobProofFixture {
+    /// Builds the fixture's blob, on the heap
+    pub fn blob(&self) -> Box<Blob> {
+        let mut bytes = vec![0u8; BYTES_PER_BLOB];
+        for (i, element) in bytes.chunks_mut(BYTES_PER_FIELD_ELEMENT).enumerate() {
+            let value = match self.pattern {
+                Pattern::Constant(value) => value,
+                Pattern::Squares => (i as u64) * (i as u64) + 1,
+                Pattern::NonCanonical => {
+                    element.fill(0xff);
+                    continue;
+                }
+            };
+            element[BYTES_PER_FIELD_ELEMENT - 8..].copy_from_slice(&value.to_be_bytes());
+        }
+        Blob::boxed_from_slice(&bytes).expect("the blob has the right size")
+    }
+}
+
+const INFINITY: [u8; 48] = point(
+    "c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+);
+const GENERATOR: [u8; 48] = point(
+    "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb",
+);
+const TWOS_COMMITMENT: [u8; 48] = point(
+    "a572cbea904d67468808c8eb50a9450c9721db309128012543902d0ac358a62ae28f75bb8f1c7c42c39a8c5529bf0f4e",
+);
+const SQUARES_COMMITMENT: [u8; 48] = point(
+    "b7c82ff57d986aa2622ffefb910b85ea77d9da8fccd2634bd6a125d2705a334525c781e9f58f0026123e02ef0eaa76dc",
+);
+const SQUARES_PROOF: [u8; 48] = point(
+    "b4b57d37d9fae09ddb27e7c36795d7c2e5f7d5f357c21c2589db8221da1af227becf71de81fbe57f03acb5bd1dde2834",
+);
+/// An x-coordinate above the field modulus
+const NOT_ON_CURVE: [u8; 48] = [0xbf; 48];
+
+/// Fixtures whose proofs verify
+pub fn valid_blob_proofs() -> Vec<BlobProofFixture> {
+    vec![
+        fixture(
+            "zero blob",
+            Pattern::Constant(0),
+            INFINITY,
+            INFINITY,
+            Some(true),
+        ),
+        fixture(
+            "constant blob",
+            Pattern::Constant(2),
+            TWOS_COMMITMENT,
+            INFINITY,
+            Some(true),
+        ),
+        fixture(
+            "squares blob",
+            Pattern::Squares,
+            SQUARES_COMMITMENT,
+            SQUARES_PROOF,
+            Some(true),
+        ),
+    ]
+}
+
+/// Fixtures whose proofs do not verify or whose inputs are rejected
+pub fn invalid_blob_proofs() -> Vec<BlobProofFixture> {
+    vec![
+        fixture(
+            "wrong proof",
+            Pattern::Squares,
+            SQUARES_COMMITMENT,
+            GENERATOR,
+            Some(false),
+        ),
+        fixture(
+            "wrong commitment",
+            Pattern::Squares,
+            TWOS_COMMITMENT,
+            SQUARES_PROOF,
+            Some(false),
+        ),
+        fixture(
+            "non-canonical blob",
+            Pattern::NonCanonical,
+            INFINITY,
+            INFINITY,
+            None,
+        ),
+        fixture(
+            "commitment not on the curve",
+            Pattern::Constant(2),
+            NOT_ON_CURVE,
+            INFINITY,
+            None,
+        ),
+    ]
+}
+
+/// Every fixture, the valid ones first
+pub fn all_blob_proofs() -> Vec<BlobProofFixture> {
+    let mut fixtures = valid_blob_proofs();
+    fixtures.extend(invalid_blob_proofs());
+    fixtures
+}
+
+fn fixture(
+    name: &'static str,
+    pattern: Pattern,
+    commitment: [u8; 48],
+    proof: [u8; 48],
+    expected: Option<bool>,
+) -> BlobProofFixture {
+    BlobProofFixture {
+        name,
+        pattern,
+        commitment: commitment.into(),
+        proof: proof.into(),
+        expected,
+    }
+}
+
+const fn point(hex: &str) -> [u8; 48] {
+    const fn nibble(c: u8) -> u8 {
+        match c {
+            b'0'..=b'9' => c - b'0',
+            b'a'..=b'f' => c - b'a' + 10,
+            _ => panic!("invalid hex digit"),
+        }
+    }
+    let hex = hex.as_bytes();
+    assert!(hex.len() == 96);
+    let mut bytes = [0u8; 48];
+    let mut i = 0;
+    while i < 48 {
+        bytes[i] = nibble(hex[2 * i]) << 4 | nibble(hex[2 * i + 1]);
+        i += 1;
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg_proof::KzgProof;
+    use crate::trusted_setup::KzgSettings;
+
+    #[test]
+    fn test_fixtures() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        for fixture in all_blob_proofs() {
+            let result = KzgProof::verify_blob_kzg_proof(
+                *fixture.blob(),
+                &fixture.commitment,
+                &fixture.proof,
+                &kzg_settings,
+            );
+            assert_eq!(result.ok(), fixture.expected, "{}", fixture.name);
+        }
+    }
+}
@@ -0,0 +1,219 @@
+//! FK20 amortized KZG proofs.
+//!
+//! Opening a polynomial of degree below `n` at every coset `h·H` of `l` points of a domain costs
+//! one MSM of `n` points per coset when done one coset at a time. Following Feist and
+//! Khovratovich, the proof for the coset with vanishing polynomial `X^l - a` is `Σ_s a^s H_s`,
+//! where the `k = n / l` commitments `H_s` do not depend on the coset. They are the product of a
+//! Toeplitz matrix of coefficients with vectors of setup points, computed with FFTs of size `2k`,
+//! and evaluating `Σ_s a^s H_s` at every `a` is one more FFT over G1. All proofs then cost
+//! `O(n log n)` group operations instead of `O(n²)`.
+//!
+//! The setup side of the product only depends on the trusted setup and `l`, so it is computed
+//! once per setup and kept.
+
+use crate::cancel::CancelToken;
+use crate::cells::{blob_to_coefficients, check_settings};
+use crate::compression::{compress_g1_projective_batch, normalize_g1_batch};
+use crate::dtypes::*;
+use crate::enums::KzgError;
+use crate::fft::{bit_reversal_permutation, fft, g1_fft, g1_inverse_fft};
+use crate::msm::g1_msm_affine;
+use crate::progress::Progress;
+use crate::trusted_setup::{KzgSettings, SetupCache};
+
+use alloc::{boxed::Box, vec::Vec};
+use bls12_381::{G1Affine, G1Projective, Scalar};
+
+/// Setup side of the FK20 product for cosets of `cell_size` points: row `r` holds, for each
+/// offset `i` within a coset, the `r`-th evaluation of the setup points the coefficients at
+/// offset `i` are multiplied with
+#[derive(Debug)]
+pub(crate) struct Fk20Table {
+    cell_size: usize,
+    rows: Vec<Vec<G1Affine>>,
+}
+
+/// Returns the FK20 table of `kzg_settings` for cosets of `cell_size` points, building it on
+/// first use
+pub(crate) fn fk20_table(
+    kzg_settings: &KzgSettings,
+    cell_size: usize,
+) -> Result<&'static Fk20Table, KzgError> {
+    static TABLES: SetupCache<usize, Fk20Table> = SetupCache::new();
+    TABLES.get_or_try_insert_with(kzg_settings.g1_points, cell_size, || {
+        Ok(Box::leak(Box::new(build_table(
+            &kzg_settings.g1_monomial()?,
+            cell_size,
+        )?)))
+    })
+}
+
+fn build_table(monomial: &[G1Affine], cell_size: usize) -> Result<Fk20Table, KzgError> {
+    let n = monomial.len();
+    if cell_size == 0 || !cell_size.is_power_of_two() || n < 2 * cell_size {
+        return Err(KzgError::BadArgs(format!(
+            "Cannot open a {}-point setup at cosets of {} points",
+            n, cell_size
+        )));
+    }
+    let k = n / cell_size;
+
+    let mut rows = vec![Vec::with_capacity(cell_size); 2 * k];
+    for offset in 0..cell_size {
+        // [τ^((k - 2 - u)·l + offset)] for u < k - 1, so that the product below is a convolution
+        let mut points = vec![G1Projective::identity(); 2 * k];
+        for (u, point) in points[..k - 1].iter_mut().enumerate() {
+            *point = monomial[(k - 2 - u) * cell_size + offset].into();
+        }
+        for (row, point) in rows.iter_mut().zip(g1_fft(&points)?) {
+            row.push(point);
+        }
+    }
+
+    // Stored in affine form, normalized together, so the MSMs over the rows use mixed additions
+    let affine = normalize_g1_batch(&rows.concat());
+    let rows = affine.chunks(cell_size).map(<[G1Affine]>::to_vec).collect();
+    Ok(Fk20Table { cell_size, rows })
+}
+
+/// Computes the `k` commitments `H_s` shared by the openings of the polynomial with the given
+/// coefficients at all cosets of the table's size. `on_progress` is called after each of the
+/// `2k` MSMs, and `cancel` checked before each.
+fn compute_h(
+    coefficients: &[Scalar],
+    table: &Fk20Table,
+    on_progress: &mut dyn FnMut(Progress),
+    cancel: Option<&CancelToken>,
+) -> Result<Vec<G1Projective>, KzgError> {
+    let l = table.cell_size;
+    let k = coefficients.len() / l;
+
+    // H_s = Σ_i Σ_{j ≥ s} c_{(j + 1)·l + i} · [τ^((j - s)·l + i)], a convolution for each offset i
+    let columns = (0..l)
+        .map(|offset| {
+            let mut column = vec![Scalar::zero(); 2 * k];
+            for (j, value) in column[..k - 1].iter_mut().enumerate() {
+                *value = coefficients[(j + 1) * l + offset];
+            }
+            fft(&column)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut products = Vec::with_capacity(2 * k);
+    for (r, row) in table.rows.iter().enumerate() {
+        if let Some(cancel) = cancel {
+            cancel.check()?;
+        }
+        let scalars = columns.iter().map(|column| column[r]).collect::<Vec<_>>();
+        products.push(g1_msm_affine(row, &scalars));
+        on_progress(Progress {
+            completed: r + 1,
+            total: 2 * k,
+        });
+    }
+
+    // Back from the evaluations to the convolution, of which H_s is entry s + k - 2
+    let mut h = g1_inverse_fft(&products)?[k - 2..2 * k - 3].to_vec();
+    h.push(G1Projective::identity());
+    Ok(h)
+}
+
+/// Computes the proofs opening the polynomial with the given coefficients at each of the
+/// `2n / cell_size` cosets of `cell_size` points of the domain of order `2n`, in bit-reversed
+/// order like the cells
+pub(crate) fn compute_fk20_cell_proofs(
+    coefficients: &[Scalar],
+    cell_size: usize,
+    kzg_settings: &KzgSettings,
+    on_progress: &mut dyn FnMut(Progress),
+    cancel: Option<&CancelToken>,
+) -> Result<Vec<Bytes48>, KzgError> {
+    let table = fk20_table(kzg_settings, cell_size)?;
+    let mut h = compute_h(coefficients, table, on_progress, cancel)?;
+
+    // The coset of cell c has a = h_c^l, the 2k-th root of unity at bit-reversed position c
+    h.resize(2 * h.len(), G1Projective::identity());
+    let proofs = g1_fft(&h)?;
+    Ok(compress_g1_projective_batch(&bit_reversal_permutation(
+        &proofs,
+    )))
+}
+
+/// Computes the proofs opening `blob` at each point of its domain: proof `i` opens it at
+/// `kzg_settings.roots_of_unity[i]`, where the blob's polynomial takes the value of its `i`-th
+/// field element
+pub fn compute_all_kzg_proofs(
+    blob: &Blob,
+    kzg_settings: &KzgSettings,
+) -> Result<Vec<Bytes48>, KzgError> {
+    check_settings(kzg_settings)?;
+    compute_fk20_point_proofs(&blob_to_coefficients(blob)?, kzg_settings)
+}
+
+/// Computes the proofs opening the polynomial with the given coefficients, as many as the setup
+/// has points, at each of the setup's roots of unity, in the setup's bit-reversed order
+fn compute_fk20_point_proofs(
+    coefficients: &[Scalar],
+    kzg_settings: &KzgSettings,
+) -> Result<Vec<Bytes48>, KzgError> {
+    let table = fk20_table(kzg_settings, 1)?;
+    let h = compute_h(coefficients, table, &mut |_| {}, None)?;
+
+    // With cosets of a single point, a is the point itself
+    let proofs = g1_fft(&h)?;
+    Ok(compress_g1_projective_batch(&bit_reversal_permutation(
+        &proofs,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg_proof::{safe_g1_affine_from_bytes, verify_kzg_proof_impl};
+    use crate::msm::g1_msm;
+
+    #[test]
+    fn test_compute_fk20_point_proofs() {
+        // A smaller setup from the same ceremony keeps the G1 FFTs short
+        let kzg_settings = KzgSettings::load_trusted_setup_file()
+            .unwrap()
+            .subset(256)
+            .unwrap();
+        let coefficients = (0..256u64)
+            .map(|i| Scalar::from(i * i + 7))
+            .collect::<Vec<_>>();
+        let monomial = kzg_settings
+            .g1_monomial()
+            .unwrap()
+            .iter()
+            .map(G1Projective::from)
+            .collect::<Vec<_>>();
+        let commitment = g1_msm(&monomial, &coefficients).into();
+
+        let proofs = compute_fk20_point_proofs(&coefficients, &kzg_settings).unwrap();
+        assert_eq!(proofs.len(), 256);
+        for i in [0, 1, 100, 255] {
+            let z = kzg_settings.roots_of_unity[i];
+            let y = coefficients
+                .iter()
+                .rev()
+                .fold(Scalar::zero(), |acc, coefficient| acc * z + coefficient);
+            let proof = safe_g1_affine_from_bytes(&proofs[i]).unwrap();
+            assert!(verify_kzg_proof_impl(commitment, z, y, proof, &kzg_settings).unwrap());
+            let other = safe_g1_affine_from_bytes(&proofs[(i + 1) % 256]).unwrap();
+            assert!(!verify_kzg_proof_impl(commitment, z, y, other, &kzg_settings).unwrap());
+        }
+
+        // Reloading the same setup reuses its table
+        let reloaded = KzgSettings::load_trusted_setup_file()
+            .unwrap()
+            .subset(256)
+            .unwrap();
+        assert!(core::ptr::eq(
+            fk20_table(&kzg_settings, 1).unwrap(),
+            fk20_table(&reloaded, 1).unwrap()
+        ));
+
+        assert!(build_table(&kzg_settings.g1_monomial().unwrap()[..4], 4).is_err());
+    }
+}
@@ -0,0 +1,102 @@
+//! Fixed-base multiplication by the G1 and G2 generators.
+//!
+//! Every verification multiplies the G1 generator by the claimed evaluation `y` and the G2
+//! generator by the evaluation point `z`. Both bases are fixed, so a table of the multiples
+//! `d · 2^(4k) · G` of each generator, for every 4-bit digit `d` and window `k`, turns the
+//! multiplication into one addition per nonzero digit of the scalar, with no doublings. The tables
+//! take about 100 KiB for G1 and 190 KiB for G2 and are built on first use.
+//!
+//! The lookups depend on the digits of the scalar, so these multiplications are only meant for
+//! the public values of the verification equations.
+
+use alloc::vec::Vec;
+use bls12_381::{G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
+use core::ops::{Add, AddAssign};
+use spin::Once;
+
+/// Bits of the scalar per window
+const WINDOW_BITS: usize = 4;
+/// Windows covering the 256 bits of a scalar encoding
+const WINDOWS: usize = 256 / WINDOW_BITS;
+/// Nonzero digits of a window
+const DIGITS: usize = (1 << WINDOW_BITS) - 1;
+
+/// Returns `scalar · G1`
+pub fn g1_generator_mul(scalar: &Scalar) -> G1Projective {
+    static TABLE: Once<GeneratorTable<G1Affine>> = Once::new();
+    TABLE
+        .call_once(|| GeneratorTable::new(G1Projective::generator(), G1Projective::batch_normalize))
+        .mul(scalar)
+}
+
+/// Returns `scalar · G2`
+pub fn g2_generator_mul(scalar: &Scalar) -> G2Projective {
+    static TABLE: Once<GeneratorTable<G2Affine>> = Once::new();
+    TABLE
+        .call_once(|| GeneratorTable::new(G2Projective::generator(), G2Projective::batch_normalize))
+        .mul(scalar)
+}
+
+/// The multiples `d · 2^(4k) · G` of a generator, the nonzero digits `d` of window `k` at
+/// `k · DIGITS..(k + 1) · DIGITS`
+struct GeneratorTable<A> {
+    points: Vec<A>,
+}
+
+impl<A: Copy + Default> GeneratorTable<A> {
+    fn new<P>(generator: P, normalize: fn(&[P], &mut [A])) -> Self
+    where
+        P: Copy + Add<Output = P>,
+    {
+        let mut multiples = Vec::with_capacity(WINDOWS * DIGITS);
+        let mut base = generator;
+        for _ in 0..WINDOWS {
+            let mut multiple = base;
+            for _ in 0..DIGITS {
+                multiples.push(multiple);
+                multiple = multiple + base;
+            }
+            // `2^WINDOW_BITS` times the base of this window
+            base = multiple;
+        }
+
+        let mut points = vec![A::default(); multiples.len()];
+        normalize(&multiples, &mut points);
+        Self { points }
+    }
+
+    fn mul<P>(&self, scalar: &Scalar) -> P
+    where
+        P: Default + for<'a> AddAssign<&'a A>,
+    {
+        let mut acc = P::default();
+        for (i, byte) in scalar.to_bytes().iter().enumerate() {
+            for (k, digit) in [(2 * i, byte & 0xf), (2 * i + 1, byte >> 4)] {
+                if digit != 0 {
+                    acc += &self.points[k * DIGITS + digit as usize - 1];
+                }
+            }
+        }
+        acc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generator_mul() {
+        let scalars = [
+            Scalar::zero(),
+            Scalar::one(),
+            -Scalar::one(),
+            Scalar::from(0xf0f0),
+            Scalar::from_raw([u64::MAX, 7, u64::MAX, 0x1234_5678_9abc_def0]),
+        ];
+        for scalar in scalars {
+            assert_eq!(g1_generator_mul(&scalar), G1Affine::generator() * scalar);
+            assert_eq!(g2_generator_mul(&scalar), G2Affine::generator() * scalar);
+        }
+    }
+}
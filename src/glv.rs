@@ -0,0 +1,221 @@
+//! GLV scalar multiplication on G1, with the `glv` feature.
+//!
+//! G1 has the endomorphism `φ(x, y) = (βx, y)`, with `β` a cube root of unity in the base field,
+//! which acts on the prime-order subgroup as multiplication by `λ = z² - 1`, `z` being the curve
+//! parameter. The group order is `λ² + λ + 1`, so every scalar splits as `k = k₁ + k₂·λ` with
+//! 128-bit halves, and `k·P = k₁·P + k₂·φ(P)` shares 128 doublings between both halves instead of
+//! doubling 255 times.
+//!
+//! The multiplication branches on the bits of the scalar, so it is not constant time. It is only
+//! applied to the public batching challenges of the verification equations, in batches too small
+//! for Pippenger and in `BatchAccumulator`, and stays behind a feature for users who want the
+//! constant-time multiplication of `bls12_381` everywhere. Multiples of the generators, such as
+//! the claimed evaluations, use the faster fixed-base tables of `generators` instead.
+
+use bls12_381::{G1Affine, G1Projective, Scalar};
+use ff::derive::{adc, sbb};
+
+/// `λ = z² - 1`, the eigenvalue of [`endomorphism`] on the prime-order subgroup
+const LAMBDA: u128 = 0xac45_a401_0001_a402_0000_0000_ffff_ffff;
+
+/// Modulus of the base field, in little-endian 64-bit limbs
+const FP_MODULUS: [u64; 6] = [
+    0xb9fe_ffff_ffff_aaab,
+    0x1eab_fffe_b153_ffff,
+    0x6730_d2a0_f6b0_f624,
+    0x6477_4b84_f385_12bf,
+    0x4b1b_a7b6_434b_acd7,
+    0x1a01_11ea_397f_e69a,
+];
+
+/// The cube root of unity `β` of [`endomorphism`], in little-endian 64-bit limbs
+const BETA: [u64; 6] = [
+    0x8bfd_0000_0000_aaac,
+    0x4094_27eb_4f49_fffd,
+    0x897d_2965_0fb8_5f9b,
+    0xaa0d_857d_8975_9ad4,
+    0xec02_4086_63d4_de85,
+    0x1a01_11ea_397f_e699,
+];
+
+/// Returns `scalar · point` with the GLV method. For points outside the prime-order subgroup,
+/// which parsing with `SubgroupCheck::Skip` lets through, the result differs from
+/// `point * scalar`.
+pub fn g1_mul_glv(point: &G1Affine, scalar: &Scalar) -> G1Projective {
+    #[cfg(test)]
+    tests::GLV_MULS.with(|count| count.set(count.get() + 1));
+
+    if bool::from(point.is_identity()) {
+        return G1Projective::identity();
+    }
+
+    let (k1, k2) = decompose(scalar);
+    let endomorphism = endomorphism(point);
+    let both = G1Affine::from(G1Projective::from(point) + endomorphism);
+
+    let mut acc = G1Projective::identity();
+    for i in (0..128).rev() {
+        acc = acc.double();
+        match ((k1 >> i) & 1, (k2 >> i) & 1) {
+            (1, 0) => acc += point,
+            (0, 1) => acc += endomorphism,
+            (1, 1) => acc += both,
+            _ => {}
+        }
+    }
+    acc
+}
+
+/// Returns `φ(point) = (βx, y)`, which is `λ · point` on the prime-order subgroup
+pub fn endomorphism(point: &G1Affine) -> G1Affine {
+    if bool::from(point.is_identity()) {
+        return *point;
+    }
+
+    // The flags of a finite point are zero, so the first 48 bytes are exactly `x`
+    let mut bytes = point.to_uncompressed();
+    let mut x = [0u64; 6];
+    for (limb, chunk) in x.iter_mut().zip(bytes[..48].rchunks(8)) {
+        *limb = u64::from_be_bytes(chunk.try_into().unwrap());
+    }
+    let x = fp_mul(&x, &BETA);
+    for (limb, chunk) in x.iter().zip(bytes[..48].rchunks_mut(8)) {
+        chunk.copy_from_slice(&limb.to_be_bytes());
+    }
+    G1Affine::from_uncompressed_unchecked(&bytes).unwrap()
+}
+
+/// Splits `scalar` as `k₁ + k₂·λ` with `k₁ < λ`, dividing it by `λ`
+fn decompose(scalar: &Scalar) -> (u128, u128) {
+    let bytes = scalar.to_bytes();
+    let low = u128::from_le_bytes(bytes[..16].try_into().unwrap());
+    let high = u128::from_le_bytes(bytes[16..].try_into().unwrap());
+
+    // The scalar is below `λ² + λ + 1`, so `high < λ` and the quotient fits in 128 bits
+    let mut remainder = high;
+    let mut quotient = 0;
+    for i in (0..128).rev() {
+        let overflow = remainder >> 127 == 1;
+        remainder = (remainder << 1) | ((low >> i) & 1);
+        quotient <<= 1;
+        if overflow || remainder >= LAMBDA {
+            remainder = remainder.wrapping_sub(LAMBDA);
+            quotient |= 1;
+        }
+    }
+    (remainder, quotient)
+}
+
+/// Returns `a · b mod p` by double-and-add over the bits of `b`, which is plenty for the single
+/// multiplication of each [`endomorphism`]
+fn fp_mul(a: &[u64; 6], b: &[u64; 6]) -> [u64; 6] {
+    let mut acc = [0u64; 6];
+    for limb in b.iter().rev() {
+        for bit in (0..64).rev() {
+            acc = fp_add(&acc, &acc);
+            if (limb >> bit) & 1 == 1 {
+                acc = fp_add(&acc, a);
+            }
+        }
+    }
+    acc
+}
+
+/// Returns `a + b mod p` for reduced `a` and `b`
+fn fp_add(a: &[u64; 6], b: &[u64; 6]) -> [u64; 6] {
+    // Both are below `p < 2^381`, so the sum fits in the limbs and one subtraction reduces it
+    let mut sum = [0u64; 6];
+    let mut carry = 0;
+    for ((sum, a), b) in sum.iter_mut().zip(a).zip(b) {
+        (*sum, carry) = adc(*a, *b, carry);
+    }
+
+    let mut difference = [0u64; 6];
+    let mut borrow = 0;
+    for ((difference, sum), modulus) in difference.iter_mut().zip(&sum).zip(&FP_MODULUS) {
+        (*difference, borrow) = sbb(*sum, *modulus, borrow);
+    }
+    if borrow == 0 {
+        difference
+    } else {
+        sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    std::thread_local! {
+        /// Number of GLV multiplications on this thread
+        pub(super) static GLV_MULS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    #[test]
+    fn test_glv() {
+        let lambda = Scalar::from_raw([LAMBDA as u64, (LAMBDA >> 64) as u64, 0, 0]);
+        let generator = G1Affine::generator();
+        assert_eq!(
+            G1Projective::from(endomorphism(&generator)),
+            generator * lambda
+        );
+
+        let point = G1Affine::from(generator * Scalar::from(0x1234_5678));
+        let scalars = [
+            Scalar::zero(),
+            Scalar::one(),
+            -Scalar::one(),
+            lambda,
+            lambda + Scalar::one(),
+            Scalar::from_raw([u64::MAX, u64::MAX, 0x1234, 0]),
+            Scalar::from_raw([7, 0xdead_beef, 0xcafe, 0x73ed_a753_299d_7d47]),
+        ];
+        for scalar in scalars {
+            assert_eq!(g1_mul_glv(&generator, &scalar), generator * scalar);
+            assert_eq!(g1_mul_glv(&point, &scalar), point * scalar);
+        }
+        assert_eq!(
+            g1_mul_glv(&G1Affine::identity(), &lambda),
+            G1Projective::identity()
+        );
+    }
+
+    #[cfg(feature = "prover")]
+    #[test]
+    fn test_batch_verification_uses_glv() {
+        use crate::kzg_proof::{safe_g1_affine_from_bytes, tests::valid_kzg_proof_cases};
+        use crate::{FieldElement, KzgProof, KzgSettings};
+
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let cases = valid_kzg_proof_cases()
+            .into_iter()
+            .filter(|case| case.output == Some(true))
+            .take(2)
+            .collect::<Vec<_>>();
+        let commitments = cases
+            .iter()
+            .map(|case| safe_g1_affine_from_bytes(&case.commitment).unwrap())
+            .collect::<Vec<_>>();
+        let zs = cases
+            .iter()
+            .map(|case| FieldElement::from_bytes(&case.z).unwrap())
+            .collect::<Vec<_>>();
+        let ys = cases
+            .iter()
+            .map(|case| FieldElement::from_bytes(&case.y).unwrap())
+            .collect::<Vec<_>>();
+        let proofs = cases
+            .iter()
+            .map(|case| safe_g1_affine_from_bytes(&case.proof).unwrap())
+            .collect::<Vec<_>>();
+
+        let before = GLV_MULS.with(Cell::get);
+        assert!(
+            KzgProof::verify_kzg_proof_batch(&commitments, &zs, &ys, &proofs, &kzg_settings)
+                .unwrap()
+        );
+        // The three MSMs of the batch equation each multiply both openings
+        assert_eq!(GLV_MULS.with(Cell::get) - before, 6);
+    }
+}
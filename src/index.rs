@@ -0,0 +1,275 @@
+//! Bounded record of verified commitments.
+//!
+//! [`CommitmentIndex`] maps the versioned hash of every commitment that passed verification to
+//! the commitment and when it was first seen, so RPC layers can answer "has this hash been
+//! verified?" without keeping their own cache. Under `std`, [`IndexedBackend`] keeps an index up
+//! to date from the results of a wrapped backend.
+
+use crate::dtypes::*;
+use crate::utils::kzg_to_versioned_hash;
+
+use alloc::collections::{BTreeMap, VecDeque};
+
+#[cfg(feature = "std")]
+use crate::{backend::KzgBackend, enums::KzgError, trusted_setup::KzgSettings};
+#[cfg(feature = "std")]
+use std::sync::{Mutex, MutexGuard};
+
+/// A commitment recorded in a [`CommitmentIndex`]
+#[derive(Debug, Clone)]
+pub struct IndexEntry {
+    pub commitment: Bytes48,
+    /// Position of the commitment in the order the index first saw each commitment
+    pub first_seen: u64,
+}
+
+/// Map from versioned hash to commitment holding at most `capacity` entries. When full, the
+/// commitment seen first is evicted.
+#[derive(Debug, Clone)]
+pub struct CommitmentIndex {
+    entries: BTreeMap<[u8; 32], IndexEntry>,
+    order: VecDeque<[u8; 32]>,
+    capacity: usize,
+    next_sequence: u64,
+}
+
+impl CommitmentIndex {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            order: VecDeque::new(),
+            capacity,
+            next_sequence: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Records `commitment` under its versioned hash, returning `false` if it was already known.
+    /// A known commitment keeps its original entry.
+    pub fn insert(&mut self, commitment: &Bytes48) -> bool {
+        if self.capacity == 0 {
+            return false;
+        }
+
+        let versioned_hash: [u8; 32] = kzg_to_versioned_hash(commitment).into();
+        if self.entries.contains_key(&versioned_hash) {
+            return false;
+        }
+
+        if self.entries.len() == self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+
+        self.entries.insert(
+            versioned_hash,
+            IndexEntry {
+                commitment: commitment.clone(),
+                first_seen: self.next_sequence,
+            },
+        );
+        self.order.push_back(versioned_hash);
+        self.next_sequence += 1;
+        true
+    }
+
+    pub fn get(&self, versioned_hash: &Bytes32) -> Option<&IndexEntry> {
+        let key: &[u8; 32] = versioned_hash.as_slice().try_into().ok()?;
+        self.entries.get(key)
+    }
+
+    pub fn contains(&self, versioned_hash: &Bytes32) -> bool {
+        self.get(versioned_hash).is_some()
+    }
+
+    /// Removes every entry and restarts the `first_seen` sequence from zero
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+        self.next_sequence = 0;
+    }
+}
+
+/// Backend that forwards to `B` and records the commitments of every successful verification in
+/// a shared [`CommitmentIndex`]
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct IndexedBackend<B> {
+    pub backend: B,
+    index: Mutex<CommitmentIndex>,
+}
+
+#[cfg(feature = "std")]
+impl<B: KzgBackend> IndexedBackend<B> {
+    pub fn new(backend: B, capacity: usize) -> Self {
+        Self {
+            backend,
+            index: Mutex::new(CommitmentIndex::new(capacity)),
+        }
+    }
+
+    /// Locks and returns the index, for lookups or to clear it
+    pub fn index(&self) -> MutexGuard<'_, CommitmentIndex> {
+        self.index.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Returns `true` if a commitment with this versioned hash was verified and is still indexed
+    pub fn contains(&self, versioned_hash: &Bytes32) -> bool {
+        self.index().contains(versioned_hash)
+    }
+
+    fn record<'a>(
+        &self,
+        commitments: impl IntoIterator<Item = &'a Bytes48>,
+        result: Result<bool, KzgError>,
+    ) -> Result<bool, KzgError> {
+        if let Ok(true) = result {
+            let mut index = self.index();
+            for commitment in commitments {
+                index.insert(commitment);
+            }
+        }
+        result
+    }
+}
+
+#[cfg(feature = "std")]
+impl<B: KzgBackend> KzgBackend for IndexedBackend<B> {
+    fn name(&self) -> &'static str {
+        self.backend.name()
+    }
+
+    fn verify_kzg_proof(
+        &self,
+        commitment_bytes: &Bytes48,
+        z_bytes: &Bytes32,
+        y_bytes: &Bytes32,
+        proof_bytes: &Bytes48,
+        kzg_settings: &KzgSettings,
+    ) -> Result<bool, KzgError> {
+        let result = self.backend.verify_kzg_proof(
+            commitment_bytes,
+            z_bytes,
+            y_bytes,
+            proof_bytes,
+            kzg_settings,
+        );
+        self.record([commitment_bytes], result)
+    }
+
+    #[cfg(feature = "prover")]
+    fn verify_blob_kzg_proof(
+        &self,
+        blob: &Blob,
+        commitment_bytes: &Bytes48,
+        proof_bytes: &Bytes48,
+        kzg_settings: &KzgSettings,
+    ) -> Result<bool, KzgError> {
+        let result =
+            self.backend
+                .verify_blob_kzg_proof(blob, commitment_bytes, proof_bytes, kzg_settings);
+        self.record([commitment_bytes], result)
+    }
+
+    #[cfg(feature = "prover")]
+    fn verify_blob_kzg_proof_batch(
+        &self,
+        blobs: &[Blob],
+        commitments_bytes: &[Bytes48],
+        proofs_bytes: &[Bytes48],
+        kzg_settings: &KzgSettings,
+    ) -> Result<bool, KzgError> {
+        let result = self.backend.verify_blob_kzg_proof_batch(
+            blobs,
+            commitments_bytes,
+            proofs_bytes,
+            kzg_settings,
+        );
+        self.record(commitments_bytes, result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commitment(i: u8) -> Bytes48 {
+        Bytes48::from([i; 48])
+    }
+
+    #[test]
+    fn test_commitment_index() {
+        let mut index = CommitmentIndex::new(2);
+        assert!(index.insert(&commitment(1)));
+        assert!(!index.insert(&commitment(1)));
+        assert!(index.insert(&commitment(2)));
+
+        let hash = kzg_to_versioned_hash(&commitment(1));
+        let entry = index.get(&hash).unwrap();
+        assert_eq!(entry.commitment.as_slice(), commitment(1).as_slice());
+        assert_eq!(entry.first_seen, 0);
+
+        // The first commitment seen is evicted once the index is full
+        assert!(index.insert(&commitment(3)));
+        assert_eq!(index.len(), 2);
+        assert!(!index.contains(&hash));
+        assert_eq!(
+            index
+                .get(&kzg_to_versioned_hash(&commitment(3)))
+                .unwrap()
+                .first_seen,
+            2
+        );
+
+        index.clear();
+        assert!(index.is_empty());
+        assert!(index.insert(&commitment(3)));
+        assert_eq!(
+            index
+                .get(&kzg_to_versioned_hash(&commitment(3)))
+                .unwrap()
+                .first_seen,
+            0
+        );
+
+        assert!(!CommitmentIndex::new(0).insert(&commitment(1)));
+    }
+
+    #[cfg(all(feature = "std", feature = "prover"))]
+    #[test]
+    fn test_indexed_backend() {
+        use crate::backend::RustBackend;
+        use crate::kzg_proof::tests::valid_kzg_proof_cases;
+
+        let backend = IndexedBackend::new(RustBackend, 1024);
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+
+        for case in valid_kzg_proof_cases() {
+            let valid = backend
+                .verify_kzg_proof(
+                    &case.commitment,
+                    &case.z,
+                    &case.y,
+                    &case.proof,
+                    &kzg_settings,
+                )
+                .unwrap_or(false);
+            if valid {
+                assert!(backend.contains(&kzg_to_versioned_hash(&case.commitment)));
+            }
+        }
+        assert!(!backend.index().is_empty());
+    }
+}
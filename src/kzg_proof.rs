@@ -1,17 +1,29 @@
-use core::num::NonZeroUsize;
-use core::ops::Mul;
-
 use crate::enums::KzgError;
-use crate::trusted_setup::KzgSettings;
-use crate::{
-    dtypes::*, pairings_verify, BYTES_PER_BLOB, BYTES_PER_COMMITMENT, BYTES_PER_FIELD_ELEMENT,
-    BYTES_PER_PROOF, CHALLENGE_INPUT_SIZE, DOMAIN_STR_LENGTH, FIAT_SHAMIR_PROTOCOL_DOMAIN, MODULUS,
-    NUM_FIELD_ELEMENTS_PER_BLOB, RANDOM_CHALLENGE_KZG_BATCH_DOMAIN,
-};
+use crate::generators::{g1_generator_mul, g2_generator_mul};
+use crate::trusted_setup::{pairings_verify_cached, KzgSettings};
+use crate::utils::batch_inversion_skip_zeros;
+pub use crate::utils::compute_powers;
+#[cfg(feature = "prover")]
+use crate::FieldElement;
+use crate::{dtypes::*, BYTES_PER_COMMITMENT, BYTES_PER_FIELD_ELEMENT, BYTES_PER_PROOF, MODULUS};
 
 use alloc::{string::ToString, vec::Vec};
-use bls12_381::{G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
+use bls12_381::{G1Affine, G2Affine, Scalar};
 use ff::derive::sbb;
+
+#[cfg(feature = "prover")]
+use crate::{
+    cancel::CancelToken,
+    compression::{decompress_g1_batch, normalize_g1_batch},
+    msm::{g1_msm, g1_msm_affine},
+    polynomial::Polynomial,
+    progress::{self, Progress},
+    utils::kzg_to_versioned_hash,
+    BYTES_PER_BLOB, DOMAIN_STR_LENGTH,
+};
+#[cfg(feature = "prover")]
+use bls12_381::{G1Projective, G2Projective};
+#[cfg(feature = "prover")]
 use sha2::{Digest, Sha256};
 
 pub fn safe_g1_affine_from_bytes(bytes: &Bytes48) -> Result<G1Affine, KzgError> {
@@ -42,33 +54,56 @@ pub fn safe_scalar_affine_from_bytes(bytes: &Bytes32) -> Result<Scalar, KzgError
     Ok(scalar.unwrap())
 }
 
-/// Return the Fiat-Shamir challenge required to verify `blob` and `commitment`.
-fn compute_challenge(blob: &Blob, commitment: &G1Affine) -> Result<Scalar, KzgError> {
-    let mut bytes = [0_u8; CHALLENGE_INPUT_SIZE];
-    let mut offset = 0_usize;
-    // Copy domain separator
-    bytes[offset..DOMAIN_STR_LENGTH].copy_from_slice(FIAT_SHAMIR_PROTOCOL_DOMAIN.as_bytes());
-    offset += DOMAIN_STR_LENGTH;
-    // Copy polynomial degree (16-bytes, big-endian)
-    bytes[offset..offset + 8].copy_from_slice(&0_u64.to_be_bytes());
-    offset += 8;
-    bytes[offset..offset + 8].copy_from_slice(&(NUM_FIELD_ELEMENTS_PER_BLOB as u64).to_be_bytes());
-    offset += 8;
-    // Copy blob
-    bytes[offset..offset + BYTES_PER_BLOB].copy_from_slice(blob.as_slice());
-    offset += BYTES_PER_BLOB;
-    // Copy commitment
-    bytes[offset..offset + BYTES_PER_COMMITMENT].copy_from_slice(&commitment.to_compressed());
-    offset += BYTES_PER_COMMITMENT;
-    /* Make sure we wrote the entire buffer */
-    if offset != CHALLENGE_INPUT_SIZE {
-        return Err(KzgError::InvalidBytesLength(format!(
-            "The challenge should be {} length, but was {}",
-            CHALLENGE_INPUT_SIZE, offset,
-        )));
-    }
-    let evaluation: [u8; 32] = Sha256::digest(bytes).into();
-    Ok(scalar_from_bytes_unchecked(evaluation))
+/// Serializes a scalar as 32 big-endian bytes, the inverse of [`safe_scalar_affine_from_bytes`]
+pub fn scalar_to_bytes32(scalar: &Scalar) -> Bytes32 {
+    let mut bytes = scalar.to_bytes();
+    bytes.reverse();
+    Bytes32::from(bytes)
+}
+
+/// Return the Fiat-Shamir challenge required to verify `blob` and `commitment`, hashed after the
+/// domain separator `domain`.
+#[cfg(feature = "prover")]
+fn compute_challenge(
+    blob: &Blob,
+    commitment: &G1Affine,
+    domain: &[u8; DOMAIN_STR_LENGTH],
+) -> Result<Scalar, KzgError> {
+    Ok(compute_challenge_for_bytes(
+        blob.as_slice(),
+        commitment,
+        domain,
+    ))
+}
+
+/// Same as `compute_challenge` for a blob of any number of field elements, which is hashed as
+/// the polynomial degree
+#[cfg(feature = "prover")]
+pub(crate) fn compute_challenge_for_bytes(
+    blob: &[u8],
+    commitment: &G1Affine,
+    domain: &[u8; DOMAIN_STR_LENGTH],
+) -> Scalar {
+    scalar_from_bytes_unchecked(compute_challenge_hash(blob, commitment, domain))
+}
+
+/// Returns the SHA-256 of the challenge transcript, the challenge before its reduction into the
+/// scalar field
+#[cfg(feature = "prover")]
+pub(crate) fn compute_challenge_hash(
+    blob: &[u8],
+    commitment: &G1Affine,
+    domain: &[u8; DOMAIN_STR_LENGTH],
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    // Domain separator
+    hasher.update(domain);
+    // Polynomial degree (16-bytes, big-endian)
+    hasher.update(0_u64.to_be_bytes());
+    hasher.update(((blob.len() / BYTES_PER_FIELD_ELEMENT) as u64).to_be_bytes());
+    hasher.update(blob);
+    hasher.update(commitment.to_compressed());
+    hasher.finalize().into()
 }
 
 pub fn scalar_from_bytes_unchecked(bytes: [u8; 32]) -> Scalar {
@@ -90,127 +125,200 @@ pub fn scalar_from_u64_array_unchecked(array: [u64; 4]) -> Scalar {
     Scalar::from_raw([array[3], array[2], array[1], array[0]])
 }
 
-/// Evaluates a polynomial in evaluation form at a given point
+/// Evaluates the polynomial taking the values `polynomial[i]` at `kzg_settings.roots_of_unity[i]`
+/// at `x`, with the barycentric formula. There must be one value per root of unity.
 pub fn evaluate_polynomial_in_evaluation_form(
-    polynomial: Vec<Scalar>,
+    polynomial: &[Scalar],
     x: Scalar,
     kzg_settings: &KzgSettings,
 ) -> Result<Scalar, KzgError> {
-    if polynomial.len() != NUM_FIELD_ELEMENTS_PER_BLOB {
+    let ys = evaluate_polynomial_in_evaluation_form_multi(
+        polynomial,
+        core::slice::from_ref(&x),
+        kzg_settings,
+    )?;
+    Ok(ys[0])
+}
+
+/// Same as [`evaluate_polynomial_in_evaluation_form`] at every point of `xs`, returning the
+/// evaluations in order. The inversions for all points are batched together and the constant
+/// factors computed once, so this is cheaper than evaluating at each point in turn.
+pub fn evaluate_polynomial_in_evaluation_form_multi(
+    polynomial: &[Scalar],
+    xs: &[Scalar],
+    kzg_settings: &KzgSettings,
+) -> Result<Vec<Scalar>, KzgError> {
+    let roots_of_unity = kzg_settings.roots_of_unity;
+    let n = roots_of_unity.len();
+    if polynomial.len() != n {
         return Err(KzgError::InvalidBytesLength(
             "The polynomial length is incorrect".to_string(),
         ));
     }
-
-    let mut inverses_in = vec![Scalar::default(); NUM_FIELD_ELEMENTS_PER_BLOB];
-    let mut inverses = vec![Scalar::default(); NUM_FIELD_ELEMENTS_PER_BLOB];
-    let roots_of_unity = kzg_settings.roots_of_unity;
-    for i in 0..NUM_FIELD_ELEMENTS_PER_BLOB {
-        if x == roots_of_unity[i] {
-            return Ok(polynomial[i]);
-        }
-        inverses_in[i] = x - roots_of_unity[i];
+    if n == 0 {
+        return Err(KzgError::BadArgs(
+            "The trusted setup has no roots of unity".to_string(),
+        ));
     }
 
-    batch_inversion(
-        &mut inverses,
-        &inverses_in,
-        NonZeroUsize::new(NUM_FIELD_ELEMENTS_PER_BLOB).unwrap(),
-    )?;
-
-    let mut out = Scalar::zero();
+    // x - ω_i for every point and root, which is only zero when a point is one of the roots
+    let inverses_in = xs
+        .iter()
+        .flat_map(|x| roots_of_unity.iter().map(move |root| x - root))
+        .collect::<Vec<_>>();
+    let (inverses, zeros) = batch_inversion_skip_zeros(&inverses_in);
+    let mut zeros = zeros.into_iter().peekable();
 
-    for i in 0..NUM_FIELD_ELEMENTS_PER_BLOB {
-        out += (inverses[i] * roots_of_unity[i]) * polynomial[i];
-    }
+    let domain = kzg_settings.domain()?;
+    let evaluations = xs
+        .iter()
+        .zip(inverses.chunks(n))
+        .enumerate()
+        .map(|(j, (x, inverses))| {
+            // The value at a root of unity is given directly
+            if let Some(index) = zeros.next_if(|index| index / n == j) {
+                return polynomial[index % n];
+            }
 
-    out *= Scalar::from(NUM_FIELD_ELEMENTS_PER_BLOB as u64)
-        .invert()
-        .unwrap();
-    out *= x.pow(&[NUM_FIELD_ELEMENTS_PER_BLOB as u64, 0, 0, 0]) - Scalar::one();
+            let mut out = Scalar::zero();
+            for i in 0..n {
+                out += (inverses[i] * roots_of_unity[i]) * polynomial[i];
+            }
+            out * domain.size_inverse * domain.vanishing_at(*x)
+        })
+        .collect();
+    Ok(evaluations)
+}
 
-    Ok(out)
+/// Computes the commitment to a polynomial in evaluation form as a linear combination of the
+/// Lagrange-form G1 points of the trusted setup, with their precomputed table if there is one
+#[cfg(feature = "prover")]
+pub(crate) fn polynomial_to_commitment(
+    polynomial: &Polynomial,
+    kzg_settings: &KzgSettings,
+) -> Result<G1Affine, KzgError> {
+    polynomial_to_commitment_projective(polynomial, kzg_settings).map(Into::into)
 }
 
-/// Montgomery batch inversion in a finite field
-/// Given a list of elements \( x_1, x_2, \dots, x_n \) from a finite field \( F \), Montgomery batch inversion computes the inverses \( x_1^{-1}, x_2^{-1}, \dots, x_n^{-1} \) as follows:
-///
-/// Let's consider three elements \( a \), \( b \), and \( c \) in a finite field \( F \). The steps are as follows:
-///
-/// 1. **Product Accumulation**:
-///     \[
-///     P = a \times b \times c
-///     \]
-///
-/// 2. **Single Inversion**:
-///     \[
-///     P^{-1} = \text{inverse}(P)
-///     \]
-///
-/// 3. **Backward Substitution**:
-///     - \( a^{-1} = P^{-1} \times (b \times c) \)
-///     - \( b^{-1} = P^{-1} \times (a \times c) \)
-///     - \( c^{-1} = P^{-1} \times (a \times b) \)
-///
-fn batch_inversion(out: &mut [Scalar], a: &[Scalar], len: NonZeroUsize) -> Result<(), KzgError> {
-    if a == out {
-        return Err(KzgError::BadArgs(
-            "Destination is the same as source".to_string(),
+/// Same as [`polynomial_to_commitment`], leaving the commitment in projective form for callers
+/// that normalize several commitments together
+#[cfg(feature = "prover")]
+pub(crate) fn polynomial_to_commitment_projective(
+    polynomial: &Polynomial,
+    kzg_settings: &KzgSettings,
+) -> Result<G1Projective, KzgError> {
+    let polynomial = polynomial.evaluations()?;
+    if polynomial.len() != kzg_settings.g1_points.len() {
+        return Err(KzgError::InvalidBytesLength(
+            "The polynomial length does not match the trusted setup".to_string(),
         ));
     }
 
-    // Compute the product of all the elements:
-    //
-    // \[
-    // P = x_1 \times x_2 \times \dots \times x_n
-    // \]
+    Ok(match kzg_settings.g1_lagrange_table {
+        Some(table) if table.len() == polynomial.len() => table.msm(polynomial),
+        _ => g1_msm_affine(kzg_settings.g1_points, polynomial),
+    })
+}
+
+/// Recomputes the commitment to `blob` and compares it with `commitment_bytes`
+#[cfg(feature = "prover")]
+fn verify_blob_commitment_impl(
+    blob: &Blob,
+    commitment_bytes: &Bytes48,
+    kzg_settings: &KzgSettings,
+) -> Result<bool, KzgError> {
+    // Convert commitment bytes to G1Affine
+    let commitment = kzg_settings
+        .subgroup_check
+        .g1_from_bytes(commitment_bytes)?;
+
+    // Convert blob to polynomial
+    let polynomial = blob.to_polynomial()?;
 
-    let mut accumulator = Scalar::one();
+    // Recompute the commitment and compare
+    Ok(polynomial_to_commitment(&polynomial, kzg_settings)? == commitment)
+}
 
-    for i in 0..len.into() {
-        out[i] = accumulator;
-        accumulator = accumulator.mul(&a[i]);
+#[cfg(feature = "prover")]
+fn verify_blob_commitments_controlled(
+    blobs: &[Blob],
+    commitments_bytes: &[Bytes48],
+    kzg_settings: &KzgSettings,
+    on_progress: &mut dyn FnMut(Progress),
+    cancel: Option<&CancelToken>,
+) -> Result<bool, KzgError> {
+    if blobs.len() != commitments_bytes.len() {
+        return Err(KzgError::InvalidBytesLength(
+            "Invalid commitments length".to_string(),
+        ));
     }
+    kzg_settings.limits.check_blobs(
+        blobs.len(),
+        blobs
+            .len()
+            .saturating_mul(BYTES_PER_BLOB + BYTES_PER_COMMITMENT),
+    )?;
 
-    if accumulator == Scalar::zero() {
-        return Err(KzgError::BadArgs("Zero input".to_string()));
+    if blobs.is_empty() {
+        return Ok(true);
     }
 
-    // Compute the inverse of the product \( P \):
-    //
-    // \[
-    // P^{-1} = \text{inverse}(P)
-    // \]
-    accumulator = accumulator.invert().unwrap();
+    let chunk_size = progress::chunk_size();
+    let total = blobs.len();
+
+    let mut valid = true;
+    let mut completed = 0;
+    for (blobs, commitments_bytes) in blobs
+        .chunks(chunk_size)
+        .zip(commitments_bytes.chunks(chunk_size))
+    {
+        if let Some(cancel) = cancel {
+            cancel.check()?;
+        }
+
+        #[cfg(feature = "parallel")]
+        let results = {
+            use rayon::prelude::*;
+            blobs
+                .par_iter()
+                .zip(commitments_bytes.par_iter())
+                .map(|(blob, commitment)| {
+                    verify_blob_commitment_impl(blob, commitment, kzg_settings)
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        #[cfg(not(feature = "parallel"))]
+        let results = blobs
+            .iter()
+            .zip(commitments_bytes.iter())
+            .map(|(blob, commitment)| verify_blob_commitment_impl(blob, commitment, kzg_settings))
+            .collect::<Result<Vec<_>, _>>()?;
 
-    // Compute the inverse of each element \( x_i^{-1} \) by using the precomputed product and its inverse:
-    //
-    // \[
-    // x_i^{-1} = P^{-1} \times \left(\prod_{j \neq i} x_j \right)
-    // \]
-    for i in (0..len.into()).rev() {
-        out[i] *= accumulator;
-        accumulator *= a[i];
+        valid &= results.into_iter().all(|valid| valid);
+        completed += blobs.len();
+        on_progress(Progress { completed, total });
     }
 
-    Ok(())
+    Ok(valid)
 }
 
-fn verify_kzg_proof_impl(
+#[cfg(feature = "prover")]
+pub(crate) fn verify_kzg_proof_impl(
     commitment: G1Affine,
     z: Scalar,
     y: Scalar,
     proof: G1Affine,
     kzg_settings: &KzgSettings,
 ) -> Result<bool, KzgError> {
-    let x = G2Projective::generator() * z;
+    let x = g2_generator_mul(&z);
     let x_minus_z = kzg_settings.g2_points[1] - x;
 
-    let y = G1Projective::generator() * y;
+    let y = g1_generator_mul(&y);
     let p_minus_y = commitment - y;
 
     // Verify: P - y = Q * (X - z)
-    Ok(pairings_verify(
+    Ok(pairings_verify_cached(
         p_minus_y.into(),
         G2Projective::generator().into(),
         proof,
@@ -218,7 +326,11 @@ fn verify_kzg_proof_impl(
     ))
 }
 
-fn validate_batched_input(commitment: &[G1Affine], proofs: &[G1Affine]) -> Result<(), KzgError> {
+#[cfg(feature = "prover")]
+pub(crate) fn validate_batched_input(
+    commitment: &[G1Affine],
+    proofs: &[G1Affine],
+) -> Result<(), KzgError> {
     // Check if any commitment is invalid (not on curve or identity)
     let invalid_commitment = commitment.iter().any(|commitment| {
         !bool::from(commitment.is_identity()) && !bool::from(commitment.is_on_curve())
@@ -241,103 +353,542 @@ fn validate_batched_input(commitment: &[G1Affine], proofs: &[G1Affine]) -> Resul
     Ok(()) // Return Ok if all commitments and proofs are valid
 }
 
+/// Computes the evaluation challenge and evaluation of each blob. Once `should_stop` returns
+/// `true` no further blobs are evaluated and only the already evaluated prefix is returned.
+#[cfg(feature = "prover")]
 fn compute_challenges_and_evaluate_polynomial(
-    blobs: Vec<Blob>,
+    blobs: &[Blob],
     commitment: &[G1Affine],
     kzg_settings: &KzgSettings,
+    on_progress: &mut dyn FnMut(Progress),
+    should_stop: &dyn Fn() -> bool,
 ) -> Result<(Vec<Scalar>, Vec<Scalar>), KzgError> {
-    // Initialize vectors to store evaluation challenges and polynomial evaluations
-    let mut evaluation_challenges = Vec::with_capacity(blobs.len());
-    let mut ys = Vec::with_capacity(blobs.len());
-
-    // Iterate over each blob to compute its polynomial evaluation
-    for i in 0..blobs.len() {
-        // Convert the blob to its polynomial representation
-        let polynomial = blobs[i].as_polynomial()?;
-        // Compute the Fiat-Shamir challenge for the current blob and its commitment
-        let evaluation_challenge = compute_challenge(&blobs[i], &commitment[i])?;
-        // Evaluate the polynomial at the computed challenge
-        let y =
-            evaluate_polynomial_in_evaluation_form(polynomial, evaluation_challenge, kzg_settings)?;
-
-        // Store the evaluation challenge and the polynomial evaluation
-        evaluation_challenges.push(evaluation_challenge);
-        ys.push(y);
-    }
-
-    // Return the vectors of evaluation challenges and polynomial evaluations
+    // Hashes the blob into its Fiat-Shamir challenge and evaluates it there
+    let evaluate = |(blob, commitment): (&Blob, &G1Affine)| {
+        let polynomial = blob.to_polynomial()?;
+        let evaluation_challenge =
+            compute_challenge(blob, commitment, &kzg_settings.domain_separators.blob_proof)?;
+        let y = polynomial.evaluate(evaluation_challenge, kzg_settings)?;
+        Ok((evaluation_challenge, y))
+    };
+
+    let chunk_size = progress::chunk_size();
+    let total = blobs.len();
+    let mut evaluation_challenges = Vec::with_capacity(total);
+    let mut ys = Vec::with_capacity(total);
+    for (blobs, commitment) in blobs.chunks(chunk_size).zip(commitment.chunks(chunk_size)) {
+        if should_stop() {
+            break;
+        }
+
+        #[cfg(feature = "parallel")]
+        let results = {
+            use rayon::prelude::*;
+            blobs
+                .par_iter()
+                .zip(commitment.par_iter())
+                .map(evaluate)
+                .collect::<Result<Vec<_>, KzgError>>()?
+        };
+
+        #[cfg(not(feature = "parallel"))]
+        let results = blobs
+            .iter()
+            .zip(commitment)
+            .map(evaluate)
+            .collect::<Result<Vec<_>, KzgError>>()?;
+
+        for (evaluation_challenge, y) in results {
+            evaluation_challenges.push(evaluation_challenge);
+            ys.push(y);
+        }
+        on_progress(Progress {
+            completed: ys.len(),
+            total,
+        });
+    }
+
     Ok((evaluation_challenges, ys))
 }
 
-pub fn compute_powers(base: &Scalar, num_powers: usize) -> Vec<Scalar> {
-    let mut powers = vec![Scalar::default(); num_powers];
-    if num_powers == 0 {
-        return powers;
+/// Returns the powers `1, r, .., r^(n-1)` of the random scalar `r` that batch verification uses
+/// to combine `n` openings, as derived by `verify_kzg_proof_batch` in the consensus specs: the hash
+/// of the batch domain separator, the number of field elements per blob and `n` as 8-byte
+/// big-endian integers, and for each opening the commitment, the big-endian `z` and `y` and the
+/// proof, reduced into the field.
+///
+/// Custom batchers that derive their randomness with this function combine openings exactly like
+/// [`KzgProof::verify_kzg_proof_batch`] and the reference implementation.
+#[cfg(feature = "prover")]
+pub fn compute_r_powers(
+    commitments: &[G1Affine],
+    zs: &[Scalar],
+    ys: &[Scalar],
+    proofs: &[G1Affine],
+    kzg_settings: &KzgSettings,
+) -> Result<Vec<Scalar>, KzgError> {
+    let n = commitments.len();
+    if zs.len() != n || ys.len() != n || proofs.len() != n {
+        return Err(KzgError::InvalidBytesLength(
+            "The batch inputs have different lengths".to_string(),
+        ));
     }
-    powers[0] = Scalar::one();
-    for i in 1..num_powers {
-        powers[i] = powers[i - 1].mul(base);
+
+    let mut hasher = Sha256::new();
+    hasher.update(kzg_settings.domain_separators.batch);
+    hasher.update((kzg_settings.field_elements_per_blob() as u64).to_be_bytes());
+    hasher.update((n as u64).to_be_bytes());
+    for i in 0..n {
+        hasher.update(commitments[i].to_compressed());
+        hasher.update(scalar_to_bytes32(&zs[i]).as_slice());
+        hasher.update(scalar_to_bytes32(&ys[i]).as_slice());
+        hasher.update(proofs[i].to_compressed());
     }
-    powers
+    let r = scalar_from_bytes_unchecked(hasher.finalize().into());
+
+    Ok(compute_powers(&r, n))
 }
 
-fn compute_r_powers(
-    commitment: &[G1Affine],
+#[cfg(feature = "prover")]
+pub(crate) fn verify_blob_kzg_proof_batch_controlled(
+    blobs: &[Blob],
+    commitments_bytes: &[Bytes48],
+    proofs_bytes: &[Bytes48],
+    kzg_settings: &KzgSettings,
+    on_progress: &mut dyn FnMut(Progress),
+    cancel: Option<&CancelToken>,
+) -> Result<bool, KzgError> {
+    if blobs.len() != commitments_bytes.len() {
+        return Err(KzgError::InvalidBytesLength(
+            "Invalid commitments length".to_string(),
+        ));
+    }
+
+    if blobs.len() != proofs_bytes.len() {
+        return Err(KzgError::InvalidBytesLength(
+            "Invalid proofs length".to_string(),
+        ));
+    }
+
+    check_blob_limits(blobs.len(), kzg_settings)?;
+
+    if blobs.is_empty() {
+        return Ok(true);
+    }
+
+    if let Some(cancel) = cancel {
+        cancel.check()?;
+    }
+
+    if blobs.len() == 1 {
+        let result = verify_blob_kzg_proof_impl(
+            &blobs[0],
+            &commitments_bytes[0],
+            &proofs_bytes[0],
+            kzg_settings,
+        );
+        on_progress(Progress {
+            completed: 1,
+            total: 1,
+        });
+        return result;
+    }
+
+    #[cfg(feature = "log")]
+    let started = std::time::Instant::now();
+
+    let commitments = decompress_g1_batch(commitments_bytes, kzg_settings.subgroup_check)?;
+    let proofs = decompress_g1_batch(proofs_bytes, kzg_settings.subgroup_check)?;
+    validate_batched_input(&commitments, &proofs)?;
+
+    #[cfg(feature = "log")]
+    let parsed = std::time::Instant::now();
+
+    let should_stop = || cancel.is_some_and(CancelToken::is_cancelled);
+    let (evaluation_challenges, ys) = compute_challenges_and_evaluate_polynomial(
+        blobs,
+        &commitments,
+        kzg_settings,
+        on_progress,
+        &should_stop,
+    )?;
+    if ys.len() < blobs.len() {
+        return Err(KzgError::Cancelled);
+    }
+
+    #[cfg(feature = "log")]
+    let evaluated = std::time::Instant::now();
+
+    let result = verify_kzg_proof_batch_impl(
+        &commitments,
+        &evaluation_challenges,
+        &ys,
+        &proofs,
+        kzg_settings,
+    );
+
+    #[cfg(feature = "log")]
+    log_batch_stats(
+        blobs,
+        commitments_bytes,
+        started,
+        parsed,
+        evaluated,
+        &result,
+    );
+
+    result
+}
+
+/// Logs the composition and phase timings of a blob batch at debug level
+#[cfg(all(feature = "log", feature = "prover"))]
+fn log_batch_stats(
+    blobs: &[Blob],
+    commitments_bytes: &[Bytes48],
+    started: std::time::Instant,
+    parsed: std::time::Instant,
+    evaluated: std::time::Instant,
+    result: &Result<bool, KzgError>,
+) {
+    use std::collections::HashSet;
+
+    if !log::log_enabled!(target: "kzg_rs::batch", log::Level::Debug) {
+        return;
+    }
+
+    let n = blobs.len();
+    let unique_blobs = blobs
+        .iter()
+        .map(Blob::as_slice)
+        .collect::<HashSet<_>>()
+        .len();
+    let unique_commitments = commitments_bytes
+        .iter()
+        .map(Bytes48::as_slice)
+        .collect::<HashSet<_>>()
+        .len();
+    let finished = std::time::Instant::now();
+    log::debug!(
+        target: "kzg_rs::batch",
+        "verified blob batch: size={} unique_blobs={} unique_commitments={} dedup_ratio={:.3} \
+         parse={:?} evaluate={:?} verify={:?} total={:?} result={:?}",
+        n,
+        unique_blobs,
+        unique_commitments,
+        unique_commitments as f64 / n as f64,
+        parsed - started,
+        evaluated - parsed,
+        finished - evaluated,
+        finished - started,
+        result,
+    );
+}
+
+/// Fills `results` with the validity of each opening, checking the whole range with one batched
+/// verification and only bisecting into halves when that check fails
+#[cfg(feature = "prover")]
+fn bisect_batch(
+    commitments: &[G1Affine],
     zs: &[Scalar],
     ys: &[Scalar],
     proofs: &[G1Affine],
-) -> Result<Vec<Scalar>, KzgError> {
-    let n = commitment.len();
-    let input_size =
-        32 + n * (BYTES_PER_COMMITMENT + 2 * BYTES_PER_FIELD_ELEMENT + BYTES_PER_PROOF);
+    kzg_settings: &KzgSettings,
+    results: &mut [bool],
+) -> Result<(), KzgError> {
+    let valid = match commitments.len() {
+        0 => return Ok(()),
+        1 => verify_kzg_proof_impl(commitments[0], zs[0], ys[0], proofs[0], kzg_settings)?,
+        _ => verify_kzg_proof_batch_impl(commitments, zs, ys, proofs, kzg_settings)?,
+    };
 
-    let mut bytes: Vec<u8> = vec![0; input_size];
+    if valid || commitments.len() == 1 {
+        results.fill(valid);
+        return Ok(());
+    }
 
-    // Copy domain separator
-    bytes[..16].copy_from_slice(RANDOM_CHALLENGE_KZG_BATCH_DOMAIN.as_bytes());
+    let mid = commitments.len() / 2;
+    let (left, right) = results.split_at_mut(mid);
+    bisect_batch(
+        &commitments[..mid],
+        &zs[..mid],
+        &ys[..mid],
+        &proofs[..mid],
+        kzg_settings,
+        left,
+    )?;
+    bisect_batch(
+        &commitments[mid..],
+        &zs[mid..],
+        &ys[mid..],
+        &proofs[mid..],
+        kzg_settings,
+        right,
+    )
+}
 
-    bytes[16..24].copy_from_slice(&(NUM_FIELD_ELEMENTS_PER_BLOB as u64).to_be_bytes());
+/// Computes the commitment to `blob`
+#[cfg(feature = "prover")]
+pub(crate) fn blob_to_kzg_commitment_impl(
+    blob: &Blob,
+    kzg_settings: &KzgSettings,
+) -> Result<G1Affine, KzgError> {
+    let polynomial = blob.to_polynomial()?;
+    polynomial_to_commitment(&polynomial, kzg_settings)
+}
 
-    let mut n_bytes = n.to_be_bytes().to_vec();
-    n_bytes.resize(8, 0);
-    bytes[24..32].copy_from_slice(&n_bytes);
+/// Computes the proof that the polynomial in evaluation form evaluates to `y` at `z`, returning
+/// the proof and `y`
+#[cfg(feature = "prover")]
+pub(crate) fn compute_kzg_proof_impl(
+    polynomial: &Polynomial,
+    z: Scalar,
+    kzg_settings: &KzgSettings,
+) -> Result<(G1Affine, Scalar), KzgError> {
+    let (quotient, y) = compute_quotient(polynomial, z, kzg_settings)?;
+    let proof = polynomial_to_commitment(&quotient, kzg_settings)?;
+    Ok((proof, y))
+}
 
-    let mut offset = 32;
+/// Computes the proofs that the polynomial in evaluation form evaluates to `y_i` at each `z_i`,
+/// returning the proofs and evaluations in the order of `zs`
+#[cfg(feature = "prover")]
+pub(crate) fn compute_kzg_proofs_multi_impl(
+    polynomial: &Polynomial,
+    zs: &[Scalar],
+    kzg_settings: &KzgSettings,
+) -> Result<Vec<(G1Affine, Scalar)>, KzgError> {
+    let compute = |z: &Scalar| {
+        let (quotient, y) = compute_quotient(polynomial, *z, kzg_settings)?;
+        Ok((
+            polynomial_to_commitment_projective(&quotient, kzg_settings)?,
+            y,
+        ))
+    };
 
-    for i in 0..n {
-        // Copy commitment
-        let v = commitment[i].to_compressed();
-        bytes[offset..(v.len() + offset)].copy_from_slice(&v[..]);
-        offset += BYTES_PER_COMMITMENT;
+    #[cfg(feature = "parallel")]
+    let openings = {
+        use rayon::prelude::*;
+        zs.par_iter()
+            .map(compute)
+            .collect::<Result<Vec<_>, KzgError>>()?
+    };
+
+    #[cfg(not(feature = "parallel"))]
+    let openings = zs
+        .iter()
+        .map(compute)
+        .collect::<Result<Vec<_>, KzgError>>()?;
+
+    let (proofs, ys): (Vec<_>, Vec<_>) = openings.into_iter().unzip();
+    Ok(normalize_g1_batch(&proofs).into_iter().zip(ys).collect())
+}
+
+/// Computes the quotient `q(X) = (p(X) - y) / (X - z)` in evaluation form, returning it together
+/// with `y = p(z)`. This is the polynomial committed to by the proof of `p` at `z`.
+///
+/// When `z` is one of the roots of unity `ω_m`, the quotient at `ω_m` is undefined by that
+/// formula and is instead given by [`compute_quotient_eval_within_domain`].
+#[cfg(feature = "prover")]
+pub fn compute_quotient(
+    polynomial: &Polynomial,
+    z: Scalar,
+    kzg_settings: &KzgSettings,
+) -> Result<(Polynomial, Scalar), KzgError> {
+    let y = polynomial.evaluate(z, kzg_settings)?;
+    let polynomial = polynomial.evaluations()?;
 
-        // Copy evaluation challenge
-        let v = zs[i].to_bytes();
-        bytes[offset..(v.len() + offset)].copy_from_slice(&v[..]);
-        offset += BYTES_PER_FIELD_ELEMENT;
+    let n = polynomial.len();
+    let roots_of_unity = kzg_settings.roots_of_unity;
 
-        // Copy polynomial's evaluation value
-        let v = ys[i].to_bytes();
-        bytes[offset..(v.len() + offset)].copy_from_slice(&v[..]);
-        offset += BYTES_PER_FIELD_ELEMENT;
+    // (p_i - y) / (ω_i - z), where the only zero denominator is at the root equal to `z`, if any
+    let denominators = roots_of_unity[..n]
+        .iter()
+        .map(|root| root - z)
+        .collect::<Vec<_>>();
+    let (inverses, zeros) = batch_inversion_skip_zeros(&denominators);
+    let mut quotient = polynomial
+        .iter()
+        .zip(&inverses)
+        .map(|(p, inverse)| (p - y) * inverse)
+        .collect::<Vec<_>>();
 
-        // Copy proof
-        let v = proofs[i].to_compressed();
-        bytes[offset..(v.len() + offset)].copy_from_slice(&v[..]);
-        offset += BYTES_PER_PROOF;
+    if let Some(&m) = zeros.first() {
+        quotient[m] = compute_quotient_eval_within_domain(z, polynomial, y, kzg_settings)?;
     }
 
-    // Make sure we wrote the entire buffer
-    if offset != input_size {
+    Ok((Polynomial::Evaluations(quotient), y))
+}
+
+/// Computes the value at `z` of the quotient `(p(X) - y) / (X - z)` when `z` is one of the roots
+/// of unity of `kzg_settings`, as `Σ_{ω_i ≠ z} ω_i (p_i - y) / (z (z - ω_i))` where `p_i` are
+/// the values of `polynomial`, one per root of unity. This is the spec's function of the same
+/// name.
+#[cfg(feature = "prover")]
+pub fn compute_quotient_eval_within_domain(
+    z: Scalar,
+    polynomial: &[Scalar],
+    y: Scalar,
+    kzg_settings: &KzgSettings,
+) -> Result<Scalar, KzgError> {
+    let roots_of_unity = kzg_settings.roots_of_unity;
+    let n = roots_of_unity.len();
+    if polynomial.len() != n {
         return Err(KzgError::InvalidBytesLength(
-            "Error while copying commitments".to_string(),
+            "The polynomial length is incorrect".to_string(),
+        ));
+    }
+    if z == Scalar::zero() {
+        return Err(KzgError::BadArgs(
+            "The evaluation point is not a root of unity".to_string(),
         ));
     }
 
-    // Now let's create the challenge!
-    let evaluation: [u8; 32] = Sha256::digest(bytes).into();
-    let r = scalar_from_bytes_unchecked(evaluation);
+    // z * (z - ω_i), which is only zero at the root equal to `z` and skipped there
+    let denominators = roots_of_unity
+        .iter()
+        .map(|root| (z - root) * z)
+        .collect::<Vec<_>>();
+    let (inverses, _) = batch_inversion_skip_zeros(&denominators);
 
-    Ok(compute_powers(&r, n))
+    let mut result = Scalar::zero();
+    for i in 0..n {
+        // ω_i * (p_i - y) / (z * (z - ω_i))
+        result += (polynomial[i] - y) * roots_of_unity[i] * inverses[i];
+    }
+    Ok(result)
+}
+
+/// Computes the proof for `blob` at the Fiat-Shamir challenge derived from `blob` and
+/// `commitment`
+#[cfg(feature = "prover")]
+pub(crate) fn compute_blob_kzg_proof_impl(
+    blob: &Blob,
+    commitment: &G1Affine,
+    kzg_settings: &KzgSettings,
+) -> Result<G1Affine, KzgError> {
+    let polynomial = blob.to_polynomial()?;
+    let evaluation_challenge =
+        compute_challenge(blob, commitment, &kzg_settings.domain_separators.blob_proof)?;
+    let (proof, _) = compute_kzg_proof_impl(&polynomial, evaluation_challenge, kzg_settings)?;
+    Ok(proof)
+}
+
+#[cfg(feature = "prover")]
+pub(crate) fn verify_blob_kzg_proof_impl(
+    blob: &Blob,
+    commitment_bytes: &Bytes48,
+    proof_bytes: &Bytes48,
+    kzg_settings: &KzgSettings,
+) -> Result<bool, KzgError> {
+    // Convert commitment bytes to G1Affine
+    let commitment = kzg_settings
+        .subgroup_check
+        .g1_from_bytes(commitment_bytes)?;
+
+    // Convert blob to polynomial
+    let polynomial = blob.to_polynomial()?;
+
+    // Convert proof bytes to G1Affine
+    let proof = kzg_settings.subgroup_check.g1_from_bytes(proof_bytes)?;
+
+    // Compute the evaluation challenge for the blob and commitment
+    let evaluation_challenge = compute_challenge(
+        blob,
+        &commitment,
+        &kzg_settings.domain_separators.blob_proof,
+    )?;
+
+    // Evaluate the polynomial in evaluation form
+    let y = polynomial.evaluate(evaluation_challenge, kzg_settings)?;
+
+    // Verify the KZG proof
+    verify_kzg_proof_impl(commitment, evaluation_challenge, y, proof, kzg_settings)
+}
+
+/// Verifies several openings at once with a random linear combination of the pairing equations
+#[cfg(feature = "prover")]
+pub(crate) fn verify_kzg_proof_batch_impl(
+    commitments: &[G1Affine],
+    zs: &[Scalar],
+    ys: &[Scalar],
+    proofs: &[G1Affine],
+    kzg_settings: &KzgSettings,
+) -> Result<bool, KzgError> {
+    let n = commitments.len();
+    if zs.len() != n || ys.len() != n || proofs.len() != n {
+        return Err(KzgError::InvalidBytesLength(
+            "The batch inputs have different lengths".to_string(),
+        ));
+    }
+
+    // Initialize vectors to store intermediate values
+    let mut c_minus_y: Vec<G1Projective> = Vec::with_capacity(n);
+    let mut r_times_z: Vec<Scalar> = Vec::with_capacity(n);
+
+    // Compute r powers
+    let r_powers = compute_r_powers(commitments, zs, ys, proofs, kzg_settings)?;
+
+    // Compute proof linear combination
+    let proof_lincomb = g1_msm_affine(proofs, &r_powers);
+
+    // Compute c_minus_y and r_times_z
+    for i in 0..n {
+        let ys_encrypted = g1_generator_mul(&ys[i]);
+        c_minus_y.push(commitments[i] - ys_encrypted);
+        r_times_z.push(r_powers[i] * zs[i]);
+    }
+
+    // Compute proof_z_lincomb and c_minus_y_lincomb, adding c_minus_y to the buckets in affine
+    // form after normalizing it with a single inversion
+    let proof_z_lincomb = g1_msm_affine(proofs, &r_times_z);
+    let c_minus_y_lincomb = g1_msm_affine(&normalize_g1_batch(&c_minus_y), &r_powers);
+
+    // Compute rhs_g1
+    let rhs_g1 = c_minus_y_lincomb + proof_z_lincomb;
+
+    // Verify the pairing equation
+    let lincombs = normalize_g1_batch(&[proof_lincomb, rhs_g1]);
+    let result = pairings_verify_cached(
+        lincombs[0],
+        kzg_settings.g2_points[1],
+        lincombs[1],
+        G2Affine::generator(),
+    );
+
+    Ok(result)
+}
+
+/// Rejects a batch of `count` blob openings larger than the limits of `kzg_settings`
+#[cfg(feature = "prover")]
+fn check_blob_limits(count: usize, kzg_settings: &KzgSettings) -> Result<(), KzgError> {
+    let total_bytes = count.saturating_mul(BYTES_PER_BLOB + BYTES_PER_COMMITMENT + BYTES_PER_PROOF);
+    kzg_settings.limits.check_blobs(count, total_bytes)
+}
+
+/// Parses and validates a single blob opening and evaluates the blob at its challenge, returning
+/// the commitment, evaluation point, evaluation and proof
+#[cfg(feature = "prover")]
+pub(crate) fn prepare_blob_opening(
+    blob: &Blob,
+    commitment_bytes: &Bytes48,
+    proof_bytes: &Bytes48,
+    kzg_settings: &KzgSettings,
+) -> Result<(G1Affine, Scalar, Scalar, G1Affine), KzgError> {
+    let commitment = kzg_settings
+        .subgroup_check
+        .g1_from_bytes(commitment_bytes)?;
+    let proof = kzg_settings.subgroup_check.g1_from_bytes(proof_bytes)?;
+    validate_batched_input(&[commitment], &[proof])?;
+
+    let polynomial = blob.to_polynomial()?;
+    let evaluation_challenge = compute_challenge(
+        blob,
+        &commitment,
+        &kzg_settings.domain_separators.blob_proof,
+    )?;
+    let y = polynomial.evaluate(evaluation_challenge, kzg_settings)?;
+
+    Ok((commitment, evaluation_challenge, y, proof))
 }
 
 pub struct KzgProof {}
@@ -362,26 +913,26 @@ impl KzgProof {
                 return Err(e);
             }
         };
-        let commitment = match safe_g1_affine_from_bytes(commitment_bytes) {
+        let commitment = match kzg_settings.subgroup_check.g1_from_bytes(commitment_bytes) {
             Ok(g1) => g1,
             Err(e) => {
                 return Err(e);
             }
         };
-        let proof = match safe_g1_affine_from_bytes(proof_bytes) {
+        let proof = match kzg_settings.subgroup_check.g1_from_bytes(proof_bytes) {
             Ok(g1) => g1,
             Err(e) => {
                 return Err(e);
             }
         };
 
-        let g2_x = G2Affine::generator() * z;
+        let g2_x = g2_generator_mul(&z);
         let x_minus_z = kzg_settings.g2_points[1] - g2_x;
 
-        let g1_y = G1Affine::generator() * y;
+        let g1_y = g1_generator_mul(&y);
         let p_minus_y = commitment - g1_y;
 
-        Ok(pairings_verify(
+        Ok(pairings_verify_cached(
             p_minus_y.into(),
             G2Affine::generator(),
             proof,
@@ -389,98 +940,476 @@ impl KzgProof {
         ))
     }
 
+    /// Same as [`KzgProof::verify_kzg_proof`] over plain byte arrays, for callers such as FFI
+    /// shims and zkVM guests that do not use the wrapper types
+    pub fn verify_kzg_proof_raw(
+        commitment_bytes: &[u8; BYTES_PER_COMMITMENT],
+        z_bytes: &[u8; BYTES_PER_FIELD_ELEMENT],
+        y_bytes: &[u8; BYTES_PER_FIELD_ELEMENT],
+        proof_bytes: &[u8; BYTES_PER_PROOF],
+        kzg_settings: &KzgSettings,
+    ) -> Result<bool, KzgError> {
+        Self::verify_kzg_proof(
+            commitment_bytes.into(),
+            z_bytes.into(),
+            y_bytes.into(),
+            proof_bytes.into(),
+            kzg_settings,
+        )
+    }
+}
+
+#[cfg(feature = "prover")]
+impl KzgProof {
+    /// Verifies several openings at once with a random linear combination of the pairing
+    /// equations, so the cost is two pairings and three MSMs regardless of the batch size
     pub fn verify_kzg_proof_batch(
         commitments: &[G1Affine],
-        zs: &[Scalar],
-        ys: &[Scalar],
+        zs: &[FieldElement],
+        ys: &[FieldElement],
         proofs: &[G1Affine],
         kzg_settings: &KzgSettings,
     ) -> Result<bool, KzgError> {
-        let n = commitments.len();
+        verify_kzg_proof_batch_impl(
+            commitments,
+            FieldElement::as_scalars(zs),
+            FieldElement::as_scalars(ys),
+            proofs,
+            kzg_settings,
+        )
+    }
 
-        // Initialize vectors to store intermediate values
-        let mut c_minus_y: Vec<G1Projective> = Vec::with_capacity(n);
-        let mut r_times_z: Vec<Scalar> = Vec::with_capacity(n);
+    /// Same as [`KzgProof::verify_kzg_proof_batch`] over serialized inputs, which are parsed and
+    /// validated like in [`KzgProof::verify_kzg_proof`]
+    pub fn verify_kzg_proof_batch_bytes(
+        commitments_bytes: &[Bytes48],
+        zs_bytes: &[Bytes32],
+        ys_bytes: &[Bytes32],
+        proofs_bytes: &[Bytes48],
+        kzg_settings: &KzgSettings,
+    ) -> Result<bool, KzgError> {
+        let n = commitments_bytes.len();
+        if zs_bytes.len() != n || ys_bytes.len() != n || proofs_bytes.len() != n {
+            return Err(KzgError::InvalidBytesLength(
+                "The batch inputs have different lengths".to_string(),
+            ));
+        }
 
-        // Compute r powers
-        let r_powers = compute_r_powers(commitments, zs, ys, proofs)?;
+        let commitments = decompress_g1_batch(commitments_bytes, kzg_settings.subgroup_check)?;
+        let proofs = decompress_g1_batch(proofs_bytes, kzg_settings.subgroup_check)?;
+        let zs = zs_bytes
+            .iter()
+            .map(safe_scalar_affine_from_bytes)
+            .collect::<Result<Vec<_>, _>>()?;
+        let ys = ys_bytes
+            .iter()
+            .map(safe_scalar_affine_from_bytes)
+            .collect::<Result<Vec<_>, _>>()?;
 
-        // Convert proofs to G1Projective
-        let proofs = proofs.iter().map(Into::into).collect::<Vec<_>>();
+        verify_kzg_proof_batch_impl(&commitments, &zs, &ys, &proofs, kzg_settings)
+    }
 
-        // Compute proof linear combination
-        let proof_lincomb = G1Projective::msm_variable_base(&proofs, &r_powers);
+    pub fn verify_blob_kzg_proof(
+        blob: Blob,
+        commitment_bytes: &Bytes48,
+        proof_bytes: &Bytes48,
+        kzg_settings: &KzgSettings,
+    ) -> Result<bool, KzgError> {
+        verify_blob_kzg_proof_impl(&blob, commitment_bytes, proof_bytes, kzg_settings)
+    }
 
-        // Compute c_minus_y and r_times_z
-        for i in 0..n {
-            let ys_encrypted = G1Affine::generator() * ys[i];
-            c_minus_y.push(commitments[i] - ys_encrypted);
-            r_times_z.push(r_powers[i] * zs[i]);
+    /// Verifies the blob proof and that the commitment hashes to `versioned_hash`, as execution
+    /// clients do for blob transactions. A mismatched hash is an error, checked before the proof.
+    pub fn verify_blob_kzg_proof_with_versioned_hash(
+        blob: &Blob,
+        commitment_bytes: &Bytes48,
+        proof_bytes: &Bytes48,
+        versioned_hash: &Bytes32,
+        kzg_settings: &KzgSettings,
+    ) -> Result<bool, KzgError> {
+        if kzg_to_versioned_hash(commitment_bytes).as_slice() != versioned_hash.as_slice() {
+            return Err(KzgError::BadArgs(
+                "Versioned hash does not match the commitment".to_string(),
+            ));
         }
+        verify_blob_kzg_proof_impl(blob, commitment_bytes, proof_bytes, kzg_settings)
+    }
 
-        // Compute proof_z_lincomb and c_minus_y_lincomb
-        let proof_z_lincomb = G1Projective::msm_variable_base(&proofs, &r_times_z);
-        let c_minus_y_lincomb = G1Projective::msm_variable_base(&c_minus_y, &r_powers);
+    /// Same as [`KzgProof::verify_blob_kzg_proof`] over plain byte arrays, for callers such as
+    /// FFI shims and zkVM guests that do not use the wrapper types. The blob is not copied.
+    pub fn verify_blob_kzg_proof_raw(
+        blob: &[u8; BYTES_PER_BLOB],
+        commitment_bytes: &[u8; BYTES_PER_COMMITMENT],
+        proof_bytes: &[u8; BYTES_PER_PROOF],
+        kzg_settings: &KzgSettings,
+    ) -> Result<bool, KzgError> {
+        verify_blob_kzg_proof_impl(
+            blob.into(),
+            commitment_bytes.into(),
+            proof_bytes.into(),
+            kzg_settings,
+        )
+    }
 
-        // Compute rhs_g1
-        let rhs_g1 = c_minus_y_lincomb + proof_z_lincomb;
+    /// Same as [`KzgProof::verify_blob_kzg_proof_batch`] over plain byte arrays. The blobs are
+    /// not copied.
+    pub fn verify_blob_kzg_proof_batch_raw(
+        blobs: &[[u8; BYTES_PER_BLOB]],
+        commitments_bytes: &[[u8; BYTES_PER_COMMITMENT]],
+        proofs_bytes: &[[u8; BYTES_PER_PROOF]],
+        kzg_settings: &KzgSettings,
+    ) -> Result<bool, KzgError> {
+        verify_blob_kzg_proof_batch_controlled(
+            Blob::from_arrays(blobs),
+            Bytes48::from_arrays(commitments_bytes),
+            Bytes48::from_arrays(proofs_bytes),
+            kzg_settings,
+            &mut |_| {},
+            None,
+        )
+    }
 
-        // Verify the pairing equation
-        let result = pairings_verify(
-            proof_lincomb.into(),
-            kzg_settings.g2_points[1],
-            rhs_g1.into(),
-            G2Affine::generator(),
-        );
+    /// Computes the commitment to `blob` from the Lagrange-form G1 points of the trusted setup
+    pub fn blob_to_kzg_commitment(
+        blob: &Blob,
+        kzg_settings: &KzgSettings,
+    ) -> Result<Bytes48, KzgError> {
+        let commitment = blob_to_kzg_commitment_impl(blob, kzg_settings)?;
+        Ok(Bytes48::from(commitment.to_compressed()))
+    }
 
-        Ok(result)
+    /// Updates the commitment to a blob after some of its field elements changed, given the
+    /// difference between the new and the old value of each changed element as returned by
+    /// [`Blob::patch`].
+    ///
+    /// Costs one small MSM over the changed elements instead of a full recommitment.
+    pub fn update_commitment(
+        commitment_bytes: &Bytes48,
+        deltas: &[(usize, FieldElement)],
+        kzg_settings: &KzgSettings,
+    ) -> Result<Bytes48, KzgError> {
+        let commitment = kzg_settings
+            .subgroup_check
+            .g1_from_bytes(commitment_bytes)?;
+
+        let mut points = Vec::with_capacity(deltas.len());
+        let mut scalars = Vec::with_capacity(deltas.len());
+        for &(index, delta) in deltas {
+            let point = kzg_settings.g1_points.get(index).ok_or_else(|| {
+                KzgError::BadArgs(format!("Field element index {} is out of range", index))
+            })?;
+            points.push(G1Projective::from(point));
+            scalars.push(Scalar::from(delta));
+        }
+
+        let updated = commitment + g1_msm(&points, &scalars);
+        Ok(Bytes48::from(G1Affine::from(updated).to_compressed()))
     }
 
-    pub fn verify_blob_kzg_proof(
-        blob: Blob,
+    /// Computes the proof that `blob` evaluates to `y` at `z_bytes`.
+    ///
+    /// Together with [`KzgProof::blob_to_kzg_commitment`] this produces every input of the
+    /// point evaluation precompile.
+    pub fn compute_kzg_proof(
+        blob: &Blob,
+        z_bytes: &Bytes32,
+        kzg_settings: &KzgSettings,
+    ) -> Result<ProofWithEvaluation, KzgError> {
+        let polynomial = blob.to_polynomial()?;
+        let z = safe_scalar_affine_from_bytes(z_bytes)?;
+        let (proof, y) = compute_kzg_proof_impl(&polynomial, z, kzg_settings)?;
+        Ok(ProofWithEvaluation {
+            proof: Bytes48::from(proof.to_compressed()),
+            y: scalar_to_bytes32(&y),
+        })
+    }
+
+    /// Computes the proof for `blob` at the Fiat-Shamir challenge derived from `blob` and
+    /// `commitment_bytes`, as checked by [`KzgProof::verify_blob_kzg_proof`]
+    pub fn compute_blob_kzg_proof(
+        blob: &Blob,
         commitment_bytes: &Bytes48,
-        proof_bytes: &Bytes48,
         kzg_settings: &KzgSettings,
-    ) -> Result<bool, KzgError> {
-        // Convert commitment bytes to G1Affine
-        let commitment = safe_g1_affine_from_bytes(commitment_bytes)?;
+    ) -> Result<Bytes48, KzgError> {
+        let commitment = kzg_settings
+            .subgroup_check
+            .g1_from_bytes(commitment_bytes)?;
+        let proof = compute_blob_kzg_proof_impl(blob, &commitment, kzg_settings)?;
+        Ok(Bytes48::from(proof.to_compressed()))
+    }
+
+    /// Computes the proofs that `blob` evaluates to `y_i` at each of `zs_bytes`, in order.
+    ///
+    /// The blob is decoded once for all points, which makes this cheaper than repeated
+    /// single-point calls when serving many openings of the same blob.
+    /// With the `parallel` feature enabled the proofs are computed on the rayon thread pool.
+    pub fn compute_kzg_proofs_multi(
+        blob: &Blob,
+        zs_bytes: &[Bytes32],
+        kzg_settings: &KzgSettings,
+    ) -> Result<Vec<ProofWithEvaluation>, KzgError> {
+        let polynomial = blob.to_polynomial()?;
+        let zs = zs_bytes
+            .iter()
+            .map(safe_scalar_affine_from_bytes)
+            .collect::<Result<Vec<_>, _>>()?;
 
-        // Convert blob to polynomial
-        let polynomial = blob.as_polynomial()?;
+        let openings = compute_kzg_proofs_multi_impl(&polynomial, &zs, kzg_settings)?;
+        Ok(openings
+            .into_iter()
+            .map(|(proof, y)| ProofWithEvaluation {
+                proof: Bytes48::from(proof.to_compressed()),
+                y: scalar_to_bytes32(&y),
+            })
+            .collect())
+    }
 
-        // Convert proof bytes to G1Affine
-        let proof = safe_g1_affine_from_bytes(proof_bytes)?;
+    /// Recomputes the commitment to `blob` and checks that it matches `commitment_bytes`.
+    ///
+    /// This is useful when a blob and its claimed commitment are available but no proof is.
+    pub fn verify_blob_commitment(
+        blob: &Blob,
+        commitment_bytes: &Bytes48,
+        kzg_settings: &KzgSettings,
+    ) -> Result<bool, KzgError> {
+        verify_blob_commitment_impl(blob, commitment_bytes, kzg_settings)
+    }
 
-        // Compute the evaluation challenge for the blob and commitment
-        let evaluation_challenge = compute_challenge(&blob, &commitment)?;
+    /// Recomputes the commitments to all `blobs` and checks that they match `commitments_bytes`.
+    ///
+    /// With the `parallel` feature enabled the blobs are processed on the rayon thread pool.
+    pub fn verify_blob_commitments(
+        blobs: &[Blob],
+        commitments_bytes: &[Bytes48],
+        kzg_settings: &KzgSettings,
+    ) -> Result<bool, KzgError> {
+        Self::verify_blob_commitments_with_progress(blobs, commitments_bytes, kzg_settings, |_| {})
+    }
 
-        // Evaluate the polynomial in evaluation form
-        let y =
-            evaluate_polynomial_in_evaluation_form(polynomial, evaluation_challenge, kzg_settings)?;
+    /// Same as [`KzgProof::verify_blob_commitments`], calling `on_progress` after each chunk of
+    /// blobs has been processed.
+    pub fn verify_blob_commitments_with_progress(
+        blobs: &[Blob],
+        commitments_bytes: &[Bytes48],
+        kzg_settings: &KzgSettings,
+        mut on_progress: impl FnMut(Progress),
+    ) -> Result<bool, KzgError> {
+        verify_blob_commitments_controlled(
+            blobs,
+            commitments_bytes,
+            kzg_settings,
+            &mut on_progress,
+            None,
+        )
+    }
 
-        // Verify the KZG proof
-        verify_kzg_proof_impl(commitment, evaluation_challenge, y, proof, kzg_settings)
+    /// Same as [`KzgProof::verify_blob_commitments`], returning [`KzgError::Cancelled`] once
+    /// `cancel` is triggered. The token is checked between chunks of blobs.
+    pub fn verify_blob_commitments_with_cancel(
+        blobs: &[Blob],
+        commitments_bytes: &[Bytes48],
+        kzg_settings: &KzgSettings,
+        cancel: &CancelToken,
+    ) -> Result<bool, KzgError> {
+        verify_blob_commitments_controlled(
+            blobs,
+            commitments_bytes,
+            kzg_settings,
+            &mut |_| {},
+            Some(cancel),
+        )
     }
 
+    /// Verifies the proofs of several blobs with a single pairing check. With the `parallel`
+    /// feature the blobs are hashed and evaluated at their challenges on the rayon thread pool.
     pub fn verify_blob_kzg_proof_batch(
         blobs: Vec<Blob>,
         commitments_bytes: Vec<Bytes48>,
         proofs_bytes: Vec<Bytes48>,
         kzg_settings: &KzgSettings,
     ) -> Result<bool, KzgError> {
-        if blobs.is_empty() {
-            return Ok(true);
+        Self::verify_blob_kzg_proof_batch_with_progress(
+            blobs,
+            commitments_bytes,
+            proofs_bytes,
+            kzg_settings,
+            |_| {},
+        )
+    }
+
+    /// Same as [`KzgProof::verify_blob_kzg_proof_batch`], calling `on_progress` after each chunk
+    /// of blobs has been evaluated at its challenges. With the `parallel` feature a chunk holds a
+    /// blob per thread of the rayon pool.
+    pub fn verify_blob_kzg_proof_batch_with_progress(
+        blobs: Vec<Blob>,
+        commitments_bytes: Vec<Bytes48>,
+        proofs_bytes: Vec<Bytes48>,
+        kzg_settings: &KzgSettings,
+        mut on_progress: impl FnMut(Progress),
+    ) -> Result<bool, KzgError> {
+        verify_blob_kzg_proof_batch_controlled(
+            &blobs,
+            &commitments_bytes,
+            &proofs_bytes,
+            kzg_settings,
+            &mut on_progress,
+            None,
+        )
+    }
+
+    /// Same as [`KzgProof::verify_blob_kzg_proof_batch`], returning [`KzgError::Cancelled`] once
+    /// `cancel` is triggered. The token is checked before each blob is evaluated.
+    pub fn verify_blob_kzg_proof_batch_with_cancel(
+        blobs: Vec<Blob>,
+        commitments_bytes: Vec<Bytes48>,
+        proofs_bytes: Vec<Bytes48>,
+        kzg_settings: &KzgSettings,
+        cancel: &CancelToken,
+    ) -> Result<bool, KzgError> {
+        verify_blob_kzg_proof_batch_controlled(
+            &blobs,
+            &commitments_bytes,
+            &proofs_bytes,
+            kzg_settings,
+            &mut |_| {},
+            Some(cancel),
+        )
+    }
+
+    /// Verifies every blob of the batch and reports the validity of each one individually.
+    ///
+    /// The whole batch is checked first with a single aggregated verification; only when that
+    /// fails is it bisected to locate the invalid proofs, so the common all-valid case costs the
+    /// same as [`KzgProof::verify_blob_kzg_proof_batch`].
+    pub fn verify_blob_kzg_proof_batch_individual(
+        blobs: &[Blob],
+        commitments_bytes: &[Bytes48],
+        proofs_bytes: &[Bytes48],
+        kzg_settings: &KzgSettings,
+    ) -> Result<Vec<bool>, KzgError> {
+        if blobs.len() != commitments_bytes.len() {
+            return Err(KzgError::InvalidBytesLength(
+                "Invalid commitments length".to_string(),
+            ));
+        }
+
+        if blobs.len() != proofs_bytes.len() {
+            return Err(KzgError::InvalidBytesLength(
+                "Invalid proofs length".to_string(),
+            ));
+        }
+
+        check_blob_limits(blobs.len(), kzg_settings)?;
+
+        let commitments = decompress_g1_batch(commitments_bytes, kzg_settings.subgroup_check)?;
+        let proofs = decompress_g1_batch(proofs_bytes, kzg_settings.subgroup_check)?;
+
+        validate_batched_input(&commitments, &proofs)?;
+
+        let (evaluation_challenges, ys) = compute_challenges_and_evaluate_polynomial(
+            blobs,
+            &commitments,
+            kzg_settings,
+            &mut |_| {},
+            &|| false,
+        )?;
+
+        let mut results = vec![false; blobs.len()];
+        bisect_batch(
+            &commitments,
+            &evaluation_challenges,
+            &ys,
+            &proofs,
+            kzg_settings,
+            &mut results,
+        )?;
+
+        Ok(results)
+    }
+
+    /// Verifies every blob of the batch, reporting a separate result for each one.
+    ///
+    /// Unlike [`KzgProof::verify_blob_kzg_proof_batch_individual`], a blob, commitment or proof
+    /// that fails to parse only produces an error in its own slot; the remaining well-formed
+    /// openings are still verified together. Only mismatched input lengths fail the whole call.
+    pub fn verify_blob_kzg_proof_batch_detailed(
+        blobs: &[Blob],
+        commitments_bytes: &[Bytes48],
+        proofs_bytes: &[Bytes48],
+        kzg_settings: &KzgSettings,
+    ) -> Result<Vec<Result<bool, KzgError>>, KzgError> {
+        if blobs.len() != commitments_bytes.len() {
+            return Err(KzgError::InvalidBytesLength(
+                "Invalid commitments length".to_string(),
+            ));
+        }
+
+        if blobs.len() != proofs_bytes.len() {
+            return Err(KzgError::InvalidBytesLength(
+                "Invalid proofs length".to_string(),
+            ));
+        }
+
+        check_blob_limits(blobs.len(), kzg_settings)?;
+
+        let mut results = Vec::with_capacity(blobs.len());
+        let mut indices = Vec::new();
+        let mut commitments = Vec::new();
+        let mut evaluation_challenges = Vec::new();
+        let mut ys = Vec::new();
+        let mut proofs = Vec::new();
+
+        for (i, ((blob, commitment), proof)) in blobs
+            .iter()
+            .zip(commitments_bytes.iter())
+            .zip(proofs_bytes.iter())
+            .enumerate()
+        {
+            match prepare_blob_opening(blob, commitment, proof, kzg_settings) {
+                Ok((commitment, z, y, proof)) => {
+                    indices.push(i);
+                    commitments.push(commitment);
+                    evaluation_challenges.push(z);
+                    ys.push(y);
+                    proofs.push(proof);
+                    results.push(Ok(false));
+                }
+                Err(e) => results.push(Err(e)),
+            }
         }
 
-        if blobs.len() == 1 {
-            return Self::verify_blob_kzg_proof(
-                blobs[0].clone(),
-                &commitments_bytes[0],
-                &proofs_bytes[0],
-                kzg_settings,
-            );
+        let mut valid = vec![false; indices.len()];
+        bisect_batch(
+            &commitments,
+            &evaluation_challenges,
+            &ys,
+            &proofs,
+            kzg_settings,
+            &mut valid,
+        )?;
+
+        for (i, valid) in indices.into_iter().zip(valid) {
+            results[i] = Ok(valid);
         }
 
+        Ok(results)
+    }
+
+    /// Verifies as many leading blobs of the batch as possible before `deadline`.
+    ///
+    /// Blobs are evaluated one at a time, or a chunk per thread pool round with the `parallel`
+    /// feature, until the deadline passes; the evaluated prefix is then
+    /// checked with a single batched pairing, so the call may overrun the deadline by the cost of
+    /// that final check. The returned [`PartialBatchResult`] reports how many blobs were verified
+    /// and how many were left for the caller to retry or drop.
+    #[cfg(feature = "std")]
+    pub fn verify_blob_kzg_proof_batch_with_deadline(
+        blobs: &[Blob],
+        commitments_bytes: &[Bytes48],
+        proofs_bytes: &[Bytes48],
+        kzg_settings: &KzgSettings,
+        deadline: std::time::Instant,
+    ) -> Result<progress::PartialBatchResult, KzgError> {
         if blobs.len() != commitments_bytes.len() {
             return Err(KzgError::InvalidBytesLength(
                 "Invalid commitments length".to_string(),
@@ -493,34 +1422,53 @@ impl KzgProof {
             ));
         }
 
-        let commitments = commitments_bytes
-            .iter()
-            .map(safe_g1_affine_from_bytes)
-            .collect::<Result<Vec<_>, _>>()?;
+        check_blob_limits(blobs.len(), kzg_settings)?;
 
-        let proofs = proofs_bytes
-            .iter()
-            .map(safe_g1_affine_from_bytes)
-            .collect::<Result<Vec<_>, _>>()?;
+        let commitments = decompress_g1_batch(commitments_bytes, kzg_settings.subgroup_check)?;
+        let proofs = decompress_g1_batch(proofs_bytes, kzg_settings.subgroup_check)?;
 
         validate_batched_input(&commitments, &proofs)?;
 
-        let (evaluation_challenges, ys) =
-            compute_challenges_and_evaluate_polynomial(blobs, &commitments, kzg_settings)?;
-
-        Self::verify_kzg_proof_batch(
+        let should_stop = || std::time::Instant::now() >= deadline;
+        let (evaluation_challenges, ys) = compute_challenges_and_evaluate_polynomial(
+            blobs,
             &commitments,
-            &evaluation_challenges,
-            &ys,
-            &proofs,
             kzg_settings,
-        )
+            &mut |_| {},
+            &should_stop,
+        )?;
+
+        let verified = ys.len();
+        let valid = match verified {
+            0 => true,
+            1 => verify_kzg_proof_impl(
+                commitments[0],
+                evaluation_challenges[0],
+                ys[0],
+                proofs[0],
+                kzg_settings,
+            )?,
+            _ => verify_kzg_proof_batch_impl(
+                &commitments[..verified],
+                &evaluation_challenges,
+                &ys,
+                &proofs[..verified],
+                kzg_settings,
+            )?,
+        };
+
+        Ok(progress::PartialBatchResult {
+            verified,
+            remaining: blobs.len() - verified,
+            valid,
+        })
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "prover"))]
 pub mod tests {
     use super::*;
+    use crate::domains::DomainSeparators;
     use crate::test_files::{
         VERIFY_BLOB_KZG_PROOF_BATCH_TESTS, VERIFY_BLOB_KZG_PROOF_TESTS, VERIFY_KZG_PROOF_TESTS,
     };
@@ -672,6 +1620,585 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_verify_blob_kzg_proof_with_versioned_hash() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+
+        for (_test_file, data) in VERIFY_BLOB_KZG_PROOF_TESTS {
+            let test: Test<BlobInput> = serde_yaml::from_str(data).unwrap();
+            let (Ok(blob), Ok(commitment), Ok(proof), Some(output)) = (
+                test.input.get_blob(),
+                test.input.get_commitment(),
+                test.input.get_proof(),
+                test.get_output(),
+            ) else {
+                continue;
+            };
+
+            let versioned_hash = kzg_to_versioned_hash(&commitment);
+            assert_eq!(
+                KzgProof::verify_blob_kzg_proof_with_versioned_hash(
+                    &blob,
+                    &commitment,
+                    &proof,
+                    &versioned_hash,
+                    &kzg_settings
+                )
+                .unwrap(),
+                output
+            );
+
+            let mut wrong_hash: [u8; 32] = versioned_hash.into();
+            wrong_hash[31] ^= 1;
+            assert!(KzgProof::verify_blob_kzg_proof_with_versioned_hash(
+                &blob,
+                &commitment,
+                &proof,
+                &wrong_hash.into(),
+                &kzg_settings
+            )
+            .is_err());
+        }
+    }
+
+    #[test]
+    pub fn test_verify_blob_commitment() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let test_files = VERIFY_BLOB_KZG_PROOF_TESTS;
+
+        for (_test_file, data) in test_files {
+            let test: Test<BlobInput> = serde_yaml::from_str(data).unwrap();
+            let (Ok(blob), Ok(commitment), Some(true)) = (
+                test.input.get_blob(),
+                test.input.get_commitment(),
+                test.get_output(),
+            ) else {
+                continue;
+            };
+
+            assert!(KzgProof::verify_blob_commitment(&blob, &commitment, &kzg_settings).unwrap());
+
+            let other = Bytes48::from_slice(&G1Affine::generator().to_compressed()).unwrap();
+            assert!(!KzgProof::verify_blob_commitment(&blob, &other, &kzg_settings).unwrap());
+        }
+
+        // The configured subgroup check decides whether a point outside the subgroup parses
+        let (blobs, _, _) = valid_blob_cases();
+        let outside = crate::subgroup::tests::g1_not_in_subgroup();
+        assert!(KzgProof::verify_blob_commitment(&blobs[0], &outside, &kzg_settings).is_err());
+        let unchecked = kzg_settings.with_subgroup_check(crate::SubgroupCheck::Skip);
+        assert!(!KzgProof::verify_blob_commitment(&blobs[0], &outside, &unchecked).unwrap());
+    }
+
+    /// Collects the blobs, commitments and proofs of every valid `verify_blob_kzg_proof` case
+    pub fn valid_blob_cases() -> (Vec<Blob>, Vec<Bytes48>, Vec<Bytes48>) {
+        let mut blobs = Vec::new();
+        let mut commitments = Vec::new();
+        let mut proofs = Vec::new();
+        for (_test_file, data) in VERIFY_BLOB_KZG_PROOF_TESTS {
+            let test: Test<BlobInput> = serde_yaml::from_str(data).unwrap();
+            if let (Ok(blob), Ok(commitment), Ok(proof), Some(true)) = (
+                test.input.get_blob(),
+                test.input.get_commitment(),
+                test.input.get_proof(),
+                test.get_output(),
+            ) {
+                blobs.push(blob);
+                commitments.push(commitment);
+                proofs.push(proof);
+            }
+        }
+        assert!(blobs.len() > 1);
+        (blobs, commitments, proofs)
+    }
+
+    /// A `verify_kzg_proof` case whose inputs have the lengths of a commitment, scalars and a proof
+    pub struct KzgProofCase {
+        pub commitment: Bytes48,
+        pub z: Bytes32,
+        pub y: Bytes32,
+        pub proof: Bytes48,
+        /// `None` if verification is expected to return an error
+        pub output: Option<bool>,
+    }
+
+    /// Collects every `verify_kzg_proof` case whose inputs parse as bytes of the right lengths
+    pub fn valid_kzg_proof_cases() -> Vec<KzgProofCase> {
+        let cases: Vec<_> = VERIFY_KZG_PROOF_TESTS
+            .iter()
+            .filter_map(|(_test_file, data)| {
+                let test: Test<Input> = serde_yaml::from_str(data).unwrap();
+                Some(KzgProofCase {
+                    commitment: test.input.get_commitment().ok()?,
+                    z: test.input.get_z().ok()?,
+                    y: test.input.get_y().ok()?,
+                    proof: test.input.get_proof().ok()?,
+                    output: test.get_output(),
+                })
+            })
+            .collect();
+        assert!(cases.len() > 1);
+        cases
+    }
+
+    #[test]
+    pub fn test_raw_api() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+
+        for case in valid_kzg_proof_cases() {
+            let result = KzgProof::verify_kzg_proof_raw(
+                &case.commitment.into(),
+                &case.z.into(),
+                &case.y.into(),
+                &case.proof.into(),
+                &kzg_settings,
+            );
+            assert_eq!(result.ok(), case.output);
+        }
+
+        let (blobs, commitments, proofs) = valid_blob_cases();
+        let blobs: Vec<[u8; BYTES_PER_BLOB]> = blobs.into_iter().map(Into::into).collect();
+        let commitments: Vec<[u8; 48]> = commitments.into_iter().map(Into::into).collect();
+        let mut proofs: Vec<[u8; 48]> = proofs.into_iter().map(Into::into).collect();
+
+        assert!(KzgProof::verify_blob_kzg_proof_raw(
+            &blobs[0],
+            &commitments[0],
+            &proofs[0],
+            &kzg_settings
+        )
+        .unwrap());
+        assert!(KzgProof::verify_blob_kzg_proof_batch_raw(
+            &blobs,
+            &commitments,
+            &proofs,
+            &kzg_settings
+        )
+        .unwrap());
+
+        proofs.swap(0, 1);
+        assert!(!KzgProof::verify_blob_kzg_proof_batch_raw(
+            &blobs,
+            &commitments,
+            &proofs,
+            &kzg_settings
+        )
+        .unwrap());
+    }
+
+    #[test]
+    pub fn test_blob_to_kzg_commitment() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+
+        for (_test_file, data) in VERIFY_BLOB_KZG_PROOF_TESTS {
+            let test: Test<BlobInput> = serde_yaml::from_str(data).unwrap();
+            let (Ok(blob), Ok(commitment), Some(true)) = (
+                test.input.get_blob(),
+                test.input.get_commitment(),
+                test.get_output(),
+            ) else {
+                continue;
+            };
+
+            let computed = KzgProof::blob_to_kzg_commitment(&blob, &kzg_settings).unwrap();
+            assert_eq!(computed.as_slice(), commitment.as_slice());
+        }
+
+        // A field element that is not canonical is rejected
+        let blob = Blob::from_slice(&[0xff; BYTES_PER_BLOB]).unwrap();
+        assert!(KzgProof::blob_to_kzg_commitment(&blob, &kzg_settings).is_err());
+    }
+
+    #[test]
+    pub fn test_compute_blob_kzg_proof() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let (blobs, commitments, proofs) = valid_blob_cases();
+
+        for ((blob, commitment), proof) in blobs.iter().zip(&commitments).zip(&proofs) {
+            let computed =
+                KzgProof::compute_blob_kzg_proof(blob, commitment, &kzg_settings).unwrap();
+            assert_eq!(computed.as_slice(), proof.as_slice());
+        }
+
+        let commitment = Bytes48::from([0xff; 48]);
+        assert!(KzgProof::compute_blob_kzg_proof(&blobs[0], &commitment, &kzg_settings).is_err());
+    }
+
+    #[test]
+    pub fn test_blob_patch_and_update_commitment() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let (blobs, commitments, _) = valid_blob_cases();
+
+        let mut blob = blobs[1].clone();
+        let patches = [
+            (0, FieldElement::from(1)),
+            (17, FieldElement::from(0xdead_beef)),
+            (4095, -FieldElement::ONE),
+        ];
+        let deltas = blob.patch(&patches).unwrap();
+        assert_eq!(blob.diff(&blobs[1]).len(), patches.len());
+        assert_eq!(
+            blob.field_element(17).unwrap(),
+            FieldElement::from(0xdead_beef)
+        );
+
+        let updated = KzgProof::update_commitment(&commitments[1], &deltas, &kzg_settings).unwrap();
+        let recomputed = KzgProof::blob_to_kzg_commitment(&blob, &kzg_settings).unwrap();
+        assert_eq!(updated.as_slice(), recomputed.as_slice());
+
+        // Patching an index twice keeps the last value and the commitment in sync
+        let deltas = blob
+            .patch(&[
+                (5, FieldElement::from(3)),
+                (5, FieldElement::from(11)),
+                (17, FieldElement::ZERO),
+            ])
+            .unwrap();
+        assert_eq!(blob.field_element(5).unwrap(), FieldElement::from(11));
+        let updated = KzgProof::update_commitment(&updated, &deltas, &kzg_settings).unwrap();
+        let recomputed = KzgProof::blob_to_kzg_commitment(&blob, &kzg_settings).unwrap();
+        assert_eq!(updated.as_slice(), recomputed.as_slice());
+
+        // An out-of-range patch leaves the blob untouched
+        let before = blob.clone();
+        assert!(blob
+            .patch(&[(0, FieldElement::ZERO), (4096, FieldElement::ZERO)])
+            .is_err());
+        assert!(blob.diff(&before).is_empty());
+    }
+
+    #[test]
+    pub fn test_compute_kzg_proof() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let (blobs, commitments, _) = valid_blob_cases();
+
+        for (blob, commitment) in blobs.iter().zip(&commitments) {
+            // Both a point outside the evaluation domain and one of the roots of unity
+            for z in [Scalar::from(12345), kzg_settings.roots_of_unity[7]] {
+                let z = scalar_to_bytes32(&z);
+                let opening = KzgProof::compute_kzg_proof(blob, &z, &kzg_settings).unwrap();
+                assert!(KzgProof::verify_kzg_proof(
+                    commitment,
+                    &z,
+                    &opening.y,
+                    &opening.proof,
+                    &kzg_settings
+                )
+                .unwrap());
+
+                let input = opening.to_precompile_input(commitment, &z).to_bytes();
+                assert!(crate::precompile::verify_point_evaluation(&input).is_ok());
+            }
+        }
+
+        // An evaluation point that is not a canonical field element is rejected
+        let z = Bytes32::from([0xff; 32]);
+        assert!(KzgProof::compute_kzg_proof(&blobs[0], &z, &kzg_settings).is_err());
+    }
+
+    #[test]
+    pub fn test_compute_quotient() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let (blobs, _, _) = valid_blob_cases();
+        let polynomial = blobs[0].to_polynomial().unwrap();
+        let x = Scalar::from(987654321);
+        let p_at_x = polynomial.evaluate(x, &kzg_settings).unwrap();
+
+        // q(x) (x - z) = p(x) - y, in and outside the domain
+        for z in [Scalar::from(12345), kzg_settings.roots_of_unity[7]] {
+            let (quotient, y) = compute_quotient(&polynomial, z, &kzg_settings).unwrap();
+            let q_at_x = quotient.evaluate(x, &kzg_settings).unwrap();
+            assert_eq!(q_at_x * (x - z), p_at_x - y);
+        }
+
+        let z = kzg_settings.roots_of_unity[7];
+        let (quotient, y) = compute_quotient(&polynomial, z, &kzg_settings).unwrap();
+        assert_eq!(y, polynomial.values()[7]);
+        assert_eq!(
+            compute_quotient_eval_within_domain(z, polynomial.values(), y, &kzg_settings).unwrap(),
+            quotient.values()[7]
+        );
+        assert!(compute_quotient(&Polynomial::Coefficients(vec![]), z, &kzg_settings).is_err());
+    }
+
+    #[test]
+    pub fn test_compute_kzg_proofs_multi() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let (blobs, commitments, _) = valid_blob_cases();
+
+        let zs = [
+            scalar_to_bytes32(&Scalar::from(12345)),
+            scalar_to_bytes32(&kzg_settings.roots_of_unity[7]),
+            scalar_to_bytes32(&Scalar::zero()),
+        ];
+        let openings = KzgProof::compute_kzg_proofs_multi(&blobs[1], &zs, &kzg_settings).unwrap();
+        assert_eq!(openings.len(), zs.len());
+        for (z, opening) in zs.iter().zip(&openings) {
+            assert!(KzgProof::verify_kzg_proof(
+                &commitments[1],
+                z,
+                &opening.y,
+                &opening.proof,
+                &kzg_settings
+            )
+            .unwrap());
+        }
+
+        assert!(
+            KzgProof::compute_kzg_proofs_multi(&blobs[1], &[], &kzg_settings)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    pub fn test_verify_kzg_proof_batch() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+
+        let (mut commitments, mut zs, mut ys, mut proofs) = (vec![], vec![], vec![], vec![]);
+        let mut bytes = (vec![], vec![], vec![], vec![]);
+        let mut invalid = None;
+        for case in valid_kzg_proof_cases() {
+            let Some(valid) = case.output else {
+                continue;
+            };
+
+            let opening = (
+                safe_g1_affine_from_bytes(&case.commitment).unwrap(),
+                FieldElement::from_bytes(&case.z).unwrap(),
+                FieldElement::from_bytes(&case.y).unwrap(),
+                safe_g1_affine_from_bytes(&case.proof).unwrap(),
+            );
+            if valid {
+                commitments.push(opening.0);
+                zs.push(opening.1);
+                ys.push(opening.2);
+                proofs.push(opening.3);
+                bytes.0.push(case.commitment);
+                bytes.1.push(case.z);
+                bytes.2.push(case.y);
+                bytes.3.push(case.proof);
+            } else {
+                invalid = Some(opening);
+            }
+        }
+        assert!(commitments.len() > 1);
+        assert!(
+            KzgProof::verify_kzg_proof_batch(&commitments, &zs, &ys, &proofs, &kzg_settings)
+                .unwrap()
+        );
+        assert!(KzgProof::verify_kzg_proof_batch_bytes(
+            &bytes.0,
+            &bytes.1,
+            &bytes.2,
+            &bytes.3,
+            &kzg_settings
+        )
+        .unwrap());
+
+        // A non-canonical evaluation is rejected rather than reduced
+        bytes.2[0] = Bytes32::from([0xff; 32]);
+        assert!(KzgProof::verify_kzg_proof_batch_bytes(
+            &bytes.0,
+            &bytes.1,
+            &bytes.2,
+            &bytes.3,
+            &kzg_settings
+        )
+        .is_err());
+
+        let invalid = invalid.unwrap();
+        commitments.push(invalid.0);
+        zs.push(invalid.1);
+        ys.push(invalid.2);
+        proofs.push(invalid.3);
+        assert!(
+            !KzgProof::verify_kzg_proof_batch(&commitments, &zs, &ys, &proofs, &kzg_settings)
+                .unwrap()
+        );
+
+        assert!(KzgProof::verify_kzg_proof_batch(
+            &commitments,
+            &zs[1..],
+            &ys,
+            &proofs,
+            &kzg_settings
+        )
+        .is_err());
+    }
+
+    #[test]
+    pub fn test_verify_blob_commitments() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let (blobs, mut commitments, _) = valid_blob_cases();
+
+        assert!(KzgProof::verify_blob_commitments(&blobs, &commitments, &kzg_settings).unwrap());
+
+        commitments.rotate_left(1);
+        assert!(!KzgProof::verify_blob_commitments(&blobs, &commitments, &kzg_settings).unwrap());
+
+        assert!(
+            KzgProof::verify_blob_commitments(&blobs, &commitments[1..], &kzg_settings).is_err()
+        );
+    }
+
+    #[test]
+    pub fn test_batch_progress() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let (blobs, commitments, proofs) = valid_blob_cases();
+        let total = blobs.len();
+
+        let mut reports = Vec::new();
+        assert!(KzgProof::verify_blob_commitments_with_progress(
+            &blobs,
+            &commitments,
+            &kzg_settings,
+            |progress| reports.push(progress),
+        )
+        .unwrap());
+        assert!(reports.windows(2).all(|w| w[0].completed < w[1].completed));
+        assert_eq!(reports.last().unwrap().completed, total);
+
+        let mut reports = Vec::new();
+        assert!(KzgProof::verify_blob_kzg_proof_batch_with_progress(
+            blobs,
+            commitments,
+            proofs,
+            &kzg_settings,
+            |progress| reports.push(progress),
+        )
+        .unwrap());
+        assert_eq!(reports.len(), total.div_ceil(progress::chunk_size()));
+        assert!(reports.last().unwrap().is_done());
+    }
+
+    #[test]
+    pub fn test_batch_cancel() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let (blobs, commitments, proofs) = valid_blob_cases();
+
+        let cancel = CancelToken::new();
+        assert!(KzgProof::verify_blob_commitments_with_cancel(
+            &blobs,
+            &commitments,
+            &kzg_settings,
+            &cancel
+        )
+        .unwrap());
+
+        cancel.cancel();
+        assert!(matches!(
+            KzgProof::verify_blob_commitments_with_cancel(
+                &blobs,
+                &commitments,
+                &kzg_settings,
+                &cancel
+            ),
+            Err(KzgError::Cancelled)
+        ));
+
+        let flag = core::sync::atomic::AtomicBool::new(true);
+        assert!(matches!(
+            KzgProof::verify_blob_kzg_proof_batch_with_cancel(
+                blobs,
+                commitments,
+                proofs,
+                &kzg_settings,
+                (&flag).into()
+            ),
+            Err(KzgError::Cancelled)
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    pub fn test_batch_deadline() {
+        use std::time::{Duration, Instant};
+
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let (blobs, commitments, proofs) = valid_blob_cases();
+
+        let result = KzgProof::verify_blob_kzg_proof_batch_with_deadline(
+            &blobs,
+            &commitments,
+            &proofs,
+            &kzg_settings,
+            Instant::now() + Duration::from_secs(3600),
+        )
+        .unwrap();
+        assert!(result.is_complete());
+        assert_eq!(result.verified, blobs.len());
+        assert!(result.valid);
+
+        let result = KzgProof::verify_blob_kzg_proof_batch_with_deadline(
+            &blobs,
+            &commitments,
+            &proofs,
+            &kzg_settings,
+            Instant::now(),
+        )
+        .unwrap();
+        assert_eq!(result.verified, 0);
+        assert_eq!(result.remaining, blobs.len());
+    }
+
+    #[test]
+    pub fn test_verify_blob_kzg_proof_batch_individual() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let (blobs, commitments, mut proofs) = valid_blob_cases();
+
+        let results = KzgProof::verify_blob_kzg_proof_batch_individual(
+            &blobs,
+            &commitments,
+            &proofs,
+            &kzg_settings,
+        )
+        .unwrap();
+        assert!(results.iter().all(|valid| *valid));
+
+        let last = proofs.len() - 1;
+        proofs.swap(1, last);
+        let results = KzgProof::verify_blob_kzg_proof_batch_individual(
+            &blobs,
+            &commitments,
+            &proofs,
+            &kzg_settings,
+        )
+        .unwrap();
+        for (i, valid) in results.into_iter().enumerate() {
+            assert_eq!(valid, i != 1 && i != last);
+        }
+    }
+
+    #[test]
+    pub fn test_verify_blob_kzg_proof_batch_detailed() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let (blobs, mut commitments, mut proofs) = valid_blob_cases();
+
+        commitments[0] = Bytes48::from_slice(&[0xff; 48]).unwrap();
+        proofs.swap(1, 2);
+        let results = KzgProof::verify_blob_kzg_proof_batch_detailed(
+            &blobs,
+            &commitments,
+            &proofs,
+            &kzg_settings,
+        )
+        .unwrap();
+
+        assert!(results[0].is_err());
+        assert!(!results[1].as_ref().unwrap());
+        assert!(!results[2].as_ref().unwrap());
+        assert!(results[3..].iter().all(|result| *result.as_ref().unwrap()));
+
+        assert!(KzgProof::verify_blob_kzg_proof_batch_detailed(
+            &blobs,
+            &commitments[1..],
+            &proofs,
+            &kzg_settings,
+        )
+        .is_err());
+    }
+
     #[derive(Debug, Deserialize)]
     struct BlobBatchInput<'a> {
         #[serde(borrow)]
@@ -737,7 +2264,8 @@ pub mod tests {
         let blob = test.input.get_blob().unwrap();
         let commitment = safe_g1_affine_from_bytes(&test.input.get_commitment().unwrap()).unwrap();
 
-        let evaluation_challenge = compute_challenge(&blob, &commitment).unwrap();
+        let evaluation_challenge =
+            compute_challenge(&blob, &commitment, &DomainSeparators::V1.blob_proof).unwrap();
 
         assert_eq!(
             format!("{evaluation_challenge}"),
@@ -745,6 +2273,34 @@ pub mod tests {
         )
     }
 
+    #[test]
+    pub fn test_compute_r_powers() {
+        let data = [
+            include_str!("../tests/verify_kzg_proof/verify_kzg_proof_case_correct_proof_02e696ada7d4631d/data.yaml"),
+            include_str!("../tests/verify_kzg_proof/verify_kzg_proof_case_correct_proof_05c1f3685f3393f0/data.yaml"),
+        ];
+        let (mut commitments, mut zs, mut ys, mut proofs) = (vec![], vec![], vec![], vec![]);
+        for data in data {
+            let test: Test<Input> = serde_yaml::from_str(data).unwrap();
+            commitments
+                .push(safe_g1_affine_from_bytes(&test.input.get_commitment().unwrap()).unwrap());
+            zs.push(safe_scalar_affine_from_bytes(&test.input.get_z().unwrap()).unwrap());
+            ys.push(safe_scalar_affine_from_bytes(&test.input.get_y().unwrap()).unwrap());
+            proofs.push(safe_g1_affine_from_bytes(&test.input.get_proof().unwrap()).unwrap());
+        }
+
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let r_powers = compute_r_powers(&commitments, &zs, &ys, &proofs, &kzg_settings).unwrap();
+        assert_eq!(r_powers[0], Scalar::one());
+        // Computed with the hash_to_bls_field of the consensus specs
+        assert_eq!(
+            format!("{}", r_powers[1]),
+            "0x343e84f85449e0fced57a962df27616941c352aaff7d5101699bb9ad96e97ad0"
+        );
+
+        assert!(compute_r_powers(&commitments, &zs[..1], &ys, &proofs, &kzg_settings).is_err());
+    }
+
     #[test]
     pub fn test_evaluate_polynomial_in_evaluation_form() {
         let data = include_str!("../tests/verify_blob_kzg_proof/verify_blob_kzg_proof_case_correct_proof_19b3f3f8c98ea31e/data.yaml");
@@ -760,13 +2316,84 @@ pub mod tests {
                 .into(),
         );
 
-        let y =
-            evaluate_polynomial_in_evaluation_form(polynomial, evaluation_challenge, &kzg_settings)
-                .unwrap();
+        let y = evaluate_polynomial_in_evaluation_form(
+            &polynomial,
+            evaluation_challenge,
+            &kzg_settings,
+        )
+        .unwrap();
 
         assert_eq!(
             format!("{y}"),
             "0x1bdfc5da40334b9c51220e8cbea1679c20a7f32dd3d7f3c463149bb4b41a7d18"
         );
+
+        // Several points at once, two of them in the domain
+        let xs = [
+            evaluation_challenge,
+            kzg_settings.roots_of_unity[9],
+            Scalar::from(5),
+            kzg_settings.roots_of_unity[0],
+        ];
+        let ys =
+            evaluate_polynomial_in_evaluation_form_multi(&polynomial, &xs, &kzg_settings).unwrap();
+        assert_eq!(ys[0], y);
+        assert_eq!(ys[1], polynomial[9]);
+        assert_eq!(
+            ys[2],
+            evaluate_polynomial_in_evaluation_form(&polynomial, xs[2], &kzg_settings).unwrap()
+        );
+        assert_eq!(ys[3], polynomial[0]);
+        assert!(
+            evaluate_polynomial_in_evaluation_form_multi(&polynomial, &[], &kzg_settings)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    // Unoptimized builds keep many more temporaries on the stack, so the budget only holds with
+    // optimizations enabled
+    #[cfg(all(feature = "bounded-stack", not(debug_assertions)))]
+    #[test]
+    fn test_bounded_stack() {
+        use crate::MAX_STACK_USAGE;
+
+        let (blobs, commitments, _) = valid_blob_cases();
+        let blob = Blob::boxed_from_slice(blobs[0].as_slice()).unwrap();
+        let commitment = commitments[0].clone();
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+
+        // Overflowing the stack of the thread aborts the test binary
+        std::thread::Builder::new()
+            .stack_size(MAX_STACK_USAGE)
+            .spawn(move || {
+                let computed = KzgProof::blob_to_kzg_commitment(&blob, &kzg_settings).unwrap();
+                assert_eq!(computed.as_slice(), commitment.as_slice());
+
+                let proof =
+                    KzgProof::compute_blob_kzg_proof(&blob, &commitment, &kzg_settings).unwrap();
+                assert!(KzgProof::verify_blob_kzg_proof_with_versioned_hash(
+                    &blob,
+                    &commitment,
+                    &proof,
+                    &kzg_to_versioned_hash(&commitment),
+                    &kzg_settings
+                )
+                .unwrap());
+
+                let z = Bytes32::from([1; 32]);
+                let opening = KzgProof::compute_kzg_proof(&blob, &z, &kzg_settings).unwrap();
+                assert!(KzgProof::verify_kzg_proof(
+                    &commitment,
+                    &z,
+                    &opening.y,
+                    &opening.proof,
+                    &kzg_settings
+                )
+                .unwrap());
+            })
+            .unwrap()
+            .join()
+            .unwrap();
     }
 }
@@ -0,0 +1,169 @@
+//! Trusted setups whose points are decompressed on first use.
+//!
+//! [`KzgSettings::load_trusted_setup`] decompresses every point up front, which takes a square
+//! root and a subgroup check per point. [`LazyKzgSettings`] keeps the compressed points and
+//! decompresses each group when it is first needed: the G2 points for verification and the G1
+//! points for commitments and proofs. A process that only verifies never decompresses the G1
+//! points, and a latency-sensitive server calls [`LazyKzgSettings::warm_up`] at startup to pay
+//! the whole cost before the first request.
+
+use crate::compression::{decompress_g1_batch, decompress_g2_batch};
+use crate::domains::DomainSeparators;
+use crate::dtypes::Bytes48;
+use crate::enums::KzgError;
+use crate::fft::EvaluationDomain;
+use crate::limits::Limits;
+use crate::subgroup::SubgroupCheck;
+use crate::trusted_setup::{split_points, KzgSettings, LEAKED_G2_POINTS};
+use crate::verifier::VerifierSettings;
+use crate::{BYTES_PER_G1_POINT, BYTES_PER_G2_POINT};
+
+use alloc::{string::ToString, vec::Vec};
+use bls12_381::G2Affine;
+use spin::Once;
+
+/// A trusted setup held in compressed form, decompressed group by group on first use
+#[derive(Debug)]
+pub struct LazyKzgSettings {
+    g1_lagrange: Vec<Bytes48>,
+    g1_monomial: Vec<Bytes48>,
+    g2: Vec<[u8; BYTES_PER_G2_POINT]>,
+    subgroup_check: SubgroupCheck,
+    g2_points: Once<Result<&'static [G2Affine], KzgError>>,
+    settings: Once<Result<KzgSettings, KzgError>>,
+}
+
+impl LazyKzgSettings {
+    /// Takes the concatenated compressed points of a trusted setup, in the layout of
+    /// [`KzgSettings::load_trusted_setup`]. Only the number of points is checked here, and the
+    /// points themselves when they are decompressed. Decompressed points are leaked, see
+    /// [`KzgSettings`].
+    pub fn new(
+        g1_lagrange_bytes: &[u8],
+        g1_monomial_bytes: &[u8],
+        g2_bytes: &[u8],
+    ) -> Result<Self, KzgError> {
+        let g1 = |bytes| {
+            split_points::<BYTES_PER_G1_POINT>(bytes, "G1")
+                .map(|points| points.into_iter().map(Bytes48::from).collect::<Vec<_>>())
+        };
+        let g1_lagrange = g1(g1_lagrange_bytes)?;
+        let g2 = split_points::<BYTES_PER_G2_POINT>(g2_bytes, "G2")?;
+        if g1_lagrange.len() < 2 || !g1_lagrange.len().is_power_of_two() {
+            return Err(KzgError::InvalidTrustedSetup(
+                "The number of G1 points must be a power of two".to_string(),
+            ));
+        }
+        if g2.len() < 2 {
+            return Err(KzgError::InvalidTrustedSetup(
+                "At least two G2 points are required".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            g1_lagrange,
+            g1_monomial: g1(g1_monomial_bytes)?,
+            g2,
+            subgroup_check: SubgroupCheck::default(),
+            g2_points: Once::new(),
+            settings: Once::new(),
+        })
+    }
+
+    /// Returns these settings checking the subgroup membership of the points with
+    /// `subgroup_check` when they are decompressed
+    pub fn with_subgroup_check(mut self, subgroup_check: SubgroupCheck) -> Self {
+        self.subgroup_check = subgroup_check;
+        self
+    }
+
+    /// Returns the settings for verification, decompressing only the G2 points
+    pub fn verifier_settings(&self) -> Result<VerifierSettings, KzgError> {
+        Ok(VerifierSettings {
+            g2_points: &self.g2_points()?[..2],
+            roots_of_unity: &EvaluationDomain::get(self.g1_lagrange.len())?.bit_reversed_roots,
+            subgroup_check: self.subgroup_check,
+            domain_separators: DomainSeparators::default(),
+            limits: Limits::default(),
+        })
+    }
+
+    /// Returns the full settings, decompressing the G1 points on the first call. A failure is
+    /// returned again by every later call.
+    pub fn settings(&self) -> Result<&KzgSettings, KzgError> {
+        self.settings
+            .call_once(|| {
+                let g2_points = self.g2_points()?.to_vec();
+                let g1_lagrange = decompress_g1_batch(&self.g1_lagrange, self.subgroup_check)?;
+                let g1_monomial = decompress_g1_batch(&self.g1_monomial, self.subgroup_check)?;
+                Ok(
+                    KzgSettings::from_points(g1_lagrange, g1_monomial, g2_points)?
+                        .with_subgroup_check(self.subgroup_check),
+                )
+            })
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+
+    /// Decompresses every point now rather than on first use
+    pub fn warm_up(&self) -> Result<(), KzgError> {
+        self.settings().map(|_| ())
+    }
+
+    /// Returns whether [`LazyKzgSettings::settings`] has run, successfully or not
+    pub fn is_decompressed(&self) -> bool {
+        self.settings.is_completed()
+    }
+
+    fn g2_points(&self) -> Result<&'static [G2Affine], KzgError> {
+        self.g2_points
+            .call_once(|| {
+                let points = decompress_g2_batch(&self.g2, self.subgroup_check)?;
+                Ok(LEAKED_G2_POINTS.leak(points))
+            })
+            .clone()
+    }
+}
+
+#[cfg(all(test, feature = "prover"))]
+mod tests {
+    use super::*;
+    use crate::fft::bit_reversal_permutation;
+    use crate::kzg_proof::tests::valid_blob_cases;
+
+    #[test]
+    fn test_lazy_settings() {
+        let mainnet = KzgSettings::load_trusted_setup_file().unwrap();
+        let mut g1_lagrange = bit_reversal_permutation(mainnet.g1_points)
+            .iter()
+            .flat_map(|point| point.to_compressed())
+            .collect::<Vec<_>>();
+        let g2 = mainnet
+            .g2_points
+            .iter()
+            .flat_map(|point| point.to_compressed())
+            .collect::<Vec<_>>();
+
+        let lazy = LazyKzgSettings::new(&g1_lagrange, &[], &g2).unwrap();
+        let verifier = lazy.verifier_settings().unwrap();
+        assert_eq!(verifier, VerifierSettings::from(&mainnet));
+        let (blobs, commitments, proofs) = valid_blob_cases();
+        assert!(verifier
+            .verify_blob_kzg_proof(&blobs[0], &commitments[0], &proofs[0])
+            .unwrap());
+        assert!(!lazy.is_decompressed());
+
+        lazy.warm_up().unwrap();
+        assert!(lazy.is_decompressed());
+        assert_eq!(*lazy.settings().unwrap(), mainnet);
+
+        // A corrupted G1 point only fails once the G1 points are needed
+        g1_lagrange[..BYTES_PER_G1_POINT].fill(0xff);
+        let lazy = LazyKzgSettings::new(&g1_lagrange, &[], &g2).unwrap();
+        assert!(lazy.verifier_settings().is_ok());
+        assert!(lazy.settings().is_err());
+        assert!(lazy.warm_up().is_err());
+
+        assert!(LazyKzgSettings::new(&g1_lagrange[..3 * BYTES_PER_G1_POINT], &[], &g2).is_err());
+    }
+}
@@ -1,23 +1,94 @@
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 #[macro_use]
 extern crate alloc;
 
+#[cfg(feature = "prover")]
+pub mod accumulator;
+#[cfg(feature = "legacy-aggregate")]
+pub mod aggregate;
+#[cfg(all(feature = "std", feature = "prover"))]
+pub mod archive;
+pub mod backend;
+#[cfg(feature = "prover")]
+pub mod binding;
+#[cfg(feature = "prover")]
+pub mod cancel;
+#[cfg(feature = "prover")]
+pub mod cells;
+#[cfg(feature = "prover")]
+pub mod column;
+pub mod compression;
 pub mod consts;
+pub mod domains;
+#[cfg(feature = "net")]
+pub mod download;
 pub mod dtypes;
 pub mod enums;
+pub mod fft;
+pub mod field;
+#[cfg(feature = "test-fixtures")]
+pub mod fixtures;
+#[cfg(feature = "prover")]
+pub mod fk20;
+pub mod generators;
+#[cfg(feature = "glv")]
+pub mod glv;
+pub mod index;
 pub mod kzg_proof;
+pub mod lazy;
+pub mod limits;
+pub mod msm;
+pub mod multiproof;
+pub mod observer;
 pub mod pairings;
+#[cfg(feature = "prover")]
+pub mod polynomial;
+pub mod precompile;
+pub mod prelude;
+#[cfg(feature = "prover")]
+pub mod progress;
+#[cfg(feature = "prover")]
+pub mod prover;
+#[cfg(feature = "prover")]
+pub mod recovery;
+#[cfg(feature = "revm")]
+pub mod revm;
+#[cfg(feature = "prover")]
+pub mod runtime;
+pub mod serialization;
+pub mod setups;
+pub mod subgroup;
+#[cfg(feature = "prover")]
+pub mod transcript;
 pub mod trusted_setup;
+pub mod utils;
+pub mod verifier;
+#[cfg(feature = "std")]
+pub mod warm_up;
 
+#[cfg(feature = "prover")]
+pub use cancel::CancelToken;
 pub use consts::*;
+pub use domains::{DomainSeparators, Fork};
 pub use dtypes::*;
+pub use field::FieldElement;
 pub use kzg_proof::KzgProof;
+pub use lazy::LazyKzgSettings;
+pub use limits::Limits;
 pub use pairings::pairings_verify;
+#[cfg(feature = "prover")]
+pub use polynomial::Polynomial;
+#[cfg(feature = "prover")]
+pub use progress::{PartialBatchResult, Progress};
+pub use setups::Setups;
+pub use subgroup::SubgroupCheck;
 pub use trusted_setup::*;
+pub use utils::{kzg_to_versioned_hash, verify_versioned_hashes, MismatchAt};
+pub use verifier::VerifierSettings;
 
-pub use enums::KzgError;
+pub use enums::{KzgError, Limit, LimitExceeded};
 
-#[cfg(test)]
+#[cfg(all(test, feature = "prover"))]
 mod test_files {
     // Tests
     pub const VERIFY_KZG_PROOF_TESTS: [(&str, &str); 122] = [
@@ -0,0 +1,135 @@
+//! Bounds on the size of the inputs accepted by the batch functions.
+//!
+//! Batch verification and recovery cost time and memory linear in their inputs, which RPC-facing
+//! services receive from untrusted peers. [`KzgSettings::with_limits`] makes the functions reject
+//! inputs over the configured sizes with [`KzgError::LimitExceeded`] before doing any work. The
+//! default limits are unbounded.
+
+use crate::enums::{KzgError, Limit, LimitExceeded};
+use crate::trusted_setup::KzgSettings;
+
+/// Largest inputs the batch functions accept
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Limits {
+    /// Largest number of blobs verified by one batch call
+    pub max_blobs_per_batch: usize,
+    /// Largest number of cells verified or recovered from by one call
+    pub max_cells_per_call: usize,
+    /// Largest total size in bytes of the blobs or cells, commitments and proofs of one call
+    pub max_total_bytes: usize,
+}
+
+impl Limits {
+    /// No limit on any input
+    pub const UNLIMITED: Self = Self {
+        max_blobs_per_batch: usize::MAX,
+        max_cells_per_call: usize::MAX,
+        max_total_bytes: usize::MAX,
+    };
+
+    /// Checks a batch of `count` blobs totalling `total_bytes` with their commitments and proofs
+    pub fn check_blobs(&self, count: usize, total_bytes: usize) -> Result<(), KzgError> {
+        check(Limit::BlobsPerBatch, count, self.max_blobs_per_batch)?;
+        check(Limit::TotalBytes, total_bytes, self.max_total_bytes)
+    }
+
+    /// Checks a call on `count` cells totalling `total_bytes` with their commitments and proofs
+    pub fn check_cells(&self, count: usize, total_bytes: usize) -> Result<(), KzgError> {
+        check(Limit::CellsPerCall, count, self.max_cells_per_call)?;
+        check(Limit::TotalBytes, total_bytes, self.max_total_bytes)
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self::UNLIMITED
+    }
+}
+
+fn check(limit: Limit, requested: usize, maximum: usize) -> Result<(), KzgError> {
+    if requested > maximum {
+        return Err(KzgError::LimitExceeded(LimitExceeded {
+            limit,
+            requested,
+            maximum,
+        }));
+    }
+    Ok(())
+}
+
+impl KzgSettings {
+    /// Returns these settings with the input limits replaced
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limits() {
+        let limits = Limits {
+            max_blobs_per_batch: 2,
+            max_cells_per_call: 64,
+            max_total_bytes: 1 << 20,
+        };
+        assert!(limits.check_blobs(2, 1000).is_ok());
+        assert!(matches!(
+            limits.check_blobs(3, 1000),
+            Err(KzgError::LimitExceeded(LimitExceeded {
+                limit: Limit::BlobsPerBatch,
+                requested: 3,
+                maximum: 2,
+            }))
+        ));
+        assert!(matches!(
+            limits.check_cells(65, 0),
+            Err(KzgError::LimitExceeded(LimitExceeded {
+                limit: Limit::CellsPerCall,
+                ..
+            }))
+        ));
+        assert!(matches!(
+            limits.check_cells(1, (1 << 20) + 1),
+            Err(KzgError::LimitExceeded(LimitExceeded {
+                limit: Limit::TotalBytes,
+                ..
+            }))
+        ));
+        assert!(Limits::default()
+            .check_blobs(usize::MAX, usize::MAX)
+            .is_ok());
+    }
+
+    #[cfg(feature = "prover")]
+    #[test]
+    fn test_limits_reject_batches() {
+        use crate::kzg_proof::tests::{BlobInput, Test};
+        use crate::kzg_proof::KzgProof;
+
+        let data = include_str!("../tests/verify_blob_kzg_proof/verify_blob_kzg_proof_case_correct_proof_fb324bc819407148/data.yaml");
+        let test: Test<BlobInput> = serde_yaml::from_str(data).unwrap();
+        let blob = test.input.get_blob().unwrap();
+        let commitment = test.input.get_commitment().unwrap();
+        let proof = test.input.get_proof().unwrap();
+
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let limited = kzg_settings.with_limits(Limits {
+            max_blobs_per_batch: 2,
+            ..Limits::UNLIMITED
+        });
+        let verify = |count: usize| {
+            KzgProof::verify_blob_kzg_proof_batch(
+                vec![blob.clone(); count],
+                vec![commitment.clone(); count],
+                vec![proof.clone(); count],
+                &limited,
+            )
+        };
+        assert!(verify(2).unwrap());
+        assert!(matches!(verify(3), Err(KzgError::LimitExceeded(_))));
+    }
+}
@@ -0,0 +1,373 @@
+//! Multi-scalar multiplication over G1 with Pippenger's bucket method.
+//!
+//! The scalars are cut into windows of `c` bits. In each window every point is added once to the
+//! bucket of its scalar's digit, and the buckets are summed with weights `1..2^c` by a running
+//! sum, so a window costs about `n + 2^(c+1)` additions instead of a scalar multiplication per
+//! point. The windows are then combined with `c` doublings each. With the `parallel` feature the
+//! windows are processed on the rayon thread pool.
+//!
+//! Commitments, proofs and the random linear combinations of batch verification all go through
+//! [`g1_msm`] or [`g1_msm_affine`].
+//!
+//! When the same points are used over and over, as the Lagrange points of the trusted setup are
+//! by every commitment, a [`FixedBaseTable`] stores each point shifted to every window once. An
+//! MSM over the table is then a single bucket pass over all windows, without doublings and with
+//! one bucket reduction instead of one per window.
+
+use crate::enums::KzgError;
+
+use alloc::vec::Vec;
+use bls12_381::{G1Affine, G1Projective, Scalar};
+use core::fmt;
+use core::ops::{AddAssign, Range};
+
+/// Below this many points the bucket setup costs more than it saves
+const PIPPENGER_THRESHOLD: usize = 8;
+
+/// Returns `Σ scalars[i] · points[i]`. Extra points or scalars beyond the shorter slice are
+/// ignored.
+pub fn g1_msm(points: &[G1Projective], scalars: &[Scalar]) -> G1Projective {
+    msm(points, scalars)
+}
+
+/// Returns `scalar · point`, with the GLV method under the `glv` feature. The batch verification
+/// equations use this for their public scalars, through the MSMs of fewer than
+/// `PIPPENGER_THRESHOLD` points.
+pub fn g1_mul(point: &G1Affine, scalar: &Scalar) -> G1Projective {
+    #[cfg(feature = "glv")]
+    {
+        crate::glv::g1_mul_glv(point, scalar)
+    }
+
+    #[cfg(not(feature = "glv"))]
+    {
+        point * scalar
+    }
+}
+
+/// Same as [`g1_msm`] for affine points, which are added to the buckets with the cheaper mixed
+/// addition
+pub fn g1_msm_affine(points: &[G1Affine], scalars: &[Scalar]) -> G1Projective {
+    msm(points, scalars)
+}
+
+/// Multiplication of a single point, for MSMs too small for Pippenger
+trait ScalarMul {
+    fn scalar_mul(&self, scalar: &Scalar) -> G1Projective;
+}
+
+impl ScalarMul for G1Affine {
+    fn scalar_mul(&self, scalar: &Scalar) -> G1Projective {
+        g1_mul(self, scalar)
+    }
+}
+
+impl ScalarMul for G1Projective {
+    fn scalar_mul(&self, scalar: &Scalar) -> G1Projective {
+        self * scalar
+    }
+}
+
+fn msm<P>(points: &[P], scalars: &[Scalar]) -> G1Projective
+where
+    P: Sync + ScalarMul,
+    G1Projective: for<'a> AddAssign<&'a P>,
+{
+    let n = points.len().min(scalars.len());
+    let (points, scalars) = (&points[..n], &scalars[..n]);
+    if n < PIPPENGER_THRESHOLD {
+        return points
+            .iter()
+            .zip(scalars)
+            .fold(G1Projective::identity(), |acc, (point, scalar)| {
+                acc + point.scalar_mul(scalar)
+            });
+    }
+
+    let c = window_bits(n);
+    let bytes = scalars.iter().map(Scalar::to_bytes).collect::<Vec<_>>();
+    let windows = (0..256).step_by(c);
+    let window_sum = |start: usize| window_sum(points, &bytes, start, c);
+
+    #[cfg(feature = "parallel")]
+    let sums = {
+        use rayon::prelude::*;
+        windows
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(window_sum)
+            .collect::<Vec<_>>()
+    };
+    #[cfg(not(feature = "parallel"))]
+    let sums = windows.map(window_sum).collect::<Vec<_>>();
+
+    // Horner's rule over the windows, highest first
+    sums.into_iter()
+        .rev()
+        .fold(G1Projective::identity(), |acc, sum| {
+            (0..c).fold(acc, |acc, _| acc.double()) + sum
+        })
+}
+
+/// Multiples `2^(k·w) · P_i` of a fixed set of G1 points `P_i`, for every `w`-bit window `k` of
+/// a scalar
+pub struct FixedBaseTable {
+    window_bits: usize,
+    /// Number of points
+    len: usize,
+    /// The points of window `k` at `k · len..(k + 1) · len`
+    points: Vec<G1Affine>,
+}
+
+impl FixedBaseTable {
+    /// Largest supported window, whose `2^16` buckets take about 9 MiB per MSM
+    pub const MAX_WINDOW_BITS: usize = 16;
+
+    /// Precomputes the table for `points` with windows of `window_bits` bits. Larger windows
+    /// need fewer additions per MSM and store fewer points, see [`FixedBaseTable::size_in_bytes`],
+    /// but more buckets.
+    pub fn new(points: &[G1Affine], window_bits: usize) -> Result<Self, KzgError> {
+        if !(1..=Self::MAX_WINDOW_BITS).contains(&window_bits) {
+            return Err(KzgError::BadArgs(format!(
+                "The window size must be between 1 and {} bits, got {}",
+                Self::MAX_WINDOW_BITS,
+                window_bits
+            )));
+        }
+
+        let len = points.len();
+        let windows = num_windows(window_bits);
+        let mut table = vec![G1Affine::identity(); windows * len];
+        let mut current = points.iter().map(G1Projective::from).collect::<Vec<_>>();
+        for (k, window) in table.chunks_mut(len.max(1)).enumerate() {
+            G1Projective::batch_normalize(&current, window);
+            if k + 1 < windows {
+                shift(&mut current, window_bits);
+            }
+        }
+
+        Ok(Self {
+            window_bits,
+            len,
+            points: table,
+        })
+    }
+
+    /// Returns the number of bytes a table for `len` points with `window_bits`-bit windows takes
+    pub fn size_in_bytes(len: usize, window_bits: usize) -> usize {
+        num_windows(window_bits.max(1)) * len * core::mem::size_of::<G1Affine>()
+    }
+
+    /// Returns the window size in bits
+    pub fn window_bits(&self) -> usize {
+        self.window_bits
+    }
+
+    /// Returns the number of points
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `Σ scalars[i] · P_i`. Extra points or scalars beyond the shorter of the two are
+    /// ignored.
+    pub fn msm(&self, scalars: &[Scalar]) -> G1Projective {
+        let n = self.len.min(scalars.len());
+        let bytes = scalars[..n]
+            .iter()
+            .map(Scalar::to_bytes)
+            .collect::<Vec<_>>();
+        let windows = num_windows(self.window_bits);
+        let windows_sum = |windows: Range<usize>| {
+            let mut buckets = vec![G1Projective::identity(); (1 << self.window_bits) - 1];
+            for k in windows {
+                let points = &self.points[k * self.len..][..n];
+                for (point, scalar) in points.iter().zip(&bytes) {
+                    let digit = digit(scalar, k * self.window_bits, self.window_bits);
+                    if digit != 0 {
+                        buckets[digit - 1] += point;
+                    }
+                }
+            }
+            weighted_bucket_sum(&buckets)
+        };
+
+        // Each thread fills its own buckets from a range of windows
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            let per_thread = windows.div_ceil(rayon::current_num_threads());
+            (0..windows)
+                .step_by(per_thread)
+                .collect::<Vec<_>>()
+                .into_par_iter()
+                .map(|start| windows_sum(start..(start + per_thread).min(windows)))
+                .reduce(G1Projective::identity, |a, b| a + b)
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        windows_sum(0..windows)
+    }
+}
+
+impl fmt::Debug for FixedBaseTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FixedBaseTable")
+            .field("window_bits", &self.window_bits)
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for FixedBaseTable {
+    fn eq(&self, other: &Self) -> bool {
+        // The other windows follow from the first one
+        core::ptr::eq(self, other)
+            || (self.window_bits == other.window_bits
+                && self.len == other.len
+                && self.points[..self.len] == other.points[..other.len])
+    }
+}
+
+impl Eq for FixedBaseTable {}
+
+/// Scalars are below the 255-bit modulus, so their top bit is always clear
+fn num_windows(window_bits: usize) -> usize {
+    255usize.div_ceil(window_bits)
+}
+
+/// Multiplies every point by `2^bits`
+fn shift(points: &mut [G1Projective], bits: usize) {
+    let double = |point: &mut G1Projective| {
+        for _ in 0..bits {
+            *point = point.double();
+        }
+    };
+
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        points.par_iter_mut().for_each(double);
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    points.iter_mut().for_each(double);
+}
+
+/// Window size minimizing the number of additions, about `ln(n)`
+fn window_bits(n: usize) -> usize {
+    let log2 = (usize::BITS - n.leading_zeros()) as usize;
+    (log2 * 69 / 100 + 2).min(16)
+}
+
+/// Returns `Σ digit_i · points[i]` where `digit_i` is the `c`-bit digit of scalar `i` starting at
+/// bit `start`
+fn window_sum<P>(points: &[P], bytes: &[[u8; 32]], start: usize, c: usize) -> G1Projective
+where
+    G1Projective: for<'a> AddAssign<&'a P>,
+{
+    let mut buckets = vec![G1Projective::identity(); (1 << c) - 1];
+    for (point, scalar) in points.iter().zip(bytes) {
+        let digit = digit(scalar, start, c);
+        if digit != 0 {
+            buckets[digit - 1] += point;
+        }
+    }
+    weighted_bucket_sum(&buckets)
+}
+
+/// Returns `Σ (j + 1) · buckets[j]` as the sum of the running sums from the top bucket down
+fn weighted_bucket_sum(buckets: &[G1Projective]) -> G1Projective {
+    let mut running = G1Projective::identity();
+    let mut sum = G1Projective::identity();
+    for bucket in buckets.iter().rev() {
+        running += bucket;
+        sum += running;
+    }
+    sum
+}
+
+/// Returns the `c` bits of the little-endian `scalar` starting at bit `start`
+fn digit(scalar: &[u8; 32], start: usize, c: usize) -> usize {
+    let mut digit = 0;
+    for bit in (start..(start + c).min(256)).rev() {
+        digit = (digit << 1) | ((scalar[bit / 8] >> (bit % 8)) & 1) as usize;
+    }
+    digit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_g1_msm() {
+        let naive = |points: &[G1Projective], scalars: &[Scalar]| {
+            points
+                .iter()
+                .zip(scalars)
+                .fold(G1Projective::identity(), |acc, (p, s)| acc + p * s)
+        };
+
+        for n in [0, 1, 7, 8, 33, 100] {
+            let points = (0..n as u64)
+                .map(|i| G1Projective::generator() * Scalar::from(i * 7 + 1))
+                .collect::<Vec<_>>();
+            // Large scalars with every window populated, and some zeros
+            let scalars = (0..n as u64)
+                .map(|i| {
+                    if i % 5 == 3 {
+                        Scalar::zero()
+                    } else {
+                        -Scalar::from(i * 1_000_003 + 11).square()
+                    }
+                })
+                .collect::<Vec<_>>();
+            let expected = naive(&points, &scalars);
+            assert_eq!(g1_msm(&points, &scalars), expected);
+
+            let affine = points.iter().map(G1Affine::from).collect::<Vec<_>>();
+            assert_eq!(g1_msm_affine(&affine, &scalars), expected);
+        }
+
+        // Mismatched lengths pair up the common prefix
+        let points = vec![G1Projective::generator(); 10];
+        let scalars = vec![Scalar::one(); 12];
+        assert_eq!(
+            g1_msm(&points, &scalars),
+            G1Projective::generator() * Scalar::from(10)
+        );
+    }
+
+    #[test]
+    fn test_fixed_base_table() {
+        let points = (0..20u64)
+            .map(|i| G1Affine::from(G1Projective::generator() * Scalar::from(i * 3 + 2)))
+            .collect::<Vec<_>>();
+        let scalars = (0..20u64)
+            .map(|i| -Scalar::from(i * 999_983 + 5).square())
+            .collect::<Vec<_>>();
+        let expected = g1_msm_affine(&points, &scalars);
+
+        for window_bits in [1, 4, 8, 13] {
+            let table = FixedBaseTable::new(&points, window_bits).unwrap();
+            assert_eq!(table.msm(&scalars), expected);
+            assert_eq!(
+                table.msm(&scalars[..5]),
+                g1_msm_affine(&points[..5], &scalars)
+            );
+            assert_eq!(
+                table.points.len() * core::mem::size_of::<G1Affine>(),
+                FixedBaseTable::size_in_bytes(points.len(), window_bits)
+            );
+        }
+
+        assert!(FixedBaseTable::new(&points, 0).is_err());
+        assert!(FixedBaseTable::new(&points, FixedBaseTable::MAX_WINDOW_BITS + 1).is_err());
+        let empty = FixedBaseTable::new(&[], 8).unwrap();
+        assert_eq!(empty.msm(&scalars), G1Projective::identity());
+    }
+}
@@ -0,0 +1,223 @@
+//! Multiproofs: one proof for the evaluations of a blob at several points.
+//!
+//! For points `z_1..z_k` and values `y_i = p(z_i)`, the proof is the commitment to
+//! `q = (p - I) / Z`, where `Z = Π (X - z_i)` vanishes on the points and `I` interpolates the
+//! values. It is checked with `e(π, [Z(τ)]₂) · e(G1, [I(τ)]₂) = e(C, G2)`, which needs the G2
+//! setup points up to degree `k`, so at most `g2_points.len() - 1` points can be opened at once.
+
+use crate::dtypes::*;
+use crate::enums::KzgError;
+use crate::field::FieldElement;
+use crate::trusted_setup::KzgSettings;
+
+use alloc::{string::ToString, vec::Vec};
+use bls12_381::{multi_miller_loop, G1Affine, G2Affine, G2Prepared, G2Projective, Gt, Scalar};
+
+#[cfg(feature = "prover")]
+use crate::{
+    kzg_proof::{compute_quotient, polynomial_to_commitment},
+    polynomial::Polynomial,
+};
+
+/// Returns the coefficients, lowest degree first, of `Π (X - z)` over `points`
+pub(crate) fn vanishing_polynomial(points: &[Scalar]) -> Vec<Scalar> {
+    let mut coefficients = vec![Scalar::one()];
+    for z in points {
+        // Multiply by (X - z)
+        coefficients.push(Scalar::zero());
+        for i in (0..coefficients.len()).rev() {
+            let lower = if i > 0 {
+                coefficients[i - 1]
+            } else {
+                Scalar::zero()
+            };
+            coefficients[i] = lower - coefficients[i] * z;
+        }
+    }
+    coefficients
+}
+
+/// Returns the coefficients of the polynomial of degree below `points.len()` through
+/// `(points[i], values[i])`
+fn interpolate(points: &[Scalar], values: &[Scalar]) -> Result<Vec<Scalar>, KzgError> {
+    let k = points.len();
+    let vanishing = vanishing_polynomial(points);
+    let mut coefficients = vec![Scalar::zero(); k];
+
+    for (z, y) in points.iter().zip(values) {
+        // Z / (X - z) by synthetic division, then scale by y / (Z / (X - z))(z)
+        let mut basis = vec![Scalar::zero(); k];
+        let mut carry = Scalar::zero();
+        for i in (0..k).rev() {
+            carry = vanishing[i + 1] + carry * z;
+            basis[i] = carry;
+        }
+        let denominator = evaluate(&basis, *z);
+        let scale = Option::<Scalar>::from(denominator.invert())
+            .ok_or_else(|| KzgError::BadArgs("The points must be distinct".to_string()))?
+            * y;
+        for (coefficient, b) in coefficients.iter_mut().zip(&basis) {
+            *coefficient += b * scale;
+        }
+    }
+    Ok(coefficients)
+}
+
+/// Evaluates a polynomial given by its coefficients with Horner's rule
+pub(crate) fn evaluate(coefficients: &[Scalar], x: Scalar) -> Scalar {
+    coefficients
+        .iter()
+        .rev()
+        .fold(Scalar::zero(), |acc, coefficient| acc * x + coefficient)
+}
+
+/// Commits to a polynomial given by its coefficients with the G2 monomial setup points
+fn commit_g2(coefficients: &[Scalar], kzg_settings: &KzgSettings) -> G2Affine {
+    coefficients
+        .iter()
+        .zip(kzg_settings.g2_points)
+        .fold(G2Projective::identity(), |acc, (coefficient, point)| {
+            acc + point * coefficient
+        })
+        .into()
+}
+
+fn check_point_count(k: usize, kzg_settings: &KzgSettings) -> Result<(), KzgError> {
+    if k == 0 || k >= kzg_settings.g2_points.len() {
+        return Err(KzgError::BadArgs(format!(
+            "A multiproof opens between 1 and {} points, got {}",
+            kzg_settings.g2_points.len() - 1,
+            k
+        )));
+    }
+    Ok(())
+}
+
+/// Computes one proof for the evaluations of `blob` at every point in `points`, returning the
+/// proof and the evaluations
+#[cfg(feature = "prover")]
+pub fn compute_kzg_multiproof(
+    blob: &Blob,
+    points: &[FieldElement],
+    kzg_settings: &KzgSettings,
+) -> Result<(Bytes48, Vec<FieldElement>), KzgError> {
+    check_point_count(points.len(), kzg_settings)?;
+    let points = FieldElement::as_scalars(points);
+    let polynomial = blob.to_polynomial()?;
+
+    let values = polynomial.evaluate_many(points, kzg_settings)?;
+    let interpolation = interpolate(points, &values)?;
+
+    // p - I vanishes on every point, so dividing by each (X - z) in turn leaves the quotient
+    let mut quotient = Polynomial::Evaluations(
+        polynomial
+            .values()
+            .iter()
+            .zip(kzg_settings.roots_of_unity)
+            .map(|(p, root)| p - evaluate(&interpolation, *root))
+            .collect(),
+    );
+    for z in points {
+        quotient = compute_quotient(&quotient, *z, kzg_settings)?.0;
+    }
+
+    let proof = polynomial_to_commitment(&quotient, kzg_settings)?;
+    let values = values.into_iter().map(FieldElement::from).collect();
+    Ok((Bytes48::from(proof.to_compressed()), values))
+}
+
+/// Verifies that the polynomial committed to by `commitment_bytes` evaluates to `values[i]` at
+/// `points[i]` for every `i`, using a proof from [`compute_kzg_multiproof`]
+pub fn verify_kzg_multiproof(
+    commitment_bytes: &Bytes48,
+    points: &[FieldElement],
+    values: &[FieldElement],
+    proof_bytes: &Bytes48,
+    kzg_settings: &KzgSettings,
+) -> Result<bool, KzgError> {
+    if points.len() != values.len() {
+        return Err(KzgError::InvalidBytesLength(
+            "The number of values does not match the number of points".to_string(),
+        ));
+    }
+    check_point_count(points.len(), kzg_settings)?;
+
+    let commitment = kzg_settings
+        .subgroup_check
+        .g1_from_bytes(commitment_bytes)?;
+    let proof = kzg_settings.subgroup_check.g1_from_bytes(proof_bytes)?;
+
+    let points = FieldElement::as_scalars(points);
+    let vanishing = commit_g2(&vanishing_polynomial(points), kzg_settings);
+    let interpolation = commit_g2(
+        &interpolate(points, FieldElement::as_scalars(values))?,
+        kzg_settings,
+    );
+
+    Ok(multi_miller_loop(&[
+        (&proof, &G2Prepared::from(vanishing)),
+        (&G1Affine::generator(), &G2Prepared::from(interpolation)),
+        (&-commitment, &G2Prepared::from(G2Affine::generator())),
+    ])
+    .final_exponentiation()
+        == Gt::identity())
+}
+
+#[cfg(all(test, feature = "prover"))]
+mod tests {
+    use super::*;
+    use crate::kzg_proof::tests::valid_blob_cases;
+    use crate::kzg_proof::KzgProof;
+
+    #[test]
+    fn test_interpolate() {
+        let points = [1, 2, 3].map(Scalar::from);
+        let values = [6, 11, 18].map(Scalar::from);
+        // X^2 + 2X + 3
+        assert_eq!(
+            interpolate(&points, &values).unwrap(),
+            [3, 2, 1].map(Scalar::from)
+        );
+        assert!(interpolate(&[Scalar::one(), Scalar::one()], &values[..2]).is_err());
+    }
+
+    #[test]
+    fn test_kzg_multiproof() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let (blobs, commitments, _) = valid_blob_cases();
+        let (blob, commitment) = (&blobs[1], &commitments[1]);
+
+        // Points outside the evaluation domain and one of the roots of unity
+        let points = [
+            FieldElement::from(12345),
+            FieldElement::from(kzg_settings.roots_of_unity[7]),
+            FieldElement::from(99),
+        ];
+        let (proof, values) = compute_kzg_multiproof(blob, &points, &kzg_settings).unwrap();
+        assert!(
+            verify_kzg_multiproof(commitment, &points, &values, &proof, &kzg_settings).unwrap()
+        );
+
+        let mut wrong = values.clone();
+        wrong[2] += FieldElement::ONE;
+        assert!(
+            !verify_kzg_multiproof(commitment, &points, &wrong, &proof, &kzg_settings).unwrap()
+        );
+        assert!(
+            verify_kzg_multiproof(commitment, &points[1..], &values, &proof, &kzg_settings)
+                .is_err()
+        );
+
+        // Opening a single point gives the usual KZG proof
+        let (single, _) = compute_kzg_multiproof(blob, &points[..1], &kzg_settings).unwrap();
+        let expected =
+            KzgProof::compute_kzg_proof(blob, &points[0].to_bytes(), &kzg_settings).unwrap();
+        assert_eq!(single.as_slice(), expected.proof.as_slice());
+
+        let too_many = (0..kzg_settings.g2_points.len() as u64)
+            .map(FieldElement::from)
+            .collect::<Vec<_>>();
+        assert!(compute_kzg_multiproof(blob, &too_many, &kzg_settings).is_err());
+        assert!(compute_kzg_multiproof(blob, &[], &kzg_settings).is_err());
+    }
+}
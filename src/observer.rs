@@ -0,0 +1,176 @@
+//! Failure notifications for environments without `std` logging.
+//!
+//! Wrapping a backend in [`ObservedBackend`] reports every rejected proof and every input that
+//! fails to parse to a [`FailureObserver`], while returning the same results as the wrapped
+//! backend.
+
+use crate::backend::KzgBackend;
+use crate::dtypes::*;
+use crate::enums::KzgError;
+use crate::trusted_setup::KzgSettings;
+
+/// Verification entry point that reported a failure
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    VerifyKzgProof,
+    VerifyBlobKzgProof,
+    VerifyBlobKzgProofBatch,
+}
+
+/// Why a verification failed
+#[derive(Debug, Clone, Copy)]
+pub enum FailureReason<'a> {
+    /// The inputs were well formed but the proof did not verify
+    InvalidProof,
+    /// The inputs were rejected before the proof could be checked
+    Rejected(&'a KzgError),
+}
+
+/// Details of a failed verification passed to [`FailureObserver::on_verification_failure`]
+#[derive(Debug, Clone, Copy)]
+pub struct FailureInfo<'a> {
+    pub operation: Operation,
+    pub reason: FailureReason<'a>,
+    /// Number of openings covered by the call, `1` except for batches
+    pub count: usize,
+}
+
+/// Receives a notification for every failed verification
+pub trait FailureObserver: Send + Sync {
+    fn on_verification_failure(&self, info: FailureInfo);
+}
+
+/// Backend that forwards to `B` and reports failures to `O`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ObservedBackend<B, O> {
+    pub backend: B,
+    pub observer: O,
+}
+
+impl<B: KzgBackend, O: FailureObserver> ObservedBackend<B, O> {
+    pub fn new(backend: B, observer: O) -> Self {
+        Self { backend, observer }
+    }
+
+    fn observe(
+        &self,
+        operation: Operation,
+        count: usize,
+        result: Result<bool, KzgError>,
+    ) -> Result<bool, KzgError> {
+        let reason = match &result {
+            Ok(true) => return result,
+            Ok(false) => FailureReason::InvalidProof,
+            Err(e) => FailureReason::Rejected(e),
+        };
+        self.observer.on_verification_failure(FailureInfo {
+            operation,
+            reason,
+            count,
+        });
+        result
+    }
+}
+
+impl<B: KzgBackend, O: FailureObserver> KzgBackend for ObservedBackend<B, O> {
+    fn name(&self) -> &'static str {
+        self.backend.name()
+    }
+
+    fn verify_kzg_proof(
+        &self,
+        commitment_bytes: &Bytes48,
+        z_bytes: &Bytes32,
+        y_bytes: &Bytes32,
+        proof_bytes: &Bytes48,
+        kzg_settings: &KzgSettings,
+    ) -> Result<bool, KzgError> {
+        let result = self.backend.verify_kzg_proof(
+            commitment_bytes,
+            z_bytes,
+            y_bytes,
+            proof_bytes,
+            kzg_settings,
+        );
+        self.observe(Operation::VerifyKzgProof, 1, result)
+    }
+
+    fn verify_blob_kzg_proof(
+        &self,
+        blob: &Blob,
+        commitment_bytes: &Bytes48,
+        proof_bytes: &Bytes48,
+        kzg_settings: &KzgSettings,
+    ) -> Result<bool, KzgError> {
+        let result =
+            self.backend
+                .verify_blob_kzg_proof(blob, commitment_bytes, proof_bytes, kzg_settings);
+        self.observe(Operation::VerifyBlobKzgProof, 1, result)
+    }
+
+    fn verify_blob_kzg_proof_batch(
+        &self,
+        blobs: &[Blob],
+        commitments_bytes: &[Bytes48],
+        proofs_bytes: &[Bytes48],
+        kzg_settings: &KzgSettings,
+    ) -> Result<bool, KzgError> {
+        let result = self.backend.verify_blob_kzg_proof_batch(
+            blobs,
+            commitments_bytes,
+            proofs_bytes,
+            kzg_settings,
+        );
+        self.observe(Operation::VerifyBlobKzgProofBatch, blobs.len(), result)
+    }
+}
+
+#[cfg(all(test, feature = "prover"))]
+mod tests {
+    use super::*;
+    use crate::backend::RustBackend;
+    use crate::kzg_proof::tests::valid_kzg_proof_cases;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct CountingObserver {
+        invalid: AtomicUsize,
+        rejected: AtomicUsize,
+    }
+
+    impl FailureObserver for &CountingObserver {
+        fn on_verification_failure(&self, info: FailureInfo) {
+            assert_eq!(info.operation, Operation::VerifyKzgProof);
+            match info.reason {
+                FailureReason::InvalidProof => self.invalid.fetch_add(1, Ordering::Relaxed),
+                FailureReason::Rejected(_) => self.rejected.fetch_add(1, Ordering::Relaxed),
+            };
+        }
+    }
+
+    #[test]
+    fn test_observed_backend() {
+        let observer = CountingObserver::default();
+        let backend = ObservedBackend::new(RustBackend, &observer);
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+
+        let (mut invalid, mut rejected) = (0, 0);
+        for case in valid_kzg_proof_cases() {
+            match backend.verify_kzg_proof(
+                &case.commitment,
+                &case.z,
+                &case.y,
+                &case.proof,
+                &kzg_settings,
+            ) {
+                Ok(true) => {}
+                Ok(false) => invalid += 1,
+                Err(_) => rejected += 1,
+            }
+        }
+
+        assert!(invalid > 0 && rejected > 0);
+        assert_eq!(observer.invalid.load(Ordering::Relaxed), invalid);
+        assert_eq!(observer.rejected.load(Ordering::Relaxed), rejected);
+    }
+}
@@ -3,7 +3,15 @@ use bls12_381::{multi_miller_loop, G1Affine, G2Affine, G2Prepared, Gt, Scalar};
 
 /// Verifies the pairing of two G1 and two G2 points are equivalent using the multi-miller loop
 pub fn pairings_verify(a1: G1Affine, a2: G2Affine, b1: G1Affine, b2: G2Affine) -> bool {
-    multi_miller_loop(&[(&-a1, &G2Prepared::from(a2)), (&b1, &G2Prepared::from(b2))])
-        .final_exponentiation()
-        == Gt::identity()
+    pairings_verify_prepared(a1, &G2Prepared::from(a2), b1, &G2Prepared::from(b2))
+}
+
+/// Same as [`pairings_verify`] with the G2 points already prepared for the Miller loop
+pub fn pairings_verify_prepared(
+    a1: G1Affine,
+    a2: &G2Prepared,
+    b1: G1Affine,
+    b2: &G2Prepared,
+) -> bool {
+    multi_miller_loop(&[(&-a1, a2), (&b1, b2)]).final_exponentiation() == Gt::identity()
 }
@@ -0,0 +1,307 @@
+//! Polynomials over the scalar field, in evaluation or coefficient form.
+//!
+//! Blobs are polynomials in evaluation form: their field elements are the values at the roots of
+//! unity of the trusted setup, in the order of [`KzgSettings::roots_of_unity`]. The prover works
+//! on that form throughout, while interpolation and recovery produce coefficients. [`Polynomial`]
+//! keeps track of which form a vector of scalars is in, so the two are not mixed up, and converts
+//! between them with an FFT and the bit-reversal permutation of the evaluations.
+
+use crate::dtypes::Bytes48;
+use crate::enums::KzgError;
+use crate::fft::{bit_reversal_permutation, fft, inverse_fft};
+use crate::kzg_proof::{
+    evaluate_polynomial_in_evaluation_form, evaluate_polynomial_in_evaluation_form_multi,
+    polynomial_to_commitment,
+};
+use crate::msm::g1_msm;
+use crate::multiproof::evaluate as evaluate_coefficients;
+use crate::trusted_setup::KzgSettings;
+
+use alloc::{string::ToString, vec::Vec};
+use bls12_381::{G1Affine, G1Projective, Scalar};
+use core::ops::Mul;
+
+/// A polynomial in one of its two representations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Polynomial {
+    /// Values at the roots of unity of the trusted setup, in the order of
+    /// [`KzgSettings::roots_of_unity`]
+    Evaluations(Vec<Scalar>),
+    /// Coefficients, lowest degree first
+    Coefficients(Vec<Scalar>),
+}
+
+impl Polynomial {
+    /// Returns the values or coefficients
+    pub fn values(&self) -> &[Scalar] {
+        match self {
+            Self::Evaluations(values) | Self::Coefficients(values) => values,
+        }
+    }
+
+    /// Returns the values or coefficients, without copying them
+    pub fn into_values(self) -> Vec<Scalar> {
+        match self {
+            Self::Evaluations(values) | Self::Coefficients(values) => values,
+        }
+    }
+
+    /// Returns the number of values or coefficients
+    pub fn len(&self) -> usize {
+        self.values().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values().is_empty()
+    }
+
+    /// Returns the values of a polynomial in evaluation form, failing on coefficient form
+    pub(crate) fn evaluations(&self) -> Result<&[Scalar], KzgError> {
+        match self {
+            Self::Evaluations(values) => Ok(values),
+            Self::Coefficients(_) => Err(KzgError::BadArgs(
+                "The polynomial must be in evaluation form".to_string(),
+            )),
+        }
+    }
+
+    /// Adds two polynomials in the same form. Polynomials in evaluation form must have the same
+    /// length, while the shorter of two coefficient vectors is padded with zeros.
+    pub fn checked_add(&self, other: &Self) -> Result<Self, KzgError> {
+        match (self, other) {
+            (Self::Evaluations(a), Self::Evaluations(b)) => {
+                if a.len() != b.len() {
+                    return Err(KzgError::InvalidBytesLength(format!(
+                        "Cannot add polynomials of {} and {} evaluations",
+                        a.len(),
+                        b.len()
+                    )));
+                }
+                Ok(Self::Evaluations(
+                    a.iter().zip(b).map(|(a, b)| a + b).collect(),
+                ))
+            }
+            (Self::Coefficients(a), Self::Coefficients(b)) => {
+                let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+                let mut sum = long.clone();
+                for (s, b) in sum.iter_mut().zip(short) {
+                    *s += b;
+                }
+                Ok(Self::Coefficients(sum))
+            }
+            _ => Err(KzgError::BadArgs(
+                "Cannot add polynomials in different forms".to_string(),
+            )),
+        }
+    }
+
+    /// Multiplies the polynomial by `factor`
+    pub fn scale(&self, factor: Scalar) -> Self {
+        self.clone() * factor
+    }
+
+    /// Evaluates the polynomial at `x`. Polynomials in evaluation form must have one value per
+    /// root of unity of `kzg_settings`, which is not used for coefficient form.
+    pub fn evaluate(&self, x: Scalar, kzg_settings: &KzgSettings) -> Result<Scalar, KzgError> {
+        match self {
+            Self::Evaluations(values) => {
+                evaluate_polynomial_in_evaluation_form(values, x, kzg_settings)
+            }
+            Self::Coefficients(coefficients) => Ok(evaluate_coefficients(coefficients, x)),
+        }
+    }
+
+    /// Evaluates the polynomial at every point of `xs`, in order. In evaluation form the points
+    /// share a single batched inversion, see [`evaluate_polynomial_in_evaluation_form_multi`].
+    pub fn evaluate_many(
+        &self,
+        xs: &[Scalar],
+        kzg_settings: &KzgSettings,
+    ) -> Result<Vec<Scalar>, KzgError> {
+        match self {
+            Self::Evaluations(values) => {
+                evaluate_polynomial_in_evaluation_form_multi(values, xs, kzg_settings)
+            }
+            Self::Coefficients(coefficients) => Ok(xs
+                .iter()
+                .map(|x| evaluate_coefficients(coefficients, *x))
+                .collect()),
+        }
+    }
+
+    /// Returns the polynomial in coefficient form. The number of evaluations must be a power of
+    /// two, and as many coefficients are returned.
+    pub fn to_coefficient_form(&self) -> Result<Self, KzgError> {
+        match self {
+            Self::Evaluations(values) => Ok(Self::Coefficients(inverse_fft(
+                &bit_reversal_permutation(values),
+            )?)),
+            Self::Coefficients(_) => Ok(self.clone()),
+        }
+    }
+
+    /// Returns the polynomial in evaluation form over the roots of unity of `kzg_settings`. A
+    /// polynomial in coefficient form must have at most one coefficient per root of unity.
+    pub fn to_evaluation_form(&self, kzg_settings: &KzgSettings) -> Result<Self, KzgError> {
+        let n = kzg_settings.roots_of_unity.len();
+        match self {
+            Self::Evaluations(values) if values.len() != n => {
+                Err(KzgError::InvalidBytesLength(format!(
+                    "The polynomial has {} evaluations, but the trusted setup has {} points",
+                    values.len(),
+                    n
+                )))
+            }
+            Self::Evaluations(_) => Ok(self.clone()),
+            Self::Coefficients(coefficients) => {
+                if coefficients.len() > n {
+                    return Err(KzgError::InvalidBytesLength(format!(
+                        "The polynomial has {} coefficients, but the trusted setup has {} points",
+                        coefficients.len(),
+                        n
+                    )));
+                }
+                let mut padded = coefficients.clone();
+                padded.resize(n, Scalar::zero());
+                Ok(Self::Evaluations(bit_reversal_permutation(&fft(&padded)?)))
+            }
+        }
+    }
+
+    /// Computes the commitment to the polynomial, with the Lagrange-form points of `kzg_settings`
+    /// for evaluation form and its monomial-form points for coefficient form. Both forms of a
+    /// polynomial have the same commitment.
+    pub fn commit(&self, kzg_settings: &KzgSettings) -> Result<Bytes48, KzgError> {
+        let commitment = match self {
+            Self::Evaluations(_) => polynomial_to_commitment(self, kzg_settings)?,
+            Self::Coefficients(coefficients) => {
+                let monomial = kzg_settings.g1_monomial()?;
+                if coefficients.len() > monomial.len() {
+                    return Err(KzgError::InvalidBytesLength(format!(
+                        "The polynomial has {} coefficients, but the trusted setup has {} points",
+                        coefficients.len(),
+                        monomial.len()
+                    )));
+                }
+                let points = monomial[..coefficients.len()]
+                    .iter()
+                    .map(G1Projective::from)
+                    .collect::<Vec<_>>();
+                G1Affine::from(g1_msm(&points, coefficients))
+            }
+        };
+        Ok(Bytes48::from(commitment.to_compressed()))
+    }
+}
+
+impl Mul<Scalar> for Polynomial {
+    type Output = Self;
+
+    fn mul(mut self, factor: Scalar) -> Self {
+        match &mut self {
+            Self::Evaluations(values) | Self::Coefficients(values) => {
+                for value in values.iter_mut() {
+                    *value *= factor;
+                }
+            }
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg_proof::tests::valid_blob_cases;
+    use crate::NUM_FIELD_ELEMENTS_PER_BLOB;
+
+    #[test]
+    fn test_polynomial_arithmetic() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let x = Scalar::from(12345);
+
+        // p = 1 + 2X + 3X², q = 5 - X
+        let p = Polynomial::Coefficients(vec![Scalar::from(1), Scalar::from(2), Scalar::from(3)]);
+        let q = Polynomial::Coefficients(vec![Scalar::from(5), -Scalar::one()]);
+        let p_at_x = p.evaluate(x, &kzg_settings).unwrap();
+        let q_at_x = q.evaluate(x, &kzg_settings).unwrap();
+        assert_eq!(p_at_x, Scalar::from(1 + 2 * 12345 + 3 * 12345 * 12345));
+        assert_eq!(
+            p.checked_add(&q)
+                .unwrap()
+                .evaluate(x, &kzg_settings)
+                .unwrap(),
+            p_at_x + q_at_x
+        );
+        assert_eq!(q.checked_add(&p).unwrap(), p.checked_add(&q).unwrap());
+        assert_eq!(
+            p.scale(Scalar::from(7)).evaluate(x, &kzg_settings).unwrap(),
+            p_at_x * Scalar::from(7)
+        );
+
+        // The same polynomials in evaluation form
+        let p_evaluations = p.to_evaluation_form(&kzg_settings).unwrap();
+        let q_evaluations = q.to_evaluation_form(&kzg_settings).unwrap();
+        assert_eq!(p_evaluations.evaluate(x, &kzg_settings).unwrap(), p_at_x);
+        let sum = p_evaluations.checked_add(&q_evaluations).unwrap() * Scalar::from(2);
+        assert_eq!(
+            sum.evaluate(x, &kzg_settings).unwrap(),
+            (p_at_x + q_at_x) * Scalar::from(2)
+        );
+        assert_eq!(
+            sum.evaluate(kzg_settings.roots_of_unity[3], &kzg_settings)
+                .unwrap(),
+            sum.values()[3]
+        );
+
+        assert!(p.checked_add(&p_evaluations).is_err());
+        assert!(p_evaluations
+            .checked_add(&Polynomial::Evaluations(vec![Scalar::one()]))
+            .is_err());
+        assert!(Polynomial::Evaluations(vec![Scalar::one()])
+            .evaluate(x, &kzg_settings)
+            .is_err());
+    }
+
+    #[test]
+    fn test_polynomial_forms() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let (blobs, commitments, _) = valid_blob_cases();
+        let evaluations = blobs[0].to_polynomial().unwrap();
+
+        let coefficients = evaluations.to_coefficient_form().unwrap();
+        assert!(matches!(coefficients, Polynomial::Coefficients(_)));
+        let x = Scalar::from(424242);
+        assert_eq!(
+            coefficients.evaluate(x, &kzg_settings).unwrap(),
+            evaluations.evaluate(x, &kzg_settings).unwrap()
+        );
+        assert_eq!(
+            coefficients.to_evaluation_form(&kzg_settings).unwrap(),
+            evaluations
+        );
+
+        assert_eq!(
+            evaluations.commit(&kzg_settings).unwrap().as_slice(),
+            commitments[0].as_slice()
+        );
+        assert_eq!(
+            coefficients.commit(&kzg_settings).unwrap().as_slice(),
+            commitments[0].as_slice()
+        );
+
+        // Shorter coefficient vectors are padded
+        let low = Polynomial::Coefficients(vec![Scalar::from(3), Scalar::from(4)]);
+        let low_evaluations = low.to_evaluation_form(&kzg_settings).unwrap();
+        assert_eq!(low_evaluations.len(), NUM_FIELD_ELEMENTS_PER_BLOB);
+        assert_eq!(
+            low.commit(&kzg_settings).unwrap().as_slice(),
+            low_evaluations.commit(&kzg_settings).unwrap().as_slice()
+        );
+        assert!(
+            Polynomial::Coefficients(vec![Scalar::one(); NUM_FIELD_ELEMENTS_PER_BLOB + 1])
+                .to_evaluation_form(&kzg_settings)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,255 @@
+//! Helpers for the EIP-4844 point evaluation precompile at address `0x0A`.
+
+use crate::enums::KzgError;
+use crate::kzg_proof::KzgProof;
+use crate::trusted_setup::{EnvKzgSettings, KzgSettings};
+use crate::{dtypes::*, kzg_to_versioned_hash, MODULUS, NUM_FIELD_ELEMENTS_PER_BLOB};
+
+use alloc::string::ToString;
+
+/// Length of the point evaluation precompile calldata
+pub const POINT_EVALUATION_INPUT_LENGTH: usize = 192;
+/// Length of the data returned by a successful point evaluation precompile call
+pub const POINT_EVALUATION_OUTPUT_LENGTH: usize = 64;
+
+/// Inputs of the point evaluation precompile, laid out in the calldata as
+/// `versioned_hash || z || y || commitment || proof`
+#[derive(Debug, Clone)]
+pub struct PointEvaluationInput {
+    pub versioned_hash: Bytes32,
+    pub z: Bytes32,
+    pub y: Bytes32,
+    pub commitment: Bytes48,
+    pub proof: Bytes48,
+}
+
+impl PointEvaluationInput {
+    /// Packs the inputs into the 192-byte calldata expected by the precompile
+    pub fn to_bytes(&self) -> [u8; POINT_EVALUATION_INPUT_LENGTH] {
+        let mut bytes = [0u8; POINT_EVALUATION_INPUT_LENGTH];
+        bytes[..32].copy_from_slice(self.versioned_hash.as_slice());
+        bytes[32..64].copy_from_slice(self.z.as_slice());
+        bytes[64..96].copy_from_slice(self.y.as_slice());
+        bytes[96..144].copy_from_slice(self.commitment.as_slice());
+        bytes[144..].copy_from_slice(self.proof.as_slice());
+        bytes
+    }
+
+    /// Splits precompile calldata into its components. Only the length is checked; the values
+    /// are validated when the proof is verified.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, KzgError> {
+        if bytes.len() != POINT_EVALUATION_INPUT_LENGTH {
+            return Err(KzgError::InvalidBytesLength(
+                "Invalid point evaluation input length".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            versioned_hash: Bytes32::from_slice(&bytes[..32])?,
+            z: Bytes32::from_slice(&bytes[32..64])?,
+            y: Bytes32::from_slice(&bytes[64..96])?,
+            commitment: Bytes48::from_slice(&bytes[96..144])?,
+            proof: Bytes48::from_slice(&bytes[144..])?,
+        })
+    }
+}
+
+/// Data returned by a successful point evaluation precompile call: the number of field elements
+/// per blob and the BLS scalar field modulus, both as 32-byte big-endian integers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PointEvaluationOutput {
+    pub field_elements_per_blob: [u8; 32],
+    pub bls_modulus: [u8; 32],
+}
+
+impl Default for PointEvaluationOutput {
+    /// Returns the output mandated by EIP-4844 for the mainnet preset
+    fn default() -> Self {
+        Self::new(NUM_FIELD_ELEMENTS_PER_BLOB)
+    }
+}
+
+impl PointEvaluationOutput {
+    /// Returns the output for blobs of `field_elements_per_blob` field elements
+    pub fn new(field_elements_per_blob: usize) -> Self {
+        let mut field_elements_per_blob_bytes = [0u8; 32];
+        field_elements_per_blob_bytes[24..]
+            .copy_from_slice(&(field_elements_per_blob as u64).to_be_bytes());
+
+        let mut bls_modulus = [0u8; 32];
+        for (chunk, limb) in bls_modulus.chunks_exact_mut(8).zip(MODULUS.iter().rev()) {
+            chunk.copy_from_slice(&limb.to_be_bytes());
+        }
+
+        Self {
+            field_elements_per_blob: field_elements_per_blob_bytes,
+            bls_modulus,
+        }
+    }
+
+    /// Returns the output for blobs committed to with `kzg_settings`, which is the default
+    /// output for the mainnet setup
+    pub fn for_settings(kzg_settings: &KzgSettings) -> Self {
+        Self::new(kzg_settings.field_elements_per_blob())
+    }
+
+    pub fn to_bytes(&self) -> [u8; POINT_EVALUATION_OUTPUT_LENGTH] {
+        let mut bytes = [0u8; POINT_EVALUATION_OUTPUT_LENGTH];
+        bytes[..32].copy_from_slice(&self.field_elements_per_blob);
+        bytes[32..].copy_from_slice(&self.bls_modulus);
+        bytes
+    }
+
+    /// Parses the data returned by the precompile
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, KzgError> {
+        if bytes.len() != POINT_EVALUATION_OUTPUT_LENGTH {
+            return Err(KzgError::InvalidBytesLength(
+                "Invalid point evaluation output length".to_string(),
+            ));
+        }
+
+        let mut output = Self {
+            field_elements_per_blob: [0u8; 32],
+            bls_modulus: [0u8; 32],
+        };
+        output.field_elements_per_blob.copy_from_slice(&bytes[..32]);
+        output.bls_modulus.copy_from_slice(&bytes[32..]);
+        Ok(output)
+    }
+
+    /// Returns `true` if the output matches the one mandated by EIP-4844
+    pub fn is_expected(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Executes the point evaluation precompile against the embedded trusted setup.
+///
+/// Returns the output bytes mandated by EIP-4844 if the commitment matches the versioned hash and
+/// the proof is valid. Every other outcome, including an invalid proof, is an error, which the EVM
+/// treats as a failed call.
+pub fn verify_point_evaluation(
+    input: &[u8; POINT_EVALUATION_INPUT_LENGTH],
+) -> Result<[u8; POINT_EVALUATION_OUTPUT_LENGTH], KzgError> {
+    verify_point_evaluation_with_settings(input, EnvKzgSettings::Default.get())
+}
+
+/// Same as [`verify_point_evaluation`] with a custom trusted setup. The output reports the
+/// number of field elements per blob of that setup, see [`PointEvaluationOutput::for_settings`].
+pub fn verify_point_evaluation_with_settings(
+    input: &[u8; POINT_EVALUATION_INPUT_LENGTH],
+    kzg_settings: &KzgSettings,
+) -> Result<[u8; POINT_EVALUATION_OUTPUT_LENGTH], KzgError> {
+    let input = PointEvaluationInput::from_bytes(input)?;
+
+    if kzg_to_versioned_hash(&input.commitment).as_slice() != input.versioned_hash.as_slice() {
+        return Err(KzgError::BadArgs(
+            "Versioned hash does not match the commitment".to_string(),
+        ));
+    }
+
+    let valid = KzgProof::verify_kzg_proof(
+        &input.commitment,
+        &input.z,
+        &input.y,
+        &input.proof,
+        kzg_settings,
+    )?;
+    if !valid {
+        return Err(KzgError::BadArgs(
+            "Point evaluation proof is invalid".to_string(),
+        ));
+    }
+
+    Ok(PointEvaluationOutput::for_settings(kzg_settings).to_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "prover")]
+    use crate::kzg_proof::tests::valid_kzg_proof_cases;
+
+    #[test]
+    fn test_point_evaluation_input_roundtrip() {
+        let bytes: [u8; POINT_EVALUATION_INPUT_LENGTH] = core::array::from_fn(|i| i as u8);
+        let input = PointEvaluationInput::from_bytes(&bytes).unwrap();
+        assert_eq!(input.z.as_slice(), &bytes[32..64]);
+        assert_eq!(input.proof.as_slice(), &bytes[144..]);
+        assert_eq!(input.to_bytes(), bytes);
+
+        assert!(PointEvaluationInput::from_bytes(&bytes[1..]).is_err());
+    }
+
+    #[test]
+    fn test_point_evaluation_output() {
+        let output = PointEvaluationOutput::default();
+        assert_eq!(
+            hex::encode(output.to_bytes()),
+            "0000000000000000000000000000000000000000000000000000000000001000\
+             73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001"
+        );
+        assert!(PointEvaluationOutput::from_bytes(&output.to_bytes())
+            .unwrap()
+            .is_expected());
+    }
+
+    #[cfg(feature = "prover")]
+    #[test]
+    fn test_verify_point_evaluation() {
+        for case in valid_kzg_proof_cases() {
+            let mut input = PointEvaluationInput {
+                versioned_hash: kzg_to_versioned_hash(&case.commitment),
+                z: case.z,
+                y: case.y,
+                commitment: case.commitment,
+                proof: case.proof,
+            };
+            let result = verify_point_evaluation(&input.to_bytes());
+            if case.output == Some(true) {
+                assert_eq!(result.unwrap(), PointEvaluationOutput::default().to_bytes());
+
+                let mut versioned_hash: [u8; 32] = input.versioned_hash.into();
+                versioned_hash[0] = 0x02;
+                input.versioned_hash = versioned_hash.into();
+                assert!(verify_point_evaluation(&input.to_bytes()).is_err());
+            } else {
+                assert!(result.is_err());
+            }
+        }
+    }
+
+    #[cfg(feature = "prover")]
+    #[test]
+    fn test_verify_point_evaluation_with_settings() {
+        use bls12_381::G1Affine;
+
+        // A constant polynomial commits to the generator times the constant under any setup, and
+        // its opening proof is the identity
+        let commitment = Bytes48::from(G1Affine::generator().to_compressed());
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        let input = PointEvaluationInput {
+            versioned_hash: kzg_to_versioned_hash(&commitment),
+            z: Bytes32::from([7u8; 32]),
+            y: Bytes32::from(one),
+            commitment,
+            proof: Bytes48::from(G1Affine::identity().to_compressed()),
+        };
+
+        let minimal = KzgSettings::minimal();
+        let output = verify_point_evaluation_with_settings(&input.to_bytes(), minimal).unwrap();
+        assert_eq!(
+            output,
+            PointEvaluationOutput::new(crate::trusted_setup::MINIMAL_FIELD_ELEMENTS_PER_BLOB)
+                .to_bytes()
+        );
+        assert!(!PointEvaluationOutput::from_bytes(&output)
+            .unwrap()
+            .is_expected());
+        assert_eq!(
+            verify_point_evaluation(&input.to_bytes()).unwrap(),
+            PointEvaluationOutput::default().to_bytes()
+        );
+    }
+}
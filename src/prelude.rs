@@ -0,0 +1,21 @@
+//! Commonly used items, meant to be glob-imported with `use kzg_rs::prelude::*;`
+
+pub use crate::domains::{DomainSeparators, Fork};
+pub use crate::dtypes::*;
+pub use crate::enums::KzgError;
+pub use crate::field::FieldElement;
+pub use crate::kzg_proof::KzgProof;
+pub use crate::limits::Limits;
+pub use crate::precompile::{PointEvaluationInput, PointEvaluationOutput};
+pub use crate::subgroup::SubgroupCheck;
+pub use crate::trusted_setup::{EnvKzgSettings, KzgSettings};
+pub use crate::utils::{kzg_to_versioned_hash, verify_versioned_hashes, MismatchAt};
+pub use crate::verifier::VerifierSettings;
+
+#[cfg(feature = "prover")]
+pub use crate::{
+    cancel::CancelToken,
+    polynomial::Polynomial,
+    progress::{PartialBatchResult, Progress},
+    prover::{LocalProver, ProofProvider},
+};
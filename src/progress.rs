@@ -0,0 +1,53 @@
+/// Progress of a long-running batch operation, reported after each processed chunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// Number of items processed so far
+    pub completed: usize,
+    /// Total number of items in the operation
+    pub total: usize,
+}
+
+impl Progress {
+    /// Returns the fraction of the operation that has completed, in `[0, 1]`
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            return 1.0;
+        }
+        self.completed as f64 / self.total as f64
+    }
+
+    /// Returns `true` once every item has been processed
+    pub fn is_done(&self) -> bool {
+        self.completed >= self.total
+    }
+}
+
+/// Outcome of a batch verification that may stop before every item has been processed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartialBatchResult {
+    /// Number of leading items that were verified
+    pub verified: usize,
+    /// Number of trailing items left unverified
+    pub remaining: usize,
+    /// Whether every verified item is valid
+    pub valid: bool,
+}
+
+impl PartialBatchResult {
+    /// Returns `true` if every item of the batch was verified
+    pub fn is_complete(&self) -> bool {
+        self.remaining == 0
+    }
+}
+
+/// Number of items processed between two progress reports
+#[cfg(feature = "parallel")]
+pub(crate) fn chunk_size() -> usize {
+    rayon::current_num_threads().max(1)
+}
+
+/// Number of items processed between two progress reports
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn chunk_size() -> usize {
+    1
+}
@@ -0,0 +1,108 @@
+//! Pluggable proof generation.
+//!
+//! [`ProofProvider`] abstracts over where commitments and proofs are computed, so integrators can
+//! swap the [`LocalProver`] for a remote or hardware-accelerated service while keeping this
+//! crate's types and verification.
+
+use crate::dtypes::*;
+use crate::enums::KzgError;
+use crate::kzg_proof::KzgProof;
+use crate::trusted_setup::KzgSettings;
+
+/// A source of KZG commitments and opening proofs
+pub trait ProofProvider {
+    /// Computes the commitment to `blob`
+    fn blob_to_kzg_commitment(
+        &self,
+        blob: &Blob,
+        kzg_settings: &KzgSettings,
+    ) -> Result<Bytes48, KzgError>;
+
+    /// Computes the proof for `blob` at its Fiat-Shamir challenge, as checked by
+    /// `verify_blob_kzg_proof`
+    fn compute_blob_kzg_proof(
+        &self,
+        blob: &Blob,
+        commitment_bytes: &Bytes48,
+        kzg_settings: &KzgSettings,
+    ) -> Result<Bytes48, KzgError>;
+
+    /// Computes the proof that `blob` evaluates to `y` at `z`
+    fn compute_kzg_proof(
+        &self,
+        blob: &Blob,
+        z_bytes: &Bytes32,
+        kzg_settings: &KzgSettings,
+    ) -> Result<ProofWithEvaluation, KzgError>;
+}
+
+/// Computes commitments and proofs in-process from the trusted setup
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalProver;
+
+impl ProofProvider for LocalProver {
+    fn blob_to_kzg_commitment(
+        &self,
+        blob: &Blob,
+        kzg_settings: &KzgSettings,
+    ) -> Result<Bytes48, KzgError> {
+        KzgProof::blob_to_kzg_commitment(blob, kzg_settings)
+    }
+
+    fn compute_blob_kzg_proof(
+        &self,
+        blob: &Blob,
+        commitment_bytes: &Bytes48,
+        kzg_settings: &KzgSettings,
+    ) -> Result<Bytes48, KzgError> {
+        KzgProof::compute_blob_kzg_proof(blob, commitment_bytes, kzg_settings)
+    }
+
+    fn compute_kzg_proof(
+        &self,
+        blob: &Blob,
+        z_bytes: &Bytes32,
+        kzg_settings: &KzgSettings,
+    ) -> Result<ProofWithEvaluation, KzgError> {
+        KzgProof::compute_kzg_proof(blob, z_bytes, kzg_settings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg_proof::scalar_to_bytes32;
+    use crate::kzg_proof::tests::valid_blob_cases;
+    use bls12_381::Scalar;
+
+    #[test]
+    fn test_local_prover() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let prover: &dyn ProofProvider = &LocalProver;
+
+        let (blobs, commitments, proofs) = valid_blob_cases();
+        for ((blob, commitment), proof) in blobs.iter().zip(&commitments).zip(&proofs) {
+            let computed = prover.blob_to_kzg_commitment(blob, &kzg_settings).unwrap();
+            assert_eq!(computed.as_slice(), commitment.as_slice());
+
+            let computed = prover
+                .compute_blob_kzg_proof(blob, commitment, &kzg_settings)
+                .unwrap();
+            assert_eq!(computed.as_slice(), proof.as_slice());
+
+            // Both a point outside the evaluation domain and one of the roots of unity
+            for z in [Scalar::from(12345), kzg_settings.roots_of_unity[7]] {
+                let z = scalar_to_bytes32(&z);
+                let opening = prover.compute_kzg_proof(blob, &z, &kzg_settings).unwrap();
+                assert!(KzgProof::verify_kzg_proof(
+                    commitment,
+                    &z,
+                    &opening.y,
+                    &opening.proof,
+                    &kzg_settings
+                )
+                .unwrap());
+            }
+        }
+    }
+}
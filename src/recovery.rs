@@ -0,0 +1,164 @@
+//! Erasure recovery of a polynomial from its evaluations on part of a domain.
+//!
+//! The domain is a coset `shift·H` of the roots of unity `H` of some power-of-two order `n`, so
+//! the cells of a blob are one instance but any code over such a domain can use it. Given the
+//! evaluations of a polynomial `P` of degree below `k` at any `k` or more of the `n` points, the
+//! missing ones are recovered as in the data availability sampling specs: with `Z` vanishing at
+//! the missing points and `E` the evaluations with zeros in their place, `E·Z` equals `P·Z` on
+//! the whole domain. Its coefficients are thus those of `P·Z`, and `P` is `(E·Z) / Z`, computed
+//! on a coset where `Z` has no roots.
+
+use crate::enums::KzgError;
+use crate::fft::{
+    coset_fft, coset_inverse_fft, expand_root_of_unity, fft, inverse_fft, scale_coefficients,
+};
+use crate::multiproof::vanishing_polynomial;
+use crate::utils::batch_inversion;
+use crate::PRIMITIVE_ROOT_OF_UNITY;
+
+use alloc::{string::ToString, vec::Vec};
+use bls12_381::Scalar;
+
+/// Recovers the coefficients of the polynomial of degree below `degree_bound` taking the given
+/// values on the roots of unity of order `n = evaluations.len()`, where `evaluations[i]` is the
+/// value at `ω^i` or `None` if it is missing. At least `degree_bound` values must be present.
+///
+/// Fails if the present values do not lie on a single polynomial of degree below
+/// `degree_bound`, which is only detected when more than `degree_bound` values are present.
+pub fn recover_polynomial(
+    evaluations: &[Option<Scalar>],
+    degree_bound: usize,
+) -> Result<Vec<Scalar>, KzgError> {
+    let n = evaluations.len();
+    if degree_bound == 0 {
+        return Err(KzgError::BadArgs(
+            "The degree bound must be positive".to_string(),
+        ));
+    }
+    let present = evaluations.iter().filter(|value| value.is_some()).count();
+    if degree_bound > n || present < degree_bound {
+        return Err(KzgError::BadArgs(format!(
+            "Recovering a polynomial of degree below {} needs as many of the {} evaluations, got {}",
+            degree_bound, n, present
+        )));
+    }
+
+    let roots = expand_root_of_unity(n)?;
+    let missing = evaluations
+        .iter()
+        .zip(&roots)
+        .filter(|(value, _)| value.is_none())
+        .map(|(_, root)| *root)
+        .collect::<Vec<_>>();
+    let mut vanishing = vanishing_polynomial(&missing);
+    vanishing.resize(n, Scalar::zero());
+
+    let extended = evaluations
+        .iter()
+        .map(|value| value.unwrap_or_else(Scalar::zero))
+        .collect::<Vec<_>>();
+    recover_with_vanishing(&extended, &vanishing, degree_bound)
+}
+
+/// Same as [`recover_polynomial`] on the coset `shift·H`, where `evaluations[i]` is the value at
+/// `shift·ω^i`. `shift` must not be zero.
+pub fn recover_polynomial_on_coset(
+    evaluations: &[Option<Scalar>],
+    shift: Scalar,
+    degree_bound: usize,
+) -> Result<Vec<Scalar>, KzgError> {
+    // The values are those of P(shift·X) on H, whose coefficients are P's scaled by shift^i
+    let shift_inv = Option::<Scalar>::from(shift.invert())
+        .ok_or_else(|| KzgError::BadArgs("The coset shift must not be zero".to_string()))?;
+    let scaled = recover_polynomial(evaluations, degree_bound)?;
+    Ok(scale_coefficients(&scaled, shift_inv))
+}
+
+/// Recovers the coefficients of the polynomial of degree below `degree_bound` whose evaluations
+/// on the roots of unity, in natural order, are `extended` except where the polynomial with
+/// coefficients `vanishing`, as many as there are evaluations, has its roots. `extended` is zero
+/// at those points.
+pub(crate) fn recover_with_vanishing(
+    extended: &[Scalar],
+    vanishing: &[Scalar],
+    degree_bound: usize,
+) -> Result<Vec<Scalar>, KzgError> {
+    let vanishing_evaluations = fft(vanishing)?;
+    let product = extended
+        .iter()
+        .zip(&vanishing_evaluations)
+        .map(|(e, z)| e * z)
+        .collect::<Vec<_>>();
+    let product_coefficients = inverse_fft(&product)?;
+
+    // Z only vanishes on the roots of unity, so not on this coset
+    let shift = Scalar::from(PRIMITIVE_ROOT_OF_UNITY);
+    let product_on_coset = coset_fft(&product_coefficients, shift)?;
+    let vanishing_on_coset = coset_fft(vanishing, shift)?;
+    let vanishing_inverse = batch_inversion(&vanishing_on_coset)?;
+    let quotient_on_coset = product_on_coset
+        .iter()
+        .zip(&vanishing_inverse)
+        .map(|(p, z)| p * z)
+        .collect::<Vec<_>>();
+    let mut coefficients = coset_inverse_fft(&quotient_on_coset, shift)?;
+
+    if coefficients[degree_bound..]
+        .iter()
+        .any(|coefficient| *coefficient != Scalar::zero())
+    {
+        return Err(KzgError::BadArgs(format!(
+            "The evaluations do not lie on a polynomial of degree below {}",
+            degree_bound
+        )));
+    }
+    coefficients.truncate(degree_bound);
+    Ok(coefficients)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recover_polynomial() {
+        let coefficients = (0..8u64)
+            .map(|i| Scalar::from(3 * i + 1))
+            .collect::<Vec<_>>();
+        let mut padded = coefficients.clone();
+        padded.resize(32, Scalar::zero());
+        let evaluations = fft(&padded).unwrap();
+
+        // Keep 8 of the 32 evaluations, scattered over the domain
+        let mut partial = evaluations.iter().map(|&v| Some(v)).collect::<Vec<_>>();
+        for (i, value) in partial.iter_mut().enumerate() {
+            if i % 4 != 1 {
+                *value = None;
+            }
+        }
+        assert_eq!(recover_polynomial(&partial, 8).unwrap(), coefficients);
+        partial[0] = Some(evaluations[0]);
+        assert_eq!(recover_polynomial(&partial, 8).unwrap(), coefficients);
+
+        // A corrupted value is caught once there is redundancy
+        partial[5] = Some(evaluations[5] + Scalar::one());
+        assert!(recover_polynomial(&partial, 8).is_err());
+        partial[2] = None;
+        partial[5] = None;
+        partial[9] = None;
+        assert!(recover_polynomial(&partial, 8).is_err());
+
+        let shift = Scalar::from(5);
+        let on_coset = coset_fft(&padded, shift)
+            .unwrap()
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| (i % 3 == 0).then_some(v))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            recover_polynomial_on_coset(&on_coset, shift, 8).unwrap(),
+            coefficients
+        );
+        assert!(recover_polynomial_on_coset(&on_coset, Scalar::zero(), 8).is_err());
+    }
+}
@@ -0,0 +1,117 @@
+//! Point evaluation precompile for revm.
+//!
+//! [`run`] has the signature of revm's standard precompiles, so the precompile can be registered
+//! with `PrecompileWithAddress(POINT_EVALUATION_ADDRESS, Precompile::Standard(kzg_rs::revm::run))`
+//! in place of the c-kzg implementation.
+
+use crate::kzg_proof::KzgProof;
+use crate::precompile::{
+    PointEvaluationInput, PointEvaluationOutput, POINT_EVALUATION_INPUT_LENGTH,
+};
+use crate::trusted_setup::{EnvKzgSettings, KzgSettings};
+use crate::utils::kzg_to_versioned_hash;
+
+use revm_primitives::{Address, Bytes, PrecompileError, PrecompileOutput, PrecompileResult};
+
+/// Address of the point evaluation precompile
+pub const POINT_EVALUATION_ADDRESS: Address = Address::with_last_byte(0x0a);
+
+/// Gas charged by the point evaluation precompile
+pub const GAS_COST: u64 = 50_000;
+
+/// Runs the point evaluation precompile against the embedded trusted setup
+pub fn run(input: &Bytes, gas_limit: u64) -> PrecompileResult {
+    run_with_settings(input, gas_limit, EnvKzgSettings::Default.get())
+}
+
+/// Same as [`run`] with a custom trusted setup, whose number of field elements per blob the
+/// output reports
+pub fn run_with_settings(
+    input: &Bytes,
+    gas_limit: u64,
+    kzg_settings: &KzgSettings,
+) -> PrecompileResult {
+    if gas_limit < GAS_COST {
+        return Err(PrecompileError::OutOfGas.into());
+    }
+    if input.len() != POINT_EVALUATION_INPUT_LENGTH {
+        return Err(PrecompileError::BlobInvalidInputLength.into());
+    }
+
+    let input = PointEvaluationInput::from_bytes(input)
+        .map_err(|_| PrecompileError::BlobInvalidInputLength)?;
+    if kzg_to_versioned_hash(&input.commitment).as_slice() != input.versioned_hash.as_slice() {
+        return Err(PrecompileError::BlobMismatchedVersion.into());
+    }
+
+    let valid = KzgProof::verify_kzg_proof(
+        &input.commitment,
+        &input.z,
+        &input.y,
+        &input.proof,
+        kzg_settings,
+    );
+    if !matches!(valid, Ok(true)) {
+        return Err(PrecompileError::BlobVerifyKzgProofFailed.into());
+    }
+
+    let output = PointEvaluationOutput::for_settings(kzg_settings).to_bytes();
+    Ok(PrecompileOutput::new(
+        GAS_COST,
+        Bytes::copy_from_slice(&output),
+    ))
+}
+
+#[cfg(all(test, feature = "prover"))]
+mod tests {
+    use super::*;
+    use crate::kzg_proof::tests::valid_kzg_proof_cases;
+    use revm_primitives::PrecompileErrors;
+
+    #[test]
+    fn test_revm_point_evaluation() {
+        let case = valid_kzg_proof_cases()
+            .into_iter()
+            .find(|case| case.output == Some(true))
+            .unwrap();
+        let mut input = PointEvaluationInput {
+            versioned_hash: kzg_to_versioned_hash(&case.commitment),
+            z: case.z,
+            y: case.y,
+            commitment: case.commitment,
+            proof: case.proof,
+        }
+        .to_bytes();
+
+        let output = run(&Bytes::copy_from_slice(&input), GAS_COST).unwrap();
+        assert_eq!(output.gas_used, GAS_COST);
+        assert_eq!(
+            output.bytes.as_ref(),
+            PointEvaluationOutput::default().to_bytes()
+        );
+
+        let error = |result: PrecompileResult| match result {
+            Err(PrecompileErrors::Error(e)) => e,
+            other => panic!("unexpected result {:?}", other),
+        };
+        assert_eq!(
+            error(run(&Bytes::copy_from_slice(&input), GAS_COST - 1)),
+            PrecompileError::OutOfGas
+        );
+        assert_eq!(
+            error(run(&Bytes::copy_from_slice(&input[1..]), GAS_COST)),
+            PrecompileError::BlobInvalidInputLength
+        );
+        // Flip the y value
+        input[95] ^= 1;
+        assert_eq!(
+            error(run(&Bytes::copy_from_slice(&input), GAS_COST)),
+            PrecompileError::BlobVerifyKzgProofFailed
+        );
+        input[0] = 0x02;
+        assert_eq!(
+            error(run(&Bytes::copy_from_slice(&input), GAS_COST)),
+            PrecompileError::BlobMismatchedVersion
+        );
+    }
+}
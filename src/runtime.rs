@@ -0,0 +1,374 @@
+//! Blob operations sized by the trusted setup at runtime.
+//!
+//! The [`KzgProof`](crate::KzgProof) blob APIs take fixed-size [`Blob`](crate::Blob)s of
+//! `NUM_FIELD_ELEMENTS_PER_BLOB` field elements. These functions take the blob as a byte slice
+//! and accept any setup, such as one from [`KzgSettings::subset`], as long as the blob holds one
+//! field element per point of the setup. With the mainnet setup they produce the same results as
+//! the fixed-size APIs.
+//!
+//! With [`KzgSettings::minimal`] they implement the `minimal` preset of the consensus specs,
+//! whose blobs hold [`MINIMAL_FIELD_ELEMENTS_PER_BLOB`](crate::MINIMAL_FIELD_ELEMENTS_PER_BLOB)
+//! field elements.
+//!
+//! [`commit_to_scalars`] and its opening functions do the same for data that is not shaped like a
+//! blob, such as state diffs or witness vectors, taking the values as scalars and padding them
+//! with zeros up to the size of the setup.
+
+use crate::dtypes::*;
+use crate::enums::KzgError;
+use crate::kzg_proof::{
+    compute_challenge_for_bytes, compute_kzg_proof_impl, polynomial_to_commitment,
+    safe_scalar_affine_from_bytes, scalar_to_bytes32, validate_batched_input,
+    verify_kzg_proof_batch_impl, verify_kzg_proof_impl,
+};
+use crate::polynomial::Polynomial;
+use crate::trusted_setup::KzgSettings;
+use crate::{BYTES_PER_COMMITMENT, BYTES_PER_PROOF};
+
+use alloc::{string::ToString, vec::Vec};
+use bls12_381::{G1Affine, Scalar};
+
+/// Parses `blob` into a polynomial in evaluation form over the domain of `kzg_settings`
+fn blob_to_polynomial(blob: &[u8], kzg_settings: &KzgSettings) -> Result<Polynomial, KzgError> {
+    if blob.len() != kzg_settings.bytes_per_blob() {
+        return Err(KzgError::InvalidBytesLength(format!(
+            "The blob is {} bytes, but the trusted setup expects {}",
+            blob.len(),
+            kzg_settings.bytes_per_blob()
+        )));
+    }
+
+    bytes_to_scalars(blob).map(Polynomial::Evaluations)
+}
+
+fn commit(polynomial: &Polynomial, kzg_settings: &KzgSettings) -> Result<Bytes48, KzgError> {
+    let commitment = polynomial_to_commitment(polynomial, kzg_settings)?;
+    Ok(Bytes48::from(commitment.to_compressed()))
+}
+
+/// Computes the commitment to `blob`
+pub fn blob_to_kzg_commitment(
+    blob: &[u8],
+    kzg_settings: &KzgSettings,
+) -> Result<Bytes48, KzgError> {
+    commit(&blob_to_polynomial(blob, kzg_settings)?, kzg_settings)
+}
+
+/// Computes the proof for `blob` at its Fiat-Shamir challenge
+pub fn compute_blob_kzg_proof(
+    blob: &[u8],
+    commitment_bytes: &Bytes48,
+    kzg_settings: &KzgSettings,
+) -> Result<Bytes48, KzgError> {
+    let commitment = kzg_settings
+        .subgroup_check
+        .g1_from_bytes(commitment_bytes)?;
+    let polynomial = blob_to_polynomial(blob, kzg_settings)?;
+    let evaluation_challenge = compute_challenge_for_bytes(
+        blob,
+        &commitment,
+        &kzg_settings.domain_separators.blob_proof,
+    );
+    let (proof, _) = compute_kzg_proof_impl(&polynomial, evaluation_challenge, kzg_settings)?;
+    Ok(Bytes48::from(proof.to_compressed()))
+}
+
+/// Computes the proof that `blob` evaluates to `y` at `z_bytes`
+pub fn compute_kzg_proof(
+    blob: &[u8],
+    z_bytes: &Bytes32,
+    kzg_settings: &KzgSettings,
+) -> Result<ProofWithEvaluation, KzgError> {
+    let polynomial = blob_to_polynomial(blob, kzg_settings)?;
+    let z = safe_scalar_affine_from_bytes(z_bytes)?;
+    let (proof, y) = compute_kzg_proof_impl(&polynomial, z, kzg_settings)?;
+    Ok(ProofWithEvaluation {
+        proof: Bytes48::from(proof.to_compressed()),
+        y: scalar_to_bytes32(&y),
+    })
+}
+
+/// Parses a blob opening and evaluates the blob at its challenge, returning the commitment,
+/// evaluation point, evaluation and proof
+fn prepare_blob_opening(
+    blob: &[u8],
+    commitment_bytes: &Bytes48,
+    proof_bytes: &Bytes48,
+    kzg_settings: &KzgSettings,
+) -> Result<(G1Affine, Scalar, Scalar, G1Affine), KzgError> {
+    let commitment = kzg_settings
+        .subgroup_check
+        .g1_from_bytes(commitment_bytes)?;
+    let proof = kzg_settings.subgroup_check.g1_from_bytes(proof_bytes)?;
+    let polynomial = blob_to_polynomial(blob, kzg_settings)?;
+    let evaluation_challenge = compute_challenge_for_bytes(
+        blob,
+        &commitment,
+        &kzg_settings.domain_separators.blob_proof,
+    );
+    let y = polynomial.evaluate(evaluation_challenge, kzg_settings)?;
+    Ok((commitment, evaluation_challenge, y, proof))
+}
+
+/// Verifies a proof from [`compute_blob_kzg_proof`]
+pub fn verify_blob_kzg_proof(
+    blob: &[u8],
+    commitment_bytes: &Bytes48,
+    proof_bytes: &Bytes48,
+    kzg_settings: &KzgSettings,
+) -> Result<bool, KzgError> {
+    let (commitment, z, y, proof) =
+        prepare_blob_opening(blob, commitment_bytes, proof_bytes, kzg_settings)?;
+    verify_kzg_proof_impl(commitment, z, y, proof, kzg_settings)
+}
+
+/// Verifies proofs from [`compute_blob_kzg_proof`] with a single pairing check. An empty batch
+/// is valid.
+pub fn verify_blob_kzg_proof_batch(
+    blobs: &[&[u8]],
+    commitments_bytes: &[Bytes48],
+    proofs_bytes: &[Bytes48],
+    kzg_settings: &KzgSettings,
+) -> Result<bool, KzgError> {
+    if blobs.len() != commitments_bytes.len() || blobs.len() != proofs_bytes.len() {
+        return Err(KzgError::InvalidBytesLength(
+            "The batch inputs have different lengths".to_string(),
+        ));
+    }
+    let total_bytes = blobs
+        .iter()
+        .fold(0usize, |total, blob| total.saturating_add(blob.len()))
+        .saturating_add(
+            blobs
+                .len()
+                .saturating_mul(BYTES_PER_COMMITMENT + BYTES_PER_PROOF),
+        );
+    kzg_settings.limits.check_blobs(blobs.len(), total_bytes)?;
+    if blobs.is_empty() {
+        return Ok(true);
+    }
+
+    let mut commitments = Vec::with_capacity(blobs.len());
+    let mut zs = Vec::with_capacity(blobs.len());
+    let mut ys = Vec::with_capacity(blobs.len());
+    let mut proofs = Vec::with_capacity(blobs.len());
+    for ((blob, commitment_bytes), proof_bytes) in
+        blobs.iter().zip(commitments_bytes).zip(proofs_bytes)
+    {
+        let (commitment, z, y, proof) =
+            prepare_blob_opening(blob, commitment_bytes, proof_bytes, kzg_settings)?;
+        commitments.push(commitment);
+        zs.push(z);
+        ys.push(y);
+        proofs.push(proof);
+    }
+    validate_batched_input(&commitments, &proofs)?;
+    verify_kzg_proof_batch_impl(&commitments, &zs, &ys, &proofs, kzg_settings)
+}
+
+/// Pads `scalars` with zeros to the size of the setup
+fn pad_scalars(scalars: &[Scalar], kzg_settings: &KzgSettings) -> Result<Polynomial, KzgError> {
+    let n = kzg_settings.field_elements_per_blob();
+    if scalars.len() > n {
+        return Err(KzgError::InvalidBytesLength(format!(
+            "Got {} scalars, but the trusted setup holds at most {}",
+            scalars.len(),
+            n
+        )));
+    }
+    let mut polynomial = scalars.to_vec();
+    polynomial.resize(n, Scalar::zero());
+    Ok(Polynomial::Evaluations(polynomial))
+}
+
+/// Computes the commitment to the polynomial taking the value `scalars[i]` at
+/// `kzg_settings.roots_of_unity[i]` and zero at the remaining roots. With as many scalars as
+/// setup points, this is the commitment to the blob holding them.
+pub fn commit_to_scalars(
+    scalars: &[Scalar],
+    kzg_settings: &KzgSettings,
+) -> Result<Bytes48, KzgError> {
+    commit(&pad_scalars(scalars, kzg_settings)?, kzg_settings)
+}
+
+/// Computes the proof that the polynomial committed to by [`commit_to_scalars`] evaluates to `y`
+/// at `z`. Opening at `kzg_settings.roots_of_unity[i]` proves the value of `scalars[i]`.
+pub fn compute_scalars_proof(
+    scalars: &[Scalar],
+    z: &Scalar,
+    kzg_settings: &KzgSettings,
+) -> Result<ProofWithEvaluation, KzgError> {
+    let polynomial = pad_scalars(scalars, kzg_settings)?;
+    let (proof, y) = compute_kzg_proof_impl(&polynomial, *z, kzg_settings)?;
+    Ok(ProofWithEvaluation {
+        proof: Bytes48::from(proof.to_compressed()),
+        y: scalar_to_bytes32(&y),
+    })
+}
+
+/// Verifies an opening from [`compute_scalars_proof`] against a commitment from
+/// [`commit_to_scalars`]
+pub fn verify_scalars_proof(
+    commitment_bytes: &Bytes48,
+    z: &Scalar,
+    opening: &ProofWithEvaluation,
+    kzg_settings: &KzgSettings,
+) -> Result<bool, KzgError> {
+    let commitment = kzg_settings
+        .subgroup_check
+        .g1_from_bytes(commitment_bytes)?;
+    let proof = kzg_settings.subgroup_check.g1_from_bytes(&opening.proof)?;
+    let y = safe_scalar_affine_from_bytes(&opening.y)?;
+    verify_kzg_proof_impl(commitment, *z, y, proof, kzg_settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg_proof::tests::valid_blob_cases;
+    use crate::{KzgProof, MINIMAL_FIELD_ELEMENTS_PER_BLOB, MINIMAL_SETUP_SECRET};
+    use bls12_381::{G2Affine, G2Projective};
+
+    #[test]
+    fn test_runtime_blob_kzg_proof() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let (blobs, commitments, proofs) = valid_blob_cases();
+
+        for ((blob, commitment), proof) in blobs.iter().zip(&commitments).zip(&proofs) {
+            assert!(
+                verify_blob_kzg_proof(blob.as_slice(), commitment, proof, &kzg_settings).unwrap()
+            );
+            assert_eq!(
+                blob_to_kzg_commitment(blob.as_slice(), &kzg_settings)
+                    .unwrap()
+                    .as_slice(),
+                commitment.as_slice()
+            );
+            assert_eq!(
+                compute_blob_kzg_proof(blob.as_slice(), commitment, &kzg_settings)
+                    .unwrap()
+                    .as_slice(),
+                proof.as_slice()
+            );
+        }
+
+        let blobs = blobs.iter().map(Blob::as_slice).collect::<Vec<_>>();
+        assert!(verify_blob_kzg_proof_batch(&blobs, &commitments, &proofs, &kzg_settings).unwrap());
+        let mut swapped = proofs.clone();
+        swapped.swap(0, 1);
+        assert!(
+            !verify_blob_kzg_proof(blobs[0], &commitments[0], &swapped[0], &kzg_settings).unwrap()
+        );
+        assert!(
+            !verify_blob_kzg_proof_batch(&blobs, &commitments, &swapped, &kzg_settings).unwrap()
+        );
+
+        assert!(blob_to_kzg_commitment(&[0u8; 64], &kzg_settings).is_err());
+    }
+
+    #[test]
+    fn test_commit_to_scalars() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let (blobs, commitments, _) = valid_blob_cases();
+
+        // A full-length vector commits like the blob holding it
+        let scalars = blobs[1].as_polynomial().unwrap();
+        assert_eq!(
+            commit_to_scalars(&scalars, &kzg_settings)
+                .unwrap()
+                .as_slice(),
+            commitments[1].as_slice()
+        );
+
+        // A short vector is padded with zeros
+        let short = [Scalar::from(7), Scalar::from(11), Scalar::from(13)];
+        let mut padded = short.to_vec();
+        padded.resize(kzg_settings.g1_points.len(), Scalar::zero());
+        let commitment = commit_to_scalars(&short, &kzg_settings).unwrap();
+        assert_eq!(
+            commitment.as_slice(),
+            commit_to_scalars(&padded, &kzg_settings)
+                .unwrap()
+                .as_slice()
+        );
+
+        // Opening at the i-th root of unity proves the i-th value
+        let z = kzg_settings.roots_of_unity[1];
+        let opening = compute_scalars_proof(&short, &z, &kzg_settings).unwrap();
+        assert_eq!(
+            opening.y.as_slice(),
+            scalar_to_bytes32(&short[1]).as_slice()
+        );
+        assert!(verify_scalars_proof(&commitment, &z, &opening, &kzg_settings).unwrap());
+
+        let z = Scalar::from(12345);
+        let mut opening = compute_scalars_proof(&short, &z, &kzg_settings).unwrap();
+        assert!(verify_scalars_proof(&commitment, &z, &opening, &kzg_settings).unwrap());
+        opening.y = scalar_to_bytes32(&Scalar::from(1));
+        assert!(!verify_scalars_proof(&commitment, &z, &opening, &kzg_settings).unwrap());
+
+        assert!(commit_to_scalars(&vec![Scalar::one(); 4097], &kzg_settings).is_err());
+    }
+
+    #[test]
+    fn test_minimal_preset() {
+        let kzg_settings = KzgSettings::minimal();
+        assert_eq!(
+            kzg_settings.g1_points.len(),
+            MINIMAL_FIELD_ELEMENTS_PER_BLOB
+        );
+        assert_eq!(
+            kzg_settings.g2_points[1],
+            G2Affine::from(G2Projective::generator() * Scalar::from(MINIMAL_SETUP_SECRET))
+        );
+        kzg_settings.verify().unwrap();
+
+        let blobs = (0..3u64)
+            .map(|i| {
+                (0..MINIMAL_FIELD_ELEMENTS_PER_BLOB as u64)
+                    .flat_map(|j| <[u8; 32]>::from(scalar_to_bytes32(&-Scalar::from(i * 10 + j))))
+                    .collect::<Vec<u8>>()
+            })
+            .collect::<Vec<_>>();
+        let blobs = blobs.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        let commitments = blobs
+            .iter()
+            .map(|blob| blob_to_kzg_commitment(blob, kzg_settings).unwrap())
+            .collect::<Vec<_>>();
+        let proofs = blobs
+            .iter()
+            .zip(&commitments)
+            .map(|(blob, commitment)| {
+                compute_blob_kzg_proof(blob, commitment, kzg_settings).unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        for ((blob, commitment), proof) in blobs.iter().zip(&commitments).zip(&proofs) {
+            assert!(verify_blob_kzg_proof(blob, commitment, proof, kzg_settings).unwrap());
+        }
+        assert!(verify_blob_kzg_proof_batch(&blobs, &commitments, &proofs, kzg_settings).unwrap());
+        assert!(verify_blob_kzg_proof_batch(&[], &[], &[], kzg_settings).unwrap());
+        let mut swapped = proofs.clone();
+        swapped.swap(0, 1);
+        assert!(
+            !verify_blob_kzg_proof_batch(&blobs, &commitments, &swapped, kzg_settings).unwrap()
+        );
+        assert!(
+            verify_blob_kzg_proof_batch(&blobs, &commitments[..2], &proofs, kzg_settings).is_err()
+        );
+
+        let z = scalar_to_bytes32(&Scalar::from(99));
+        let opening = compute_kzg_proof(blobs[0], &z, kzg_settings).unwrap();
+        assert!(KzgProof::verify_kzg_proof(
+            &commitments[0],
+            &z,
+            &opening.y,
+            &opening.proof,
+            kzg_settings
+        )
+        .unwrap());
+
+        // Mainnet-sized blobs do not fit the minimal setup
+        assert!(blob_to_kzg_commitment(&[0u8; crate::BYTES_PER_BLOB], kzg_settings).is_err());
+    }
+}
@@ -0,0 +1,166 @@
+//! Compact binary format of a parsed trusted setup, which loads without the square roots of
+//! point decompression.
+//!
+//! The format starts with a 24-byte header:
+//!
+//! ```text
+//! magic "KZGSETUP" (8) | version u16 BE (2) | reserved (2) | G1 count u32 BE (4)
+//!     | G2 count u32 BE (4) | G1 monomial count u32 BE (4)
+//! ```
+//!
+//! followed by the uncompressed G1 Lagrange points in the bit-reversed order of
+//! [`KzgSettings::g1_points`], the uncompressed G2 points and the uncompressed G1 monomial
+//! points, of which there may be none. The roots of unity are not stored, since deriving them
+//! takes one multiplication each.
+
+use crate::enums::KzgError;
+use crate::fft::bit_reversal_permutation;
+use crate::subgroup::SubgroupCheck;
+use crate::trusted_setup::KzgSettings;
+
+use alloc::{string::ToString, vec::Vec};
+use bls12_381::{G1Affine, G2Affine};
+
+pub const SETTINGS_MAGIC: [u8; 8] = *b"KZGSETUP";
+pub const SETTINGS_VERSION: u16 = 1;
+pub const SETTINGS_HEADER_SIZE: usize = 24;
+
+const BYTES_PER_UNCOMPRESSED_G1_POINT: usize = 96;
+const BYTES_PER_UNCOMPRESSED_G2_POINT: usize = 192;
+
+impl KzgSettings {
+    /// Serializes the setup points in the binary format described in [`crate::serialization`].
+    /// The subgroup check, domain separators, limits and precomputed tables are not included.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(
+            SETTINGS_HEADER_SIZE
+                + (self.g1_points.len() + self.g1_monomial_points.len())
+                    * BYTES_PER_UNCOMPRESSED_G1_POINT
+                + self.g2_points.len() * BYTES_PER_UNCOMPRESSED_G2_POINT,
+        );
+        bytes.extend_from_slice(&SETTINGS_MAGIC);
+        bytes.extend_from_slice(&SETTINGS_VERSION.to_be_bytes());
+        bytes.extend_from_slice(&[0; 2]);
+        for count in [
+            self.g1_points.len(),
+            self.g2_points.len(),
+            self.g1_monomial_points.len(),
+        ] {
+            bytes.extend_from_slice(&(count as u32).to_be_bytes());
+        }
+
+        for point in self.g1_points {
+            bytes.extend_from_slice(&point.to_uncompressed());
+        }
+        for point in self.g2_points {
+            bytes.extend_from_slice(&point.to_uncompressed());
+        }
+        for point in self.g1_monomial_points {
+            bytes.extend_from_slice(&point.to_uncompressed());
+        }
+        bytes
+    }
+
+    /// Deserializes settings written by [`KzgSettings::to_bytes`], checking that every point is
+    /// on the curve and in the prime-order subgroup.
+    ///
+    /// The points are leaked, see [`KzgSettings`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, KzgError> {
+        Self::from_bytes_with_subgroup_check(bytes, SubgroupCheck::default())
+    }
+
+    /// Same as [`KzgSettings::from_bytes`], checking the subgroup membership of the points with
+    /// `subgroup_check`. [`SubgroupCheck::Skip`], which leaves only the cheap checks that the
+    /// points are on the curve, is only appropriate for bytes whose integrity is otherwise
+    /// established, for example because this process wrote them.
+    pub fn from_bytes_with_subgroup_check(
+        bytes: &[u8],
+        subgroup_check: SubgroupCheck,
+    ) -> Result<Self, KzgError> {
+        if bytes.len() < SETTINGS_HEADER_SIZE || bytes[..8] != SETTINGS_MAGIC {
+            return Err(KzgError::InvalidTrustedSetup(
+                "Not a serialized trusted setup".to_string(),
+            ));
+        }
+        let version = u16::from_be_bytes([bytes[8], bytes[9]]);
+        if version != SETTINGS_VERSION {
+            return Err(KzgError::InvalidTrustedSetup(format!(
+                "Unsupported serialized trusted setup version {}",
+                version
+            )));
+        }
+        let count = |offset: usize| {
+            u32::from_be_bytes(bytes[offset..offset + 4].try_into().unwrap()) as usize
+        };
+        let (num_g1, num_g2, num_monomial) = (count(12), count(16), count(20));
+
+        let expected = (num_g1 + num_monomial)
+            .checked_mul(BYTES_PER_UNCOMPRESSED_G1_POINT)
+            .zip(num_g2.checked_mul(BYTES_PER_UNCOMPRESSED_G2_POINT))
+            .and_then(|(g1, g2)| g1.checked_add(g2)?.checked_add(SETTINGS_HEADER_SIZE));
+        if expected != Some(bytes.len()) {
+            return Err(KzgError::InvalidTrustedSetup(
+                "The serialized trusted setup length does not match its point counts".to_string(),
+            ));
+        }
+
+        let (g1_bytes, rest) =
+            bytes[SETTINGS_HEADER_SIZE..].split_at(num_g1 * BYTES_PER_UNCOMPRESSED_G1_POINT);
+        let (g2_bytes, monomial_bytes) = rest.split_at(num_g2 * BYTES_PER_UNCOMPRESSED_G2_POINT);
+        let g1_points = decode_g1(g1_bytes, subgroup_check)?;
+        let g2_points = g2_bytes
+            .chunks_exact(BYTES_PER_UNCOMPRESSED_G2_POINT)
+            .map(|chunk| subgroup_check.g2_from_uncompressed(chunk.try_into().unwrap()))
+            .collect::<Result<Vec<G2Affine>, _>>()?;
+        let g1_monomial = decode_g1(monomial_bytes, subgroup_check)?;
+
+        // The Lagrange points are stored in bit-reversed order, and the permutation is its own
+        // inverse
+        Self::from_points(bit_reversal_permutation(&g1_points), g1_monomial, g2_points)
+    }
+}
+
+fn decode_g1(bytes: &[u8], subgroup_check: SubgroupCheck) -> Result<Vec<G1Affine>, KzgError> {
+    bytes
+        .chunks_exact(BYTES_PER_UNCOMPRESSED_G1_POINT)
+        .map(|chunk| subgroup_check.g1_from_uncompressed(chunk.try_into().unwrap()))
+        .collect()
+}
+
+#[cfg(all(test, feature = "prover"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_bytes() {
+        let settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let bytes = settings.to_bytes();
+        assert_eq!(
+            bytes.len(),
+            SETTINGS_HEADER_SIZE + 4096 * BYTES_PER_UNCOMPRESSED_G1_POINT + 65 * 192
+        );
+        assert_eq!(KzgSettings::from_bytes(&bytes).unwrap(), settings);
+        assert_eq!(
+            KzgSettings::from_bytes_with_subgroup_check(&bytes, SubgroupCheck::Skip).unwrap(),
+            settings
+        );
+
+        // The monomial points round-trip when present
+        let subset = settings.subset(8).unwrap();
+        assert_eq!(KzgSettings::from_bytes(&subset.to_bytes()).unwrap(), subset);
+
+        let mut invalid = bytes.clone();
+        invalid[0] = b'X';
+        assert!(KzgSettings::from_bytes(&invalid).is_err());
+        let mut invalid = bytes.clone();
+        invalid[9] = 2;
+        assert!(KzgSettings::from_bytes(&invalid).is_err());
+        assert!(KzgSettings::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        // A coordinate changed, so the point is no longer on the curve
+        let mut invalid = bytes;
+        invalid[SETTINGS_HEADER_SIZE + 95] ^= 1;
+        assert!(
+            KzgSettings::from_bytes_with_subgroup_check(&invalid, SubgroupCheck::Skip).is_err()
+        );
+    }
+}
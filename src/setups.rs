@@ -0,0 +1,116 @@
+//! Registry of named trusted setups, for services that serve several networks and select the
+//! setup per request.
+//!
+//! The registry starts with the built-in setups, [`Setups::MAINNET`] and, with the `prover`
+//! feature, the testing setup of the [`Setups::MINIMAL`] preset. Custom setups are added with
+//! [`Setups::register`] and live for the rest of the process, so lookups hand out `&'static`
+//! settings without holding a lock.
+
+use crate::enums::KzgError;
+use crate::trusted_setup::KzgSettings;
+
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use spin::{Mutex, Once};
+
+/// Setups added with [`Setups::register`]
+static REGISTERED: Mutex<Vec<(String, &'static KzgSettings)>> = Mutex::new(Vec::new());
+
+/// The process-wide registry of named trusted setups
+pub struct Setups;
+
+impl Setups {
+    /// Name of the mainnet setup of the Ethereum KZG ceremony
+    pub const MAINNET: &'static str = "mainnet";
+    /// Name of the testing setup of the `minimal` preset, see [`KzgSettings::minimal`]
+    #[cfg(feature = "prover")]
+    pub const MINIMAL: &'static str = "minimal";
+
+    /// Returns the setup registered as `name`, ignoring case
+    pub fn get(name: &str) -> Option<&'static KzgSettings> {
+        if name.eq_ignore_ascii_case(Self::MAINNET) {
+            static MAINNET: Once<KzgSettings> = Once::new();
+            return Some(MAINNET.call_once(|| {
+                KzgSettings::load_trusted_setup_file()
+                    .expect("failed to load default trusted setup")
+            }));
+        }
+        #[cfg(feature = "prover")]
+        if name.eq_ignore_ascii_case(Self::MINIMAL) {
+            return Some(KzgSettings::minimal());
+        }
+
+        REGISTERED
+            .lock()
+            .iter()
+            .find(|(registered, _)| registered.eq_ignore_ascii_case(name))
+            .map(|(_, settings)| *settings)
+    }
+
+    /// Registers `settings` as `name`, returning the registered settings. Fails if the name is
+    /// taken, including by a built-in setup.
+    pub fn register(name: &str, settings: KzgSettings) -> Result<&'static KzgSettings, KzgError> {
+        let taken =
+            || KzgError::BadArgs(format!("A trusted setup is already registered as {}", name));
+        if Self::builtin().any(|builtin| builtin.eq_ignore_ascii_case(name)) {
+            return Err(taken());
+        }
+
+        let mut registered = REGISTERED.lock();
+        if registered
+            .iter()
+            .any(|(registered, _)| registered.eq_ignore_ascii_case(name))
+        {
+            return Err(taken());
+        }
+        let settings: &'static KzgSettings = Box::leak(Box::new(settings));
+        registered.push((name.to_string(), settings));
+        Ok(settings)
+    }
+
+    /// Returns the names of every setup, built-in ones first
+    pub fn names() -> Vec<String> {
+        Self::builtin()
+            .map(ToString::to_string)
+            .chain(REGISTERED.lock().iter().map(|(name, _)| name.clone()))
+            .collect()
+    }
+
+    fn builtin() -> impl Iterator<Item = &'static str> {
+        [
+            Self::MAINNET,
+            #[cfg(feature = "prover")]
+            Self::MINIMAL,
+        ]
+        .into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subgroup::SubgroupCheck;
+
+    #[test]
+    fn test_setups() {
+        let mainnet = Setups::get("mainnet").unwrap();
+        assert_eq!(*mainnet, KzgSettings::load_trusted_setup_file().unwrap());
+        assert!(core::ptr::eq(mainnet, Setups::get("Mainnet").unwrap()));
+        assert!(Setups::get("devnet-test").is_none());
+
+        let custom = mainnet.clone().with_subgroup_check(SubgroupCheck::Skip);
+        let registered = Setups::register("devnet-test", custom.clone()).unwrap();
+        assert_eq!(*registered, custom);
+        assert!(core::ptr::eq(
+            registered,
+            Setups::get("devnet-test").unwrap()
+        ));
+        assert!(Setups::names().contains(&"devnet-test".to_string()));
+
+        assert!(Setups::register("DEVNET-TEST", custom.clone()).is_err());
+        assert!(Setups::register(Setups::MAINNET, custom).is_err());
+    }
+}
@@ -0,0 +1,171 @@
+//! Subgroup membership checks applied when parsing compressed curve points.
+
+use crate::compression::decompress_g1_batch;
+use crate::enums::KzgError;
+use crate::{dtypes::Bytes48, MODULUS};
+
+use alloc::{string::ToString, vec::Vec};
+use bls12_381::{G1Affine, G1Projective, G2Affine, G2Projective};
+use core::ops::Add;
+
+/// How compressed G1 and G2 points are checked to lie in the prime-order subgroup.
+///
+/// Every strategy checks that the point is on the curve; they differ only in the subgroup check,
+/// which dominates the cost of parsing a point.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SubgroupCheck {
+    /// Multiplies the point by the group order and checks for the identity. The slowest
+    /// strategy, useful as a reference.
+    Full,
+    /// Uses the curve endomorphisms to check torsion freeness, as `bls12_381` does when decoding
+    /// points. Equivalent to [`SubgroupCheck::Full`] and considerably faster.
+    #[default]
+    Endomorphism,
+    /// Skips the subgroup check. Only sound for inputs that were already validated, for example
+    /// points produced by this crate or checked when they entered the system.
+    Skip,
+}
+
+impl SubgroupCheck {
+    /// Parses a compressed G1 point, checking subgroup membership with this strategy
+    pub fn g1_from_bytes(self, bytes: &Bytes48) -> Result<G1Affine, KzgError> {
+        self.g1_from_compressed(&bytes.clone().into())
+    }
+
+    /// Parses a compressed G1 point, checking subgroup membership with this strategy
+    pub fn g1_from_compressed(self, bytes: &[u8; 48]) -> Result<G1Affine, KzgError> {
+        let point = Option::<G1Affine>::from(G1Affine::from_compressed_unchecked(bytes))
+            .ok_or_else(|| KzgError::BadArgs("Failed to parse G1Affine from bytes".to_string()))?;
+        self.check_g1(point)
+    }
+
+    /// Parses an uncompressed G1 point, checking that it is on the curve and its subgroup
+    /// membership with this strategy
+    pub fn g1_from_uncompressed(self, bytes: &[u8; 96]) -> Result<G1Affine, KzgError> {
+        let point = Option::<G1Affine>::from(G1Affine::from_uncompressed_unchecked(bytes))
+            .filter(|point| bool::from(point.is_on_curve()))
+            .ok_or_else(|| KzgError::BadArgs("Failed to parse G1Affine from bytes".to_string()))?;
+        self.check_g1(point)
+    }
+
+    fn check_g1(self, point: G1Affine) -> Result<G1Affine, KzgError> {
+        let in_subgroup = match self {
+            Self::Full => {
+                let point = G1Projective::from(point);
+                bool::from(mul_by_group_order(point, G1Projective::identity()).is_identity())
+            }
+            Self::Endomorphism => bool::from(point.is_torsion_free()),
+            Self::Skip => true,
+        };
+        if !in_subgroup {
+            return Err(KzgError::BadArgs(
+                "G1 point is not in the prime-order subgroup".to_string(),
+            ));
+        }
+        Ok(point)
+    }
+
+    /// Parses a buffer of compressed G1 points written by [`Bytes48::to_contiguous`], checking
+    /// every point with this strategy
+    pub fn g1_from_contiguous(self, buffer: &[u8]) -> Result<Vec<G1Affine>, KzgError> {
+        decompress_g1_batch(Bytes48::from_contiguous(buffer)?, self)
+    }
+
+    /// Parses a compressed G2 point, checking subgroup membership with this strategy
+    pub fn g2_from_compressed(self, bytes: &[u8; 96]) -> Result<G2Affine, KzgError> {
+        let point = Option::<G2Affine>::from(G2Affine::from_compressed_unchecked(bytes))
+            .ok_or_else(|| KzgError::BadArgs("Failed to parse G2Affine from bytes".to_string()))?;
+        self.check_g2(point)
+    }
+
+    /// Parses an uncompressed G2 point, checking that it is on the curve and its subgroup
+    /// membership with this strategy
+    pub fn g2_from_uncompressed(self, bytes: &[u8; 192]) -> Result<G2Affine, KzgError> {
+        let point = Option::<G2Affine>::from(G2Affine::from_uncompressed_unchecked(bytes))
+            .filter(|point| bool::from(point.is_on_curve()))
+            .ok_or_else(|| KzgError::BadArgs("Failed to parse G2Affine from bytes".to_string()))?;
+        self.check_g2(point)
+    }
+
+    fn check_g2(self, point: G2Affine) -> Result<G2Affine, KzgError> {
+        let in_subgroup = match self {
+            Self::Full => {
+                let point = G2Projective::from(point);
+                bool::from(mul_by_group_order(point, G2Projective::identity()).is_identity())
+            }
+            Self::Endomorphism => bool::from(point.is_torsion_free()),
+            Self::Skip => true,
+        };
+        if !in_subgroup {
+            return Err(KzgError::BadArgs(
+                "G2 point is not in the prime-order subgroup".to_string(),
+            ));
+        }
+        Ok(point)
+    }
+}
+
+/// Multiplies `point` by the order of the prime-order subgroup with double-and-add
+fn mul_by_group_order<T: Copy + Add<Output = T>>(point: T, identity: T) -> T {
+    let mut acc = identity;
+    for limb in MODULUS.iter().rev() {
+        for bit in (0..64).rev() {
+            acc = acc + acc;
+            if (limb >> bit) & 1 == 1 {
+                acc = acc + point;
+            }
+        }
+    }
+    acc
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use bls12_381::Scalar;
+
+    /// Returns the encoding of a point on the G1 curve, with a small x-coordinate, outside the
+    /// prime-order subgroup
+    pub(crate) fn g1_not_in_subgroup() -> Bytes48 {
+        (1u8..)
+            .map(|x| {
+                let mut bytes = [0u8; 48];
+                bytes[0] = 0x80;
+                bytes[47] = x;
+                bytes
+            })
+            .find(|bytes| {
+                Option::<G1Affine>::from(G1Affine::from_compressed_unchecked(bytes))
+                    .is_some_and(|point| !bool::from(point.is_torsion_free()))
+            })
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn test_subgroup_checks() {
+        let point = G1Affine::from(G1Projective::generator() * Scalar::from(42));
+        let bytes = Bytes48::from(point.to_compressed());
+        for check in [
+            SubgroupCheck::Full,
+            SubgroupCheck::Endomorphism,
+            SubgroupCheck::Skip,
+        ] {
+            assert_eq!(check.g1_from_bytes(&bytes).unwrap(), point);
+        }
+
+        let buffer = Bytes48::to_contiguous(&[bytes.clone(), g1_not_in_subgroup()]);
+        assert!(SubgroupCheck::Skip.g1_from_contiguous(&buffer).is_ok());
+        assert!(SubgroupCheck::Endomorphism
+            .g1_from_contiguous(&buffer)
+            .is_err());
+
+        let g2 = bls12_381::G2Affine::generator().to_compressed();
+        assert!(SubgroupCheck::Full.g2_from_compressed(&g2).is_ok());
+
+        let bytes = g1_not_in_subgroup();
+        assert!(SubgroupCheck::Full.g1_from_bytes(&bytes).is_err());
+        assert!(SubgroupCheck::Endomorphism.g1_from_bytes(&bytes).is_err());
+        assert!(SubgroupCheck::Skip.g1_from_bytes(&bytes).is_ok());
+    }
+}
@@ -0,0 +1,204 @@
+//! Dumps of the blob Fiat-Shamir transcript for cross-client debugging.
+//!
+//! [`ChallengeTranscript::to_json`] writes the inputs hashed into the evaluation challenge and
+//! the values derived from them as a flat JSON object with a fixed key order, lowercase
+//! `0x`-prefixed hex and no whitespace. [`compare_transcripts`] reads two such dumps, for example
+//! one from this crate and one produced by another implementation, and reports every field that
+//! differs together with the first differing byte.
+
+use crate::dtypes::*;
+use crate::enums::KzgError;
+use crate::kzg_proof::{compute_challenge_hash, scalar_from_bytes_unchecked, scalar_to_bytes32};
+use crate::trusted_setup::{parse_json_object, JsonValue, KzgSettings};
+use crate::{BYTES_PER_FIELD_ELEMENT, DOMAIN_STR_LENGTH};
+
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Inputs and outputs of the evaluation challenge of one blob
+#[derive(Debug, Clone)]
+pub struct ChallengeTranscript {
+    /// Domain separator hashed first
+    pub domain: [u8; DOMAIN_STR_LENGTH],
+    /// Number of field elements in the blob, hashed as the polynomial degree
+    pub degree: u64,
+    pub blob: Vec<u8>,
+    pub commitment: Bytes48,
+    /// SHA-256 of the whole transcript, the challenge before reduction
+    pub transcript_hash: Bytes32,
+    /// Evaluation challenge `z`
+    pub challenge: Bytes32,
+    /// Evaluation `y` of the blob at the challenge
+    pub evaluation: Bytes32,
+}
+
+impl ChallengeTranscript {
+    /// Records the transcript of `blob` and `commitment_bytes`
+    pub fn new(
+        blob: &Blob,
+        commitment_bytes: &Bytes48,
+        kzg_settings: &KzgSettings,
+    ) -> Result<Self, KzgError> {
+        let commitment = kzg_settings
+            .subgroup_check
+            .g1_from_bytes(commitment_bytes)?;
+        let degree = (blob.as_slice().len() / BYTES_PER_FIELD_ELEMENT) as u64;
+        let domain = kzg_settings.domain_separators.blob_proof;
+
+        let transcript_hash = compute_challenge_hash(blob.as_slice(), &commitment, &domain);
+        let challenge = scalar_from_bytes_unchecked(transcript_hash);
+        let evaluation = blob.to_polynomial()?.evaluate(challenge, kzg_settings)?;
+
+        Ok(Self {
+            domain,
+            degree,
+            blob: blob.as_slice().to_vec(),
+            commitment: commitment_bytes.clone(),
+            transcript_hash: transcript_hash.into(),
+            challenge: scalar_to_bytes32(&challenge),
+            evaluation: scalar_to_bytes32(&evaluation),
+        })
+    }
+
+    /// Serializes the transcript in the canonical JSON format
+    pub fn to_json(&self) -> String {
+        let hex = |bytes: &[u8]| format!("\"0x{}\"", hex::encode(bytes));
+        format!(
+            "{{\"domain\":{},\"degree\":{},\"blob\":{},\"commitment\":{},\"transcript_hash\":{},\
+             \"challenge\":{},\"evaluation\":{}}}",
+            hex(&self.domain),
+            self.degree,
+            hex(&self.blob),
+            hex(self.commitment.as_slice()),
+            hex(self.transcript_hash.as_slice()),
+            hex(self.challenge.as_slice()),
+            hex(self.evaluation.as_slice()),
+        )
+    }
+}
+
+/// A field that differs between two transcript dumps
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptDifference {
+    pub field: String,
+    /// Value in the first dump, or `None` if the field is missing
+    pub ours: Option<String>,
+    /// Value in the second dump, or `None` if the field is missing
+    pub theirs: Option<String>,
+    /// Offset of the first differing byte when both values are hex strings
+    pub first_differing_byte: Option<usize>,
+}
+
+/// Compares two transcript dumps field by field. Key order and whitespace are ignored, as is the
+/// case of hex digits.
+pub fn compare_transcripts(
+    ours: &str,
+    theirs: &str,
+) -> Result<Vec<TranscriptDifference>, KzgError> {
+    let ours = parse_transcript(ours)?;
+    let theirs = parse_transcript(theirs)?;
+
+    let mut fields = ours.keys().chain(theirs.keys()).collect::<Vec<_>>();
+    fields.sort();
+    fields.dedup();
+
+    let differences = fields
+        .into_iter()
+        .filter_map(|field| {
+            let (a, b) = (ours.get(field), theirs.get(field));
+            if a == b {
+                return None;
+            }
+            let first_differing_byte = a.zip(b).and_then(|(a, b)| {
+                let (a, b) = (decode_hex(a)?, decode_hex(b)?);
+                let common = a.iter().zip(&b).position(|(x, y)| x != y);
+                Some(common.unwrap_or(a.len().min(b.len())))
+            });
+            Some(TranscriptDifference {
+                field: field.clone(),
+                ours: a.cloned(),
+                theirs: b.cloned(),
+                first_differing_byte,
+            })
+        })
+        .collect();
+    Ok(differences)
+}
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    hex::decode(value.strip_prefix("0x")?).ok()
+}
+
+/// Parses a transcript dump, normalizing strings to lowercase
+fn parse_transcript(json: &str) -> Result<BTreeMap<String, String>, KzgError> {
+    let invalid = || KzgError::BadArgs("Invalid transcript JSON".to_string());
+    parse_json_object(json)
+        .ok_or_else(invalid)?
+        .into_iter()
+        .map(|(key, value)| {
+            let value = match value {
+                JsonValue::String(value) => value.to_ascii_lowercase(),
+                JsonValue::Integer(value) => value.to_string(),
+                JsonValue::Strings(_) => return Err(invalid()),
+            };
+            Ok((key.to_string(), value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kzg_proof::tests::{BlobInput, Test};
+
+    #[test]
+    fn test_challenge_transcript() {
+        let data = include_str!("../tests/verify_blob_kzg_proof/verify_blob_kzg_proof_case_correct_proof_fb324bc819407148/data.yaml");
+        let test: Test<BlobInput> = serde_yaml::from_str(data).unwrap();
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+
+        let transcript = ChallengeTranscript::new(
+            &test.input.get_blob().unwrap(),
+            &test.input.get_commitment().unwrap(),
+            &kzg_settings,
+        )
+        .unwrap();
+        assert_eq!(
+            hex::encode(transcript.challenge.as_slice()),
+            "4f00eef944a21cb9f3ac3390702621e4bbf1198767c43c0fb9c8e9923bfbb31a"
+        );
+
+        let json = transcript.to_json();
+        assert!(
+            json.starts_with("{\"domain\":\"0x4653424c4f425645524946595f56315f\",\"degree\":4096,")
+        );
+        assert!(compare_transcripts(&json, &json).unwrap().is_empty());
+
+        // Reordered, reformatted and upper-case dumps are equal
+        let reformatted = format!(
+            "{{ \"evaluation\": \"0x{}\",\n {} }}",
+            hex::encode_upper(transcript.evaluation.as_slice()),
+            &json[1..json.find(",\"evaluation\"").unwrap()]
+        );
+        assert!(compare_transcripts(&json, &reformatted).unwrap().is_empty());
+
+        // A single flipped bit in the commitment is located
+        let mut other = transcript.clone();
+        let mut commitment: [u8; 48] = other.commitment.into();
+        commitment[10] ^= 0x04;
+        other.commitment = commitment.into();
+        let differences = compare_transcripts(&json, &other.to_json()).unwrap();
+        assert_eq!(differences.len(), 1);
+        assert_eq!(differences[0].field, "commitment");
+        assert_eq!(differences[0].first_differing_byte, Some(10));
+
+        let differences = compare_transcripts(&json, "{\"degree\":4096}").unwrap();
+        assert!(differences.iter().all(|d| d.theirs.is_none()));
+        assert_eq!(differences.len(), 6);
+
+        assert!(compare_transcripts(&json, "{\"degree\":}").is_err());
+    }
+}
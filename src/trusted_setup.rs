@@ -1,14 +1,38 @@
-use crate::{enums::KzgError, NUM_G1_POINTS, NUM_ROOTS_OF_UNITY};
+use crate::{
+    compression::{decompress_g1_batch, decompress_g2_batch},
+    domains::DomainSeparators,
+    dtypes::Bytes48,
+    enums::KzgError,
+    fft::{bit_reversal_permutation, g1_fft, g1_inverse_fft, EvaluationDomain},
+    limits::Limits,
+    msm::FixedBaseTable,
+    pairings::{pairings_verify, pairings_verify_prepared},
+    subgroup::SubgroupCheck,
+    BYTES_PER_FIELD_ELEMENT, BYTES_PER_G1_POINT, BYTES_PER_G2_POINT, FIELD_ELEMENTS_PER_CELL,
+    NUM_G2_POINTS,
+};
+#[cfg(feature = "prover")]
+use crate::{
+    kzg_proof::scalar_from_bytes_unchecked,
+    msm::g1_msm_affine,
+    utils::{batch_inversion, compute_powers},
+    NUM_G1_POINTS, NUM_ROOTS_OF_UNITY,
+};
+#[cfg(feature = "prover")]
+use bls12_381::G2Projective;
+#[cfg(feature = "prover")]
+use sha2::{Digest, Sha256};
 
-use alloc::sync::Arc;
-use bls12_381::{G1Affine, G2Affine, Scalar};
+use alloc::{borrow::Cow, boxed::Box, string::ToString, sync::Arc, vec::Vec};
+use bls12_381::{G1Affine, G1Projective, G2Affine, G2Prepared, Scalar};
 use core::{
     hash::{Hash, Hasher},
     mem::transmute,
     slice,
 };
-use spin::Once;
+use spin::{Mutex, Once};
 
+#[cfg(feature = "prover")]
 pub fn get_roots_of_unity() -> &'static [Scalar] {
     static ROOTS_OF_UNITY: Once<&'static [Scalar]> = Once::new();
     ROOTS_OF_UNITY.call_once(|| {
@@ -17,6 +41,7 @@ pub fn get_roots_of_unity() -> &'static [Scalar] {
     })
 }
 
+#[cfg(feature = "prover")]
 pub fn get_g1_points() -> &'static [G1Affine] {
     static G1_POINTS: Once<&'static [G1Affine]> = Once::new();
     G1_POINTS.call_once(|| {
@@ -29,26 +54,116 @@ pub fn get_g2_points() -> &'static [G2Affine] {
     static G2_POINTS: Once<&'static [G2Affine]> = Once::new();
     G2_POINTS.call_once(|| {
         let bytes = include_bytes!(concat!(env!("OUT_DIR"), "/g2.bin"));
-        unsafe { transmute(slice::from_raw_parts(bytes.as_ptr(), NUM_G1_POINTS)) }
+        unsafe { transmute(slice::from_raw_parts(bytes.as_ptr(), NUM_G2_POINTS)) }
+    })
+}
+
+/// Returns the G2 points that recur in pairing checks, prepared once for the Miller loop: the
+/// generator, and `[τ]G2` and `[τ^64]G2` of the embedded setup, used by blob and cell proofs
+/// respectively
+pub fn get_g2_prepared() -> &'static [(G2Affine, G2Prepared)] {
+    static G2_PREPARED: Once<Vec<(G2Affine, G2Prepared)>> = Once::new();
+    G2_PREPARED.call_once(|| {
+        let g2_points = get_g2_points();
+        [
+            G2Affine::generator(),
+            g2_points[1],
+            g2_points[FIELD_ELEMENTS_PER_CELL],
+        ]
+        .into_iter()
+        .map(|point| (point, G2Prepared::from(point)))
+        .collect()
     })
 }
 
+/// Same as [`pairings_verify`], reusing the prepared points of [`get_g2_prepared`] when the G2
+/// points are among them
+pub(crate) fn pairings_verify_cached(
+    a1: G1Affine,
+    a2: G2Affine,
+    b1: G1Affine,
+    b2: G2Affine,
+) -> bool {
+    pairings_verify_prepared(a1, &prepare_g2(a2), b1, &prepare_g2(b2))
+}
+
+fn prepare_g2(point: G2Affine) -> Cow<'static, G2Prepared> {
+    match get_g2_prepared().iter().find(|(p, _)| *p == point) {
+        Some((_, prepared)) => Cow::Borrowed(prepared),
+        None => Cow::Owned(G2Prepared::from(point)),
+    }
+}
+
+#[cfg(feature = "prover")]
 pub fn get_kzg_settings() -> KzgSettings {
     KzgSettings {
         roots_of_unity: get_roots_of_unity(),
         g1_points: get_g1_points(),
         g2_points: get_g2_points(),
+        g1_monomial_points: &[],
+        subgroup_check: SubgroupCheck::default(),
+        domain_separators: DomainSeparators::default(),
+        limits: Limits::default(),
+        g1_lagrange_table: None,
+    }
+}
+
+/// Only the G2 points are needed to verify a KZG proof, so builds without `prover` leave the G1
+/// points and roots of unity out of the binary entirely
+#[cfg(not(feature = "prover"))]
+pub fn get_kzg_settings() -> KzgSettings {
+    KzgSettings {
+        roots_of_unity: &[],
+        g1_points: &[],
+        g2_points: get_g2_points(),
+        g1_monomial_points: &[],
+        subgroup_check: SubgroupCheck::default(),
+        domain_separators: DomainSeparators::default(),
+        limits: Limits::default(),
+        g1_lagrange_table: None,
     }
 }
 
+/// The trusted setup together with the options applied to every operation.
+///
+/// The points and precomputed tables are `&'static` slices shared by every copy, so a clone
+/// copies a few pointers rather than the setup, and the settings are `Send + Sync`: worker
+/// threads can each take a clone, or borrow the same settings, without copying any points.
+///
+/// Settings loaded at runtime leak their points to get those slices, so the points live for the
+/// rest of the process like the embedded mainnet setup. Points equal to ones leaked before reuse
+/// that allocation, so loading or deriving the same setup again leaks nothing more, though
+/// cloning the settings is still cheaper than parsing the points again.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[repr(C, align(4))]
 pub struct KzgSettings {
+    /// Roots of unity of the evaluation domain, in bit-reversed order
     pub roots_of_unity: &'static [Scalar],
+    /// G1 points in Lagrange form, in bit-reversed order
     pub g1_points: &'static [G1Affine],
+    /// G2 points in monomial form
     pub g2_points: &'static [G2Affine],
+    /// G1 points in monomial form `[τ^i]G1`, or empty if the setup did not include them. Use
+    /// [`KzgSettings::g1_monomial`] to derive them on demand.
+    pub g1_monomial_points: &'static [G1Affine],
+    /// Subgroup check applied to the points passed to the verification and proving functions
+    pub subgroup_check: SubgroupCheck,
+    /// Domain separators of the Fiat-Shamir challenges
+    pub domain_separators: DomainSeparators,
+    /// Largest inputs accepted by the batch functions
+    pub limits: Limits,
+    /// Precomputed multiples of [`KzgSettings::g1_points`] used for commitments, if any. Set with
+    /// [`KzgSettings::with_precompute`].
+    pub g1_lagrange_table: Option<&'static FixedBaseTable>,
 }
 
+// Sharing settings across threads is part of the API, so a field that is not thread-safe must
+// fail to compile
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<KzgSettings>();
+};
+
 #[derive(Debug, Clone, Default, Eq)]
 pub enum EnvKzgSettings {
     #[default]
@@ -91,8 +206,1180 @@ impl EnvKzgSettings {
     }
 }
 
+/// [`KzgSettings::digest`] of the mainnet trusted setup from the Ethereum KZG ceremony, loaded
+/// without its G1 points in monomial form as from the bundled `trusted_setup.txt`
+#[cfg(feature = "prover")]
+pub const MAINNET_SETUP_DIGEST: [u8; 32] = [
+    0x67, 0xd3, 0x4d, 0x18, 0x1c, 0xa3, 0xdf, 0xaa, 0x2d, 0x8a, 0xb9, 0x81, 0x5f, 0x8b, 0x34, 0xb6,
+    0xb0, 0x46, 0x33, 0xd0, 0xcf, 0xbe, 0xe3, 0xdd, 0xc8, 0x30, 0x12, 0x33, 0x20, 0xa3, 0x3c, 0x41,
+];
+
+/// SHA-256 of the mainnet `trusted_setup.txt` published with c-kzg for EIP-4844, the file bundled
+/// with this crate, for [`KzgSettings::check_file_digest`]
+#[cfg(feature = "prover")]
+pub const MAINNET_TRUSTED_SETUP_SHA256: [u8; 32] = [
+    0x19, 0xd2, 0xf6, 0x02, 0x9b, 0x7f, 0x04, 0x52, 0xc2, 0x74, 0x73, 0xdf, 0xe2, 0x76, 0x1a, 0x99,
+    0xb8, 0xdd, 0x36, 0x8a, 0x13, 0x4c, 0xf2, 0xba, 0xc0, 0x64, 0xf8, 0xc5, 0xb5, 0x69, 0x91, 0x9c,
+];
+
+/// The mainnet trusted setup in the c-kzg text format, as embedded by the `embedded-setup`
+/// feature
+#[cfg(feature = "embedded-setup")]
+pub const EMBEDDED_TRUSTED_SETUP: &[u8] = include_bytes!("trusted_setup.txt");
+
+/// Field elements per blob in the `minimal` preset of the consensus specs
+pub const MINIMAL_FIELD_ELEMENTS_PER_BLOB: usize = 4;
+
+/// The secret τ of the testing trusted setup that the consensus specs generate for the `minimal`
+/// preset. It is public, so the setup is only fit for tests.
+pub const MINIMAL_SETUP_SECRET: u64 = 1337;
+
+/// Settings returned by [`KzgSettings::global`]
+static GLOBAL: Once<KzgSettings> = Once::new();
+
+/// Values derived from the Lagrange-form G1 points of a setup, built once and kept for the rest of
+/// the process. Entries are keyed by the points themselves rather than their address, so settings
+/// loaded again from the same setup reuse the entry of the first copy.
+pub(crate) struct SetupCache<K, V: ?Sized + 'static> {
+    entries: Mutex<Vec<(&'static [G1Affine], K, &'static V)>>,
+}
+
+impl<K: Copy + PartialEq, V: ?Sized> SetupCache<K, V> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            entries: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns the value cached for `g1_points` and `key`, building it with `build` on first use
+    pub(crate) fn get_or_try_insert_with(
+        &self,
+        g1_points: &'static [G1Affine],
+        key: K,
+        build: impl FnOnce() -> Result<&'static V, KzgError>,
+    ) -> Result<&'static V, KzgError> {
+        let find = |entries: &[(&'static [G1Affine], K, &'static V)]| {
+            entries
+                .iter()
+                .find(|(points, k, _)| {
+                    *k == key && (core::ptr::eq(*points, g1_points) || *points == g1_points)
+                })
+                .map(|(_, _, value)| *value)
+        };
+        if let Some(value) = find(&self.entries.lock()) {
+            return Ok(value);
+        }
+
+        let value = build()?;
+        let mut entries = self.entries.lock();
+        // Another thread may have built it meanwhile
+        if let Some(existing) = find(&entries) {
+            return Ok(existing);
+        }
+        entries.push((g1_points, key, value));
+        Ok(value)
+    }
+}
+
+/// Points leaked to back the slices of [`KzgSettings`]. Equal points share the slice leaked
+/// first, so settings loaded or derived again from the same setup do not leak another copy.
+pub(crate) struct LeakedPoints<T: 'static> {
+    slices: Mutex<Vec<&'static [T]>>,
+}
+
+/// G1 points of every setup loaded or derived at runtime, in either form
+pub(crate) static LEAKED_G1_POINTS: LeakedPoints<G1Affine> = LeakedPoints::new();
+
+/// G2 points of every setup loaded at runtime
+pub(crate) static LEAKED_G2_POINTS: LeakedPoints<G2Affine> = LeakedPoints::new();
+
+impl<T: PartialEq> LeakedPoints<T> {
+    const fn new() -> Self {
+        Self {
+            slices: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns a `'static` slice holding `points`, leaking them unless equal points were leaked
+    /// before
+    pub(crate) fn leak(&self, points: Vec<T>) -> &'static [T] {
+        if points.is_empty() {
+            return &[];
+        }
+        let mut slices = self.slices.lock();
+        if let Some(existing) = slices.iter().find(|slice| ***slice == points[..]) {
+            return existing;
+        }
+        let leaked: &'static [T] = points.leak();
+        slices.push(leaked);
+        leaked
+    }
+}
+
 impl KzgSettings {
     pub fn load_trusted_setup_file() -> Result<Self, KzgError> {
         Ok(get_kzg_settings())
     }
+
+    /// Returns settings shared by the whole process, so they need not be passed through every
+    /// layer of an application. Unless [`KzgSettings::set_global`] ran first, these are the
+    /// mainnet settings of [`KzgSettings::load_trusted_setup_file`], initialized on first use.
+    pub fn global() -> &'static Self {
+        GLOBAL.call_once(|| {
+            Self::load_trusted_setup_file().expect("failed to load default trusted setup")
+        })
+    }
+
+    /// Makes `settings` the ones returned by [`KzgSettings::global`], for processes that use a
+    /// custom setup or options. Fails once the global settings are initialized, including by an
+    /// earlier call to [`KzgSettings::global`].
+    pub fn set_global(settings: Self) -> Result<(), KzgError> {
+        let mut settings = Some(settings);
+        GLOBAL.call_once(|| settings.take().expect("taken once"));
+        match settings {
+            Some(_) => Err(KzgError::BadArgs(
+                "The global settings are already initialized".to_string(),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the mainnet trusted setup embedded in the binary, parsed on first use.
+    ///
+    /// Unlike [`KzgSettings::load_trusted_setup_file`], whose points are laid out in memory by
+    /// the build script, this parses and checks every point of [`EMBEDDED_TRUSTED_SETUP`] like a
+    /// setup loaded at runtime, without a filesystem, so it also works on `no_std` and zkVM
+    /// targets.
+    #[cfg(feature = "embedded-setup")]
+    pub fn embedded() -> &'static Self {
+        static EMBEDDED: Once<KzgSettings> = Once::new();
+        EMBEDDED.call_once(|| {
+            let contents = core::str::from_utf8(EMBEDDED_TRUSTED_SETUP)
+                .expect("the embedded trusted setup is ASCII");
+            Self::parse_trusted_setup(contents).expect("the embedded trusted setup is valid")
+        })
+    }
+
+    /// Returns the testing trusted setup of the `minimal` preset, with
+    /// [`MINIMAL_FIELD_ELEMENTS_PER_BLOB`] G1 points and as many G2 points as mainnet, derived
+    /// from [`MINIMAL_SETUP_SECRET`] on first use.
+    ///
+    /// The [`runtime`](crate::runtime) functions take blobs of the size of the setup, so with
+    /// these settings they run the minimal spec tests.
+    #[cfg(feature = "prover")]
+    pub fn minimal() -> &'static Self {
+        static MINIMAL: Once<KzgSettings> = Once::new();
+        MINIMAL.call_once(|| {
+            let powers = compute_powers(&Scalar::from(MINIMAL_SETUP_SECRET), NUM_G2_POINTS);
+            let monomial = powers[..MINIMAL_FIELD_ELEMENTS_PER_BLOB]
+                .iter()
+                .map(|power| G1Projective::generator() * power)
+                .collect::<Vec<_>>();
+            let g2_points = powers
+                .iter()
+                .map(|power| G2Affine::from(G2Projective::generator() * power))
+                .collect();
+
+            let lagrange = g1_inverse_fft(&monomial).expect("the minimal domain exists");
+            let mut g1_lagrange = vec![G1Affine::identity(); lagrange.len()];
+            G1Projective::batch_normalize(&lagrange, &mut g1_lagrange);
+            let mut g1_monomial = vec![G1Affine::identity(); monomial.len()];
+            G1Projective::batch_normalize(&monomial, &mut g1_monomial);
+
+            Self::from_points(g1_lagrange, g1_monomial, g2_points)
+                .expect("the minimal trusted setup is valid")
+        })
+    }
+
+    /// Parses a trusted setup in the c-kzg text format: the number of G1 points, the number of
+    /// G2 points, the G1 points in Lagrange form, the G2 points in monomial form and, in the newer
+    /// layout, the G1 points in monomial form, one hex-encoded compressed point per line.
+    ///
+    /// The parsed points are leaked, see [`KzgSettings`].
+    pub fn parse_trusted_setup(contents: &str) -> Result<Self, KzgError> {
+        Self::parse_trusted_setup_with_subgroup_check(contents, SubgroupCheck::default())
+    }
+
+    /// Same as [`KzgSettings::parse_trusted_setup`], checking the subgroup membership of the
+    /// setup points with `subgroup_check`. [`SubgroupCheck::Skip`] is only appropriate for a file
+    /// whose integrity is otherwise established, for example by its checksum.
+    pub fn parse_trusted_setup_with_subgroup_check(
+        contents: &str,
+        subgroup_check: SubgroupCheck,
+    ) -> Result<Self, KzgError> {
+        let mut lines = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty());
+
+        let mut parse_count = |name: &str| {
+            lines
+                .next()
+                .and_then(|line| line.parse::<usize>().ok())
+                .ok_or_else(|| {
+                    KzgError::InvalidTrustedSetup(format!("Missing number of {} points", name))
+                })
+        };
+        let num_g1_points = parse_count("G1")?;
+        let num_g2_points = parse_count("G2")?;
+
+        let g1_lagrange = (&mut lines)
+            .take(num_g1_points)
+            .map(|line| parse_g1_point(line, subgroup_check))
+            .collect::<Result<Vec<_>, _>>()?;
+        let g2_points = (&mut lines)
+            .take(num_g2_points)
+            .map(|line| parse_g2_point(line, subgroup_check))
+            .collect::<Result<Vec<_>, _>>()?;
+        let g1_monomial = (&mut lines)
+            .take(num_g1_points)
+            .map(|line| parse_g1_point(line, subgroup_check))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if g1_lagrange.len() != num_g1_points || g2_points.len() != num_g2_points {
+            return Err(KzgError::InvalidTrustedSetup(
+                "The trusted setup is truncated".to_string(),
+            ));
+        }
+        if lines.next().is_some() {
+            return Err(KzgError::InvalidTrustedSetup(
+                "Unexpected trailing data in the trusted setup".to_string(),
+            ));
+        }
+
+        Self::from_points(g1_lagrange, g1_monomial, g2_points)
+    }
+
+    /// Parses the `trusted_setup.json` of the Ethereum KZG ceremony: an object with the
+    /// hex-encoded compressed points in `g1_lagrange`, `g2_monomial` and, optionally,
+    /// `g1_monomial` arrays. Other keys are ignored.
+    ///
+    /// The parsed points are leaked, see [`KzgSettings`].
+    pub fn parse_trusted_setup_json(json: &str) -> Result<Self, KzgError> {
+        let invalid = || KzgError::InvalidTrustedSetup("Invalid trusted setup JSON".to_string());
+        let object = parse_json_object(json).ok_or_else(invalid)?;
+        let field = |name: &str| {
+            object
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| match value {
+                    JsonValue::Strings(values) => Ok(values.as_slice()),
+                    _ => Err(invalid()),
+                })
+                .transpose()
+        };
+        let required = |name: &str| {
+            field(name)?.ok_or_else(|| {
+                KzgError::InvalidTrustedSetup(format!("Missing {} in the trusted setup", name))
+            })
+        };
+
+        let subgroup_check = SubgroupCheck::default();
+        let parse_g1 = |points: &[&str]| {
+            points
+                .iter()
+                .map(|point| parse_g1_point(point, subgroup_check))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let g1_lagrange = parse_g1(required("g1_lagrange")?)?;
+        let g1_monomial = parse_g1(field("g1_monomial")?.unwrap_or_default())?;
+        let g2_points = required("g2_monomial")?
+            .iter()
+            .map(|point| parse_g2_point(point, subgroup_check))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::from_points(g1_lagrange, g1_monomial, g2_points)
+    }
+
+    /// Reads and parses a trusted setup file, in the c-kzg text format of
+    /// [`KzgSettings::parse_trusted_setup`] or, if it holds a JSON object, the ceremony format of
+    /// [`KzgSettings::parse_trusted_setup_json`]
+    #[cfg(feature = "std")]
+    pub fn load_trusted_setup_from_path(
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<Self, KzgError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            KzgError::InvalidTrustedSetup(format!("Failed to read trusted setup: {}", e))
+        })?;
+        Self::parse_trusted_setup_file(&contents)
+    }
+
+    /// Same as [`KzgSettings::load_trusted_setup_from_path`], first checking the file against its
+    /// expected SHA-256 with [`KzgSettings::check_file_digest`]
+    #[cfg(all(feature = "std", feature = "prover"))]
+    pub fn load_trusted_setup_from_path_checked(
+        path: impl AsRef<std::path::Path>,
+        expected_sha256: &[u8; 32],
+    ) -> Result<Self, KzgError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            KzgError::InvalidTrustedSetup(format!("Failed to read trusted setup: {}", e))
+        })?;
+        Self::check_file_digest(contents.as_bytes(), expected_sha256)?;
+        Self::parse_trusted_setup_file(&contents)
+    }
+
+    /// Parses the contents of a trusted setup file in either format of
+    /// [`KzgSettings::load_trusted_setup_from_path`]
+    #[cfg(feature = "std")]
+    pub(crate) fn parse_trusted_setup_file(contents: &str) -> Result<Self, KzgError> {
+        if contents.trim_start().starts_with('{') {
+            Self::parse_trusted_setup_json(contents)
+        } else {
+            Self::parse_trusted_setup(contents)
+        }
+    }
+
+    /// Builds the settings from the concatenated compressed points of a trusted setup, like c-kzg's
+    /// `load_trusted_setup`: the G1 points in Lagrange form, the G1 points in monomial form, which
+    /// may be empty, and the G2 points in monomial form.
+    ///
+    /// The points are leaked, see [`KzgSettings`].
+    pub fn load_trusted_setup(
+        g1_lagrange_bytes: &[u8],
+        g1_monomial_bytes: &[u8],
+        g2_bytes: &[u8],
+    ) -> Result<Self, KzgError> {
+        let subgroup_check = SubgroupCheck::default();
+        let g1 = |bytes| {
+            let encodings = split_points::<BYTES_PER_G1_POINT>(bytes, "G1")?
+                .into_iter()
+                .map(Bytes48::from)
+                .collect::<Vec<_>>();
+            decompress_g1_batch(&encodings, subgroup_check)
+        };
+        let g1_lagrange = g1(g1_lagrange_bytes)?;
+        let g1_monomial = g1(g1_monomial_bytes)?;
+        let g2_points = decompress_g2_batch(
+            &split_points::<BYTES_PER_G2_POINT>(g2_bytes, "G2")?,
+            subgroup_check,
+        )?;
+        Self::from_points(g1_lagrange, g1_monomial, g2_points)
+    }
+
+    /// Same as [`KzgSettings::load_trusted_setup`], also precomputing tables of the setup points
+    /// like c-kzg's `precompute` parameter. A `precompute` of 0 builds no table, and otherwise
+    /// it is the window size of [`KzgSettings::with_precompute`]: larger windows prove faster
+    /// at the cost of more memory, up to about 12 bits.
+    pub fn load_trusted_setup_with_precompute(
+        g1_lagrange_bytes: &[u8],
+        g1_monomial_bytes: &[u8],
+        g2_bytes: &[u8],
+        precompute: usize,
+    ) -> Result<Self, KzgError> {
+        Self::load_trusted_setup(g1_lagrange_bytes, g1_monomial_bytes, g2_bytes)?
+            .with_precompute_level(precompute)
+    }
+
+    /// Same as [`KzgSettings::load_trusted_setup_file`] with the `precompute` level of
+    /// [`KzgSettings::load_trusted_setup_with_precompute`]
+    pub fn load_trusted_setup_file_with_precompute(precompute: usize) -> Result<Self, KzgError> {
+        Self::load_trusted_setup_file()?.with_precompute_level(precompute)
+    }
+
+    fn with_precompute_level(self, precompute: usize) -> Result<Self, KzgError> {
+        match precompute {
+            0 => Ok(self),
+            window_bits => self.with_precompute(window_bits),
+        }
+    }
+
+    /// Builds the settings from G1 points in Lagrange form (in natural order), optional G1
+    /// points in monomial form and G2 points in monomial form
+    pub(crate) fn from_points(
+        g1_lagrange: Vec<G1Affine>,
+        g1_monomial: Vec<G1Affine>,
+        g2_points: Vec<G2Affine>,
+    ) -> Result<Self, KzgError> {
+        let n = g1_lagrange.len();
+        if n < 2 || !n.is_power_of_two() {
+            return Err(KzgError::InvalidTrustedSetup(
+                "The number of G1 points must be a power of two".to_string(),
+            ));
+        }
+        if g2_points.len() < 2 {
+            return Err(KzgError::InvalidTrustedSetup(
+                "At least two G2 points are required".to_string(),
+            ));
+        }
+        if !g1_monomial.is_empty() && g1_monomial.len() != n {
+            return Err(KzgError::InvalidTrustedSetup(
+                "The number of G1 monomial points must match the Lagrange points".to_string(),
+            ));
+        }
+
+        // Points in monomial form satisfy e([τ]G1, G2) == e(G1, [τ]G2), which would mean the
+        // Lagrange section holds monomial points
+        if pairings_verify(g1_lagrange[1], g2_points[0], g1_lagrange[0], g2_points[1]) {
+            return Err(KzgError::InvalidTrustedSetup(
+                "The G1 Lagrange points are in monomial form".to_string(),
+            ));
+        }
+
+        let roots_of_unity = &EvaluationDomain::get(n)?.bit_reversed_roots;
+        let g1_points = bit_reversal_permutation(&g1_lagrange);
+
+        Ok(Self {
+            roots_of_unity,
+            g1_points: LEAKED_G1_POINTS.leak(g1_points),
+            g2_points: LEAKED_G2_POINTS.leak(g2_points),
+            g1_monomial_points: LEAKED_G1_POINTS.leak(g1_monomial),
+            subgroup_check: SubgroupCheck::default(),
+            domain_separators: DomainSeparators::default(),
+            limits: Limits::default(),
+            g1_lagrange_table: None,
+        })
+    }
+
+    /// Returns these settings with the subgroup check applied to input points replaced
+    pub fn with_subgroup_check(mut self, subgroup_check: SubgroupCheck) -> Self {
+        self.subgroup_check = subgroup_check;
+        self
+    }
+
+    /// Returns these settings with a table of precomputed multiples of the Lagrange-form G1
+    /// points, which makes later commitments and proofs cheaper: about 40% for the mainnet setup
+    /// with 12-bit windows. The table takes [`FixedBaseTable::size_in_bytes`] bytes, about 9 MiB
+    /// in that case. Larger windows store fewer points, but past 12 bits their buckets cost more
+    /// than they save.
+    ///
+    /// Tables are built once per setup and window size, and shared by every later load of the
+    /// same setup.
+    pub fn with_precompute(mut self, window_bits: usize) -> Result<Self, KzgError> {
+        static TABLES: SetupCache<usize, FixedBaseTable> = SetupCache::new();
+        let table = TABLES.get_or_try_insert_with(self.g1_points, window_bits, || {
+            Ok(Box::leak(Box::new(FixedBaseTable::new(
+                self.g1_points,
+                window_bits,
+            )?)))
+        })?;
+        self.g1_lagrange_table = Some(table);
+        Ok(self)
+    }
+
+    /// Returns the number of field elements in a blob committed to with this setup, one per
+    /// Lagrange point. Every constructor checks that it is a power of two matching the loaded
+    /// points. For the mainnet setup it is [`crate::NUM_FIELD_ELEMENTS_PER_BLOB`], the size of
+    /// `Blob`; blobs of other sizes go through the `runtime` functions.
+    pub fn field_elements_per_blob(&self) -> usize {
+        self.roots_of_unity.len()
+    }
+
+    /// Returns the size in bytes of a blob committed to with this setup
+    pub fn bytes_per_blob(&self) -> usize {
+        self.field_elements_per_blob() * BYTES_PER_FIELD_ELEMENT
+    }
+
+    /// Returns the evaluation domain of the setup, whose bit-reversed roots are
+    /// [`KzgSettings::roots_of_unity`], with the values derived from it cached
+    pub fn domain(&self) -> Result<&'static EvaluationDomain, KzgError> {
+        EvaluationDomain::get(self.field_elements_per_blob())
+    }
+
+    /// Returns the domain of twice the order of [`KzgSettings::domain`], over which blobs are
+    /// extended into cells
+    pub fn extended_domain(&self) -> Result<&'static EvaluationDomain, KzgError> {
+        EvaluationDomain::get(2 * self.field_elements_per_blob())
+    }
+
+    /// Returns the G1 points in monomial form, deriving them from the Lagrange-form points when
+    /// the setup did not include them
+    pub fn g1_monomial(&self) -> Result<Cow<'static, [G1Affine]>, KzgError> {
+        if !self.g1_monomial_points.is_empty() {
+            return Ok(Cow::Borrowed(self.g1_monomial_points));
+        }
+
+        // The embedded setup ships without them, so they are derived once per setup
+        static DERIVED_MONOMIAL: SetupCache<(), [G1Affine]> = SetupCache::new();
+        let points = DERIVED_MONOMIAL.get_or_try_insert_with(self.g1_points, (), || {
+            Ok(LEAKED_G1_POINTS.leak(derive_g1_monomial(self.g1_points)?))
+        })?;
+        Ok(Cow::Borrowed(points))
+    }
+
+    /// Returns the SHA-256 of the compressed G1 Lagrange points, in the bit-reversed order of
+    /// [`KzgSettings::g1_points`], followed by the compressed G2 points and, when the setup was
+    /// loaded with them, the compressed G1 points in monomial form. It identifies the setup
+    /// whatever format it was loaded from, but a setup loaded with its monomial points has another
+    /// digest than the same setup loaded without; [`KzgSettings::check_file_digest`] checks a
+    /// setup file against its published hash instead.
+    #[cfg(feature = "prover")]
+    pub fn digest(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        for point in self.g1_points {
+            hasher.update(point.to_compressed());
+        }
+        for point in self.g2_points {
+            hasher.update(point.to_compressed());
+        }
+        for point in self.g1_monomial_points {
+            hasher.update(point.to_compressed());
+        }
+        hasher.finalize().into()
+    }
+
+    /// Checks that the SHA-256 of the contents of a trusted setup file is `expected`, for example
+    /// [`MAINNET_TRUSTED_SETUP_SHA256`], the hash published for the mainnet file
+    #[cfg(feature = "prover")]
+    pub fn check_file_digest(contents: &[u8], expected: &[u8; 32]) -> Result<(), KzgError> {
+        let digest: [u8; 32] = Sha256::digest(contents).into();
+        if digest != *expected {
+            return Err(KzgError::InvalidTrustedSetup(format!(
+                "The trusted setup file has SHA-256 0x{}, expected 0x{}",
+                hex::encode(digest),
+                hex::encode(expected)
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks that [`KzgSettings::digest`] is `expected`, for example [`MAINNET_SETUP_DIGEST`],
+    /// to detect a corrupted or tampered setup file
+    #[cfg(feature = "prover")]
+    pub fn check_digest(&self, expected: &[u8; 32]) -> Result<(), KzgError> {
+        let digest = self.digest();
+        if digest != *expected {
+            return Err(KzgError::InvalidTrustedSetup(format!(
+                "The trusted setup digest is 0x{}, expected 0x{}",
+                hex::encode(digest),
+                hex::encode(expected)
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks that the setup is a well-formed powers-of-tau sequence for a single secret `τ`:
+    /// the G1 points in monomial form are `[τ^i]G1` and the G2 points `[τ^i]G2`, starting at the
+    /// generators, and the Lagrange-form points commit to the same polynomials.
+    ///
+    /// Consecutive points are compared with pairings, batched with a random linear combination
+    /// derived from the points themselves, so the check costs a few MSMs and pairings rather
+    /// than one pairing per point. Without monomial points in the setup they are derived from the
+    /// Lagrange points, which makes the two consistent by construction.
+    #[cfg(feature = "prover")]
+    pub fn verify(&self) -> Result<(), KzgError> {
+        let invalid = |message: &str| Err(KzgError::InvalidTrustedSetup(message.to_string()));
+        let monomial = self.g1_monomial()?;
+        let (g1, g2) = (&monomial[..], self.g2_points);
+        if g1.len() < 2 || g2.len() < 2 {
+            return invalid("The setup needs at least two G1 and two G2 points");
+        }
+        if g1[0] != G1Affine::generator() || g2[0] != G2Affine::generator() {
+            return invalid("The setup does not start at the generators");
+        }
+
+        let mut hasher = Sha256::new();
+        for point in self.g1_points.iter().chain(self.g1_monomial_points) {
+            hasher.update(point.to_compressed());
+        }
+        for point in g2 {
+            hasher.update(point.to_compressed());
+        }
+        let r = scalar_from_bytes_unchecked(hasher.finalize().into());
+        let r_powers = compute_powers(&r, g1.len().max(g2.len()));
+
+        // e(Σ r^i [τ^(i+1)]G1, G2) == e(Σ r^i [τ^i]G1, [τ]G2)
+        let shifted = g1_msm_affine(&g1[1..], &r_powers);
+        let unshifted = g1_msm_affine(&g1[..g1.len() - 1], &r_powers);
+        if !pairings_verify(shifted.into(), g2[0], unshifted.into(), g2[1]) {
+            return invalid("The G1 points are not successive powers of the G2 secret");
+        }
+
+        // e(G1, Σ r^i [τ^(i+1)]G2) == e([τ]G1, Σ r^i [τ^i]G2)
+        let combine = |points: &[G2Affine]| {
+            points
+                .iter()
+                .zip(&r_powers)
+                .fold(G2Projective::identity(), |acc, (point, r)| acc + point * r)
+        };
+        let shifted = combine(&g2[1..]);
+        let unshifted = combine(&g2[..g2.len() - 1]);
+        if !pairings_verify(g1[0], shifted.into(), g1[1], unshifted.into()) {
+            return invalid("The G2 points are not successive powers of the G1 secret");
+        }
+
+        // Both forms must commit to P(X) = Σ r^j X^j alike: with monomial points its commitment
+        // is Σ r^j [τ^j]G1, and with Lagrange points Σ P(ω_i) [L_i(τ)]G1, where
+        // P(ω_i) = (r^n - 1) / (r ω_i - 1) as ω_i^n = 1
+        if !self.g1_monomial_points.is_empty() {
+            let numerator = self.domain()?.vanishing_at(r);
+            let denominators = self
+                .roots_of_unity
+                .iter()
+                .map(|root| r * root - Scalar::one())
+                .collect::<Vec<_>>();
+            let weights = batch_inversion(&denominators)?
+                .iter()
+                .map(|inverse| numerator * inverse)
+                .collect::<Vec<_>>();
+            if g1_msm_affine(self.g1_points, &weights) != g1_msm_affine(g1, &r_powers) {
+                return invalid("The G1 Lagrange points do not match the monomial points");
+            }
+        }
+        Ok(())
+    }
+
+    /// Derives the setup for a domain of `n` points from the same ceremony, for committing to
+    /// polynomials of degree below `n`. `n` must be a power of two no larger than the current
+    /// domain. The G2 points are shared.
+    pub fn subset(&self, n: usize) -> Result<Self, KzgError> {
+        let monomial = self.g1_monomial()?;
+        if n < 2 || !n.is_power_of_two() || n > monomial.len() {
+            return Err(KzgError::BadArgs(format!(
+                "Cannot derive a {}-point setup from a {}-point setup",
+                n,
+                monomial.len()
+            )));
+        }
+        // The monomial points are a prefix of the current ones, which stay alive as they are
+        // leaked or cached
+        let monomial = match monomial {
+            Cow::Borrowed(points) => &points[..n],
+            Cow::Owned(points) => &LEAKED_G1_POINTS.leak(points)[..n],
+        };
+
+        // [L_i(τ)]G1 = 1/n Σ_j ω^{-ij} [τ^j]G1, an inverse DFT of the monomial points
+        let roots_of_unity = &EvaluationDomain::get(n)?.bit_reversed_roots;
+        let lagrange =
+            g1_inverse_fft(&monomial.iter().map(G1Projective::from).collect::<Vec<_>>())?;
+
+        let mut g1_points = vec![G1Affine::identity(); n];
+        G1Projective::batch_normalize(&lagrange, &mut g1_points);
+
+        Ok(Self {
+            roots_of_unity,
+            g1_points: LEAKED_G1_POINTS.leak(bit_reversal_permutation(&g1_points)),
+            g2_points: self.g2_points,
+            g1_monomial_points: monomial,
+            subgroup_check: self.subgroup_check,
+            domain_separators: self.domain_separators,
+            limits: self.limits,
+            g1_lagrange_table: None,
+        })
+    }
+}
+
+/// Derives the G1 points in monomial form from the Lagrange-form points in bit-reversed order
+fn derive_g1_monomial(g1_points: &[G1Affine]) -> Result<Vec<G1Affine>, KzgError> {
+    // [τ^i]G1 = Σ_j ω^{ij} [L_j(τ)]G1, a DFT of the Lagrange points in natural order
+    let lagrange = bit_reversal_permutation(g1_points)
+        .into_iter()
+        .map(G1Projective::from)
+        .collect::<Vec<_>>();
+    let monomial = g1_fft(&lagrange)?;
+
+    let mut affine = vec![G1Affine::identity(); monomial.len()];
+    G1Projective::batch_normalize(&monomial, &mut affine);
+    Ok(affine)
+}
+
+/// Splits concatenated `N`-byte point encodings
+pub(crate) fn split_points<const N: usize>(
+    bytes: &[u8],
+    group: &str,
+) -> Result<Vec<[u8; N]>, KzgError> {
+    if !bytes.len().is_multiple_of(N) {
+        return Err(KzgError::InvalidTrustedSetup(format!(
+            "The {} points take {} bytes, which is not a multiple of {}",
+            group,
+            bytes.len(),
+            N
+        )));
+    }
+    Ok(bytes
+        .chunks_exact(N)
+        .map(|chunk| chunk.try_into().expect("the chunks have N bytes"))
+        .collect())
+}
+
+/// Value of a field of a JSON object read by [`parse_json_object`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum JsonValue<'a> {
+    String(&'a str),
+    /// Non-negative integer, as written
+    Integer(&'a str),
+    Strings(Vec<&'a str>),
+}
+
+/// Parses a JSON object whose values are strings, non-negative integers or arrays of strings,
+/// none of them with escapes. Returns `None` if the JSON is malformed or uses anything else.
+pub(crate) fn parse_json_object(json: &str) -> Option<Vec<(&str, JsonValue<'_>)>> {
+    let mut rest = json.trim().strip_prefix('{')?.strip_suffix('}')?.trim();
+
+    let mut object = Vec::new();
+    while !rest.is_empty() {
+        let (key, after) = parse_json_string(rest)?;
+        rest = after.trim_start().strip_prefix(':')?.trim_start();
+
+        let value = if rest.starts_with('"') {
+            let (value, after) = parse_json_string(rest)?;
+            rest = after;
+            JsonValue::String(value)
+        } else if let Some(after) = rest.strip_prefix('[') {
+            rest = after.trim_start();
+            let mut values = Vec::new();
+            if let Some(after) = rest.strip_prefix(']') {
+                rest = after;
+            } else {
+                loop {
+                    let (value, after) = parse_json_string(rest)?;
+                    values.push(value);
+                    rest = after.trim_start();
+                    if let Some(after) = rest.strip_prefix(',') {
+                        rest = after.trim_start();
+                    } else {
+                        rest = rest.strip_prefix(']')?;
+                        break;
+                    }
+                }
+            }
+            JsonValue::Strings(values)
+        } else {
+            let end = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            if end == 0 {
+                return None;
+            }
+            let (value, after) = rest.split_at(end);
+            rest = after;
+            JsonValue::Integer(value)
+        };
+        object.push((key, value));
+
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix(',') {
+            rest = after.trim_start();
+            if rest.is_empty() {
+                return None;
+            }
+        } else if !rest.is_empty() {
+            return None;
+        }
+    }
+    Some(object)
+}
+
+/// Parses a JSON string without escapes at the start of `input`, returning it and the rest
+fn parse_json_string(input: &str) -> Option<(&str, &str)> {
+    let input = input.strip_prefix('"')?;
+    let end = input.find('"')?;
+    if input[..end].contains('\\') {
+        return None;
+    }
+    Some((&input[..end], &input[end + 1..]))
+}
+
+fn parse_g1_point(line: &str, subgroup_check: SubgroupCheck) -> Result<G1Affine, KzgError> {
+    let bytes: [u8; BYTES_PER_G1_POINT] = decode_point_hex(line)?;
+    subgroup_check
+        .g1_from_compressed(&bytes)
+        .map_err(|_| KzgError::InvalidTrustedSetup("Invalid G1 point".to_string()))
+}
+
+fn parse_g2_point(line: &str, subgroup_check: SubgroupCheck) -> Result<G2Affine, KzgError> {
+    let bytes: [u8; BYTES_PER_G2_POINT] = decode_point_hex(line)?;
+    subgroup_check
+        .g2_from_compressed(&bytes)
+        .map_err(|_| KzgError::InvalidTrustedSetup("Invalid G2 point".to_string()))
+}
+
+fn decode_point_hex<const N: usize>(line: &str) -> Result<[u8; N], KzgError> {
+    let line = line.strip_prefix("0x").unwrap_or(line);
+    hex::decode(line)
+        .map_err(|e| KzgError::InvalidHexFormat(format!("Failed to decode hex: {}", e)))?
+        .try_into()
+        .map_err(|bytes: Vec<u8>| {
+            KzgError::InvalidBytesLength(format!("Expected {} bytes, got {}", N, bytes.len()))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fft::expand_root_of_unity;
+    use bls12_381::G2Projective;
+
+    /// Builds a tiny setup with a known secret, returning the G1 Lagrange points in natural
+    /// order, the G1 monomial points and the G2 monomial points
+    fn tiny_setup(n: usize) -> (Vec<G1Affine>, Vec<G1Affine>, Vec<G2Affine>) {
+        let tau = Scalar::from(5);
+        let roots = expand_root_of_unity(n).unwrap();
+        let n_inv = Scalar::from(n as u64).invert().unwrap();
+        let tau_n_minus_one = tau.pow(&[n as u64, 0, 0, 0]) - Scalar::one();
+
+        let lagrange = roots
+            .iter()
+            .map(|root| {
+                let l = *root * n_inv * tau_n_minus_one * (tau - root).invert().unwrap();
+                (G1Projective::generator() * l).into()
+            })
+            .collect();
+        let monomial = (0..n as u64)
+            .map(|i| (G1Projective::generator() * tau.pow(&[i, 0, 0, 0])).into())
+            .collect();
+        let g2 = vec![
+            G2Projective::generator().into(),
+            (G2Projective::generator() * tau).into(),
+        ];
+        (lagrange, monomial, g2)
+    }
+
+    fn to_text(g1: &[G1Affine], g2: &[G2Affine], monomial: &[G1Affine]) -> String {
+        let mut text = format!("{}\n{}\n", g1.len(), g2.len());
+        for point in g1 {
+            text += &format!("{}\n", hex::encode(point.to_compressed()));
+        }
+        for point in g2 {
+            text += &format!("{}\n", hex::encode(point.to_compressed()));
+        }
+        for point in monomial {
+            text += &format!("{}\n", hex::encode(point.to_compressed()));
+        }
+        text
+    }
+
+    #[test]
+    fn test_parse_trusted_setup() {
+        let (lagrange, monomial, g2) = tiny_setup(8);
+
+        let settings =
+            KzgSettings::parse_trusted_setup(&to_text(&lagrange, &g2, &monomial)).unwrap();
+        assert_eq!(settings.g1_points, bit_reversal_permutation(&lagrange));
+        assert_eq!(settings.g2_points, g2);
+        assert_eq!(&*settings.g1_monomial().unwrap(), &monomial[..]);
+
+        // The original layout without monomial points derives them from the Lagrange points
+        let settings = KzgSettings::parse_trusted_setup(&to_text(&lagrange, &g2, &[])).unwrap();
+        assert!(settings.g1_monomial_points.is_empty());
+        assert_eq!(&*settings.g1_monomial().unwrap(), &monomial[..]);
+
+        // Monomial points in the Lagrange section are rejected
+        assert!(KzgSettings::parse_trusted_setup(&to_text(&monomial, &g2, &[])).is_err());
+        // Truncated files are rejected
+        let text = to_text(&lagrange, &g2, &[]);
+        let truncated = &text[..text.rfind(char::is_alphanumeric).unwrap() - 200];
+        assert!(KzgSettings::parse_trusted_setup(truncated).is_err());
+        // Short and long point lines report the expected and actual lengths
+        let line = hex::encode(lagrange[0].to_compressed());
+        for (bad, len) in [(&line[..94], 47), (&format!("{}00", line), 49)] {
+            let text = text.replacen(&line, bad, 1);
+            let err = KzgSettings::parse_trusted_setup(&text).unwrap_err();
+            assert!(
+                matches!(&err, KzgError::InvalidBytesLength(msg) if *msg == format!("Expected 48 bytes, got {}", len)),
+                "{:?}",
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_trusted_setup_json() {
+        let (lagrange, monomial, g2) = tiny_setup(8);
+        let array = |points: Vec<[u8; 48]>| {
+            points
+                .iter()
+                .map(|point| format!("\"0x{}\"", hex::encode(point)))
+                .collect::<Vec<_>>()
+                .join(",\n    ")
+        };
+        let g1_lagrange = array(lagrange.iter().map(G1Affine::to_compressed).collect());
+        let g1_monomial = array(monomial.iter().map(G1Affine::to_compressed).collect());
+        let g2_monomial = g2
+            .iter()
+            .map(|point| format!("\"0x{}\"", hex::encode(point.to_compressed())))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let json = format!(
+            "{{\n  \"g1_monomial\": [\n    {}\n  ],\n  \"g1_lagrange\": [{}],\n  \"g2_monomial\": [{}]\n}}\n",
+            g1_monomial, g1_lagrange, g2_monomial
+        );
+        let settings = KzgSettings::parse_trusted_setup_json(&json).unwrap();
+        assert_eq!(
+            settings,
+            KzgSettings::parse_trusted_setup(&to_text(&lagrange, &g2, &monomial)).unwrap()
+        );
+
+        // The monomial points are optional, and unknown keys are ignored
+        let json = format!(
+            "{{\"g1_lagrange\":[{}],\"g2_monomial\":[{}],\"note\":[],\"curve\":\"bls12_381\",\"n\":8}}",
+            g1_lagrange, g2_monomial
+        );
+        let settings = KzgSettings::parse_trusted_setup_json(&json).unwrap();
+        assert_eq!(&*settings.g1_monomial().unwrap(), &monomial[..]);
+
+        let json = format!("{{\"g1_lagrange\":[{}]}}", g1_lagrange);
+        assert!(KzgSettings::parse_trusted_setup_json(&json).is_err());
+        let json = format!("{{\"g1_lagrange\":8,\"g2_monomial\":[{}]}}", g2_monomial);
+        assert!(KzgSettings::parse_trusted_setup_json(&json).is_err());
+        let json = format!(
+            "{{\"g1_lagrange\":[{},],\"g2_monomial\":[{}]}}",
+            g1_lagrange, g2_monomial
+        );
+        assert!(KzgSettings::parse_trusted_setup_json(&json).is_err());
+        assert!(KzgSettings::parse_trusted_setup_json("{\"g1_lagrange\": 1}").is_err());
+    }
+
+    #[test]
+    fn test_load_trusted_setup() {
+        let (lagrange, monomial, g2) = tiny_setup(8);
+        let concat = |points: Vec<Vec<u8>>| points.concat();
+        let lagrange_bytes = concat(
+            lagrange
+                .iter()
+                .map(|p| p.to_compressed().to_vec())
+                .collect(),
+        );
+        let monomial_bytes = concat(
+            monomial
+                .iter()
+                .map(|p| p.to_compressed().to_vec())
+                .collect(),
+        );
+        let g2_bytes = concat(g2.iter().map(|p| p.to_compressed().to_vec()).collect());
+
+        let settings =
+            KzgSettings::load_trusted_setup(&lagrange_bytes, &monomial_bytes, &g2_bytes).unwrap();
+        assert_eq!(
+            settings,
+            KzgSettings::parse_trusted_setup(&to_text(&lagrange, &g2, &monomial)).unwrap()
+        );
+        let settings = KzgSettings::load_trusted_setup(&lagrange_bytes, &[], &g2_bytes).unwrap();
+        assert_eq!(&*settings.g1_monomial().unwrap(), &monomial[..]);
+
+        let load = |precompute| {
+            KzgSettings::load_trusted_setup_with_precompute(
+                &lagrange_bytes,
+                &monomial_bytes,
+                &g2_bytes,
+                precompute,
+            )
+        };
+        assert!(load(0).unwrap().g1_lagrange_table.is_none());
+        let precomputed = load(6).unwrap();
+        assert_eq!(precomputed.g1_lagrange_table.unwrap().window_bits(), 6);
+        assert_eq!(precomputed.g1_points, settings.g1_points);
+        assert!(load(FixedBaseTable::MAX_WINDOW_BITS + 1).is_err());
+
+        assert!(KzgSettings::load_trusted_setup(&lagrange_bytes[1..], &[], &g2_bytes).is_err());
+        assert!(KzgSettings::load_trusted_setup(&lagrange_bytes, &[], &g2_bytes[..96]).is_err());
+        let mut corrupted = lagrange_bytes.clone();
+        corrupted[..48].fill(0xff);
+        assert!(KzgSettings::load_trusted_setup(&corrupted, &[], &g2_bytes).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "embedded-setup")]
+    fn test_embedded() {
+        let embedded = KzgSettings::embedded();
+        assert!(core::ptr::eq(embedded, KzgSettings::embedded()));
+
+        let settings = KzgSettings::load_trusted_setup_file().unwrap();
+        assert_eq!(embedded.g2_points, settings.g2_points);
+        #[cfg(feature = "prover")]
+        {
+            assert_eq!(embedded.g1_points, settings.g1_points);
+            assert_eq!(embedded.roots_of_unity, settings.roots_of_unity);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "prover")]
+    fn test_check_digest() {
+        let settings = KzgSettings::load_trusted_setup_file().unwrap();
+        settings.check_digest(&MAINNET_SETUP_DIGEST).unwrap();
+
+        let (lagrange, mut monomial, g2) = tiny_setup(8);
+        let tiny = KzgSettings::parse_trusted_setup(&to_text(&lagrange, &g2, &monomial)).unwrap();
+        assert!(tiny.check_digest(&MAINNET_SETUP_DIGEST).is_err());
+        tiny.check_digest(&tiny.digest()).unwrap();
+
+        // Tampered monomial points change the digest
+        monomial.swap(2, 3);
+        let tampered =
+            KzgSettings::parse_trusted_setup(&to_text(&lagrange, &g2, &monomial)).unwrap();
+        assert!(tampered.check_digest(&tiny.digest()).is_err());
+
+        let file = include_bytes!("trusted_setup.txt");
+        KzgSettings::check_file_digest(file, &MAINNET_TRUSTED_SETUP_SHA256).unwrap();
+        assert!(KzgSettings::check_file_digest(&file[1..], &MAINNET_TRUSTED_SETUP_SHA256).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "prover")]
+    fn test_verify() {
+        KzgSettings::load_trusted_setup_file()
+            .unwrap()
+            .verify()
+            .unwrap();
+
+        let (lagrange, monomial, g2) = tiny_setup(8);
+        let parse = |lagrange: &[G1Affine], g2: &[G2Affine], monomial: &[G1Affine]| {
+            KzgSettings::parse_trusted_setup(&to_text(lagrange, g2, monomial)).unwrap()
+        };
+        parse(&lagrange, &g2, &monomial).verify().unwrap();
+        parse(&lagrange, &g2, &[]).verify().unwrap();
+
+        let mut tampered = monomial.clone();
+        tampered[5] = (G1Projective::from(tampered[5]) * Scalar::from(2)).into();
+        assert!(parse(&lagrange, &g2, &tampered).verify().is_err());
+
+        let mut tampered = lagrange.clone();
+        tampered.swap(2, 3);
+        assert!(parse(&tampered, &g2, &monomial).verify().is_err());
+
+        let mut tampered = g2.clone();
+        tampered.push((G2Projective::generator() * Scalar::from(24)).into());
+        assert!(parse(&lagrange, &tampered, &monomial).verify().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "prover")]
+    fn test_clone_shares_points() {
+        let settings = KzgSettings::load_trusted_setup_file()
+            .unwrap()
+            .with_precompute(8)
+            .unwrap();
+        let clone = std::thread::spawn({
+            let settings = settings.clone();
+            move || settings
+        })
+        .join()
+        .unwrap();
+        assert!(core::ptr::eq(clone.g1_points, settings.g1_points));
+        assert!(core::ptr::eq(clone.g2_points, settings.g2_points));
+        assert!(core::ptr::eq(
+            clone.g1_lagrange_table.unwrap(),
+            settings.g1_lagrange_table.unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_global() {
+        let global = KzgSettings::global();
+        assert_eq!(*global, KzgSettings::load_trusted_setup_file().unwrap());
+        assert!(core::ptr::eq(global, KzgSettings::global()));
+
+        let custom = global.clone().with_subgroup_check(SubgroupCheck::Skip);
+        assert!(KzgSettings::set_global(custom).is_err());
+        assert_eq!(
+            KzgSettings::global().subgroup_check,
+            SubgroupCheck::default()
+        );
+    }
+
+    #[test]
+    fn test_subset() {
+        let (lagrange, monomial, g2) = tiny_setup(8);
+        let settings =
+            KzgSettings::parse_trusted_setup(&to_text(&lagrange, &g2, &monomial)).unwrap();
+
+        // Loading the same setup again reuses the leaked points
+        let reloaded =
+            KzgSettings::parse_trusted_setup(&to_text(&lagrange, &g2, &monomial)).unwrap();
+        assert!(core::ptr::eq(reloaded.g1_points, settings.g1_points));
+        assert!(core::ptr::eq(reloaded.g2_points, settings.g2_points));
+        assert!(core::ptr::eq(
+            reloaded.g1_monomial_points,
+            settings.g1_monomial_points
+        ));
+
+        let (lagrange, monomial, _) = tiny_setup(4);
+        let subset = settings.subset(4).unwrap();
+        // So does deriving the same subset again, and the monomial points are a prefix of the
+        // full setup's
+        assert!(core::ptr::eq(
+            settings.subset(4).unwrap().g1_points,
+            subset.g1_points
+        ));
+        assert!(core::ptr::eq(
+            subset.g1_monomial_points,
+            &settings.g1_monomial_points[..4]
+        ));
+        assert_eq!(subset.g1_points, bit_reversal_permutation(&lagrange));
+        assert_eq!(subset.g1_monomial_points, monomial);
+        assert_eq!(
+            subset.roots_of_unity,
+            bit_reversal_permutation(&expand_root_of_unity(4).unwrap())
+        );
+        assert_eq!(subset.g2_points, settings.g2_points);
+        assert_eq!(settings.field_elements_per_blob(), 8);
+        assert_eq!(subset.field_elements_per_blob(), 4);
+        assert_eq!(subset.bytes_per_blob(), 4 * BYTES_PER_FIELD_ELEMENT);
+
+        assert!(settings.subset(16).is_err());
+        assert!(settings.subset(6).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "prover")]
+    fn test_embedded_setup_monomial_points() {
+        let settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let monomial = settings.g1_monomial().unwrap();
+        assert_eq!(monomial[0], G1Affine::generator());
+        // e([τ]G1, G2) == e(G1, [τ]G2)
+        assert!(pairings_verify(
+            monomial[1],
+            settings.g2_points[0],
+            monomial[0],
+            settings.g2_points[1]
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "prover")]
+    fn test_reloaded_setup_shares_derived_points() {
+        let (lagrange, monomial, g2) = tiny_setup(8);
+        let text = to_text(&lagrange, &g2, &[]);
+        let first = KzgSettings::parse_trusted_setup(&text).unwrap();
+        let second = KzgSettings::parse_trusted_setup(&text).unwrap();
+        assert!(core::ptr::eq(first.g1_points, second.g1_points));
+
+        let derived = first.g1_monomial().unwrap();
+        assert_eq!(&*derived, &monomial[..]);
+        assert!(core::ptr::eq(&*derived, &*second.g1_monomial().unwrap()));
+
+        let table = first.with_precompute(4).unwrap().g1_lagrange_table.unwrap();
+        assert!(core::ptr::eq(
+            second
+                .with_precompute(4)
+                .unwrap()
+                .g1_lagrange_table
+                .unwrap(),
+            table
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "prover")]
+    fn test_with_precompute() {
+        use crate::kzg_proof::{tests::valid_blob_cases, KzgProof};
+
+        let settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let precomputed = settings.clone().with_precompute(8).unwrap();
+        let table = precomputed.g1_lagrange_table.unwrap();
+        assert_eq!(table.window_bits(), 8);
+        assert_eq!(table.len(), settings.g1_points.len());
+        // The table is built once per setup and window size
+        assert!(core::ptr::eq(
+            settings
+                .clone()
+                .with_precompute(8)
+                .unwrap()
+                .g1_lagrange_table
+                .unwrap(),
+            table
+        ));
+
+        let (blobs, commitments, _) = valid_blob_cases();
+        for (blob, commitment) in blobs.iter().zip(&commitments) {
+            assert_eq!(
+                KzgProof::blob_to_kzg_commitment(blob, &precomputed)
+                    .unwrap()
+                    .as_slice(),
+                commitment.as_slice()
+            );
+        }
+        assert!(settings.with_precompute(0).is_err());
+    }
 }
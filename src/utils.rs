@@ -0,0 +1,219 @@
+//! Small helpers shared by the KZG algorithms and useful to downstream protocols.
+
+use crate::{dtypes::*, enums::KzgError, VERSIONED_HASH_VERSION_KZG};
+
+use alloc::vec::Vec;
+use bls12_381::Scalar;
+use sha2::{Digest, Sha256};
+
+/// Returns `[1, base, base^2, ..., base^(num_powers - 1)]`
+pub fn compute_powers(base: &Scalar, num_powers: usize) -> Vec<Scalar> {
+    let mut powers = vec![Scalar::default(); num_powers];
+    if num_powers == 0 {
+        return powers;
+    }
+    powers[0] = Scalar::one();
+    for i in 1..num_powers {
+        powers[i] = powers[i - 1] * base;
+    }
+    powers
+}
+
+/// Montgomery batch inversion in a finite field
+/// Given a list of elements \( x_1, x_2, \dots, x_n \) from a finite field \( F \), Montgomery batch inversion computes the inverses \( x_1^{-1}, x_2^{-1}, \dots, x_n^{-1} \) as follows:
+///
+/// Let's consider three elements \( a \), \( b \), and \( c \) in a finite field \( F \). The steps are as follows:
+///
+/// 1. **Product Accumulation**:
+///    \[
+///    P = a \times b \times c
+///    \]
+///
+/// 2. **Single Inversion**:
+///    \[
+///    P^{-1} = \text{inverse}(P)
+///    \]
+///
+/// 3. **Backward Substitution**:
+///    - \( a^{-1} = P^{-1} \times (b \times c) \)
+///    - \( b^{-1} = P^{-1} \times (a \times c) \)
+///    - \( c^{-1} = P^{-1} \times (a \times b) \)
+///
+/// Any number of elements can be inverted, including none. Fails if one of them is zero, see
+/// [`batch_inversion_skip_zeros`] to invert the others instead.
+pub fn batch_inversion(values: &[Scalar]) -> Result<Vec<Scalar>, KzgError> {
+    let (inverses, zeros) = batch_inversion_skip_zeros(values);
+    if let Some(index) = zeros.first() {
+        return Err(KzgError::BadArgs(format!(
+            "Cannot invert element {}, which is zero",
+            index
+        )));
+    }
+    Ok(inverses)
+}
+
+/// Same as [`batch_inversion`], except that zero elements are left out of the product and map
+/// to zero. Returns the inverses together with the indices of the zero elements, in order.
+pub fn batch_inversion_skip_zeros(values: &[Scalar]) -> (Vec<Scalar>, Vec<usize>) {
+    let mut inverses = Vec::with_capacity(values.len());
+    let mut zeros = Vec::new();
+
+    // Compute the product of all the non-zero elements:
+    //
+    // \[
+    // P = x_1 \times x_2 \times \dots \times x_n
+    // \]
+    //
+    // storing the product of the elements before each one
+    let mut accumulator = Scalar::one();
+    for (i, value) in values.iter().enumerate() {
+        inverses.push(accumulator);
+        if *value == Scalar::zero() {
+            zeros.push(i);
+        } else {
+            accumulator *= value;
+        }
+    }
+
+    // Compute the inverse of the product \( P \), which is not zero
+    accumulator = accumulator.invert().unwrap();
+
+    // Compute the inverse of each element \( x_i^{-1} \) by using the precomputed product and its inverse:
+    //
+    // \[
+    // x_i^{-1} = P^{-1} \times \left(\prod_{j \neq i} x_j \right)
+    // \]
+    for (inverse, value) in inverses.iter_mut().zip(values).rev() {
+        if *value == Scalar::zero() {
+            *inverse = Scalar::zero();
+        } else {
+            *inverse *= accumulator;
+            accumulator *= value;
+        }
+    }
+
+    (inverses, zeros)
+}
+
+/// Returns the EIP-4844 versioned hash of a commitment: `0x01 || sha256(commitment)[1..]`
+pub fn kzg_to_versioned_hash(commitment: &Bytes48) -> Bytes32 {
+    let mut hash: [u8; 32] = Sha256::digest(commitment.as_slice()).into();
+    hash[0] = VERSIONED_HASH_VERSION_KZG;
+    Bytes32::from(hash)
+}
+
+/// Index of the first commitment that does not match its versioned hash. When the slices have
+/// different lengths and agree on their common prefix, the index is the length of the shorter one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MismatchAt {
+    pub index: usize,
+}
+
+impl From<MismatchAt> for KzgError {
+    fn from(value: MismatchAt) -> Self {
+        KzgError::BadArgs(format!(
+            "Versioned hash {} does not match the commitment",
+            value.index
+        ))
+    }
+}
+
+/// Checks every commitment of a transaction against its versioned hash, stopping at the first
+/// mismatch. This only hashes, so it is a cheap filter to run before any pairing work. The
+/// SHA-256 implementation uses the SHA extensions of the CPU when they are available.
+pub fn verify_versioned_hashes(
+    commitments: &[Bytes48],
+    versioned_hashes: &[VersionedHash],
+) -> Result<(), MismatchAt> {
+    if let Some(index) =
+        commitments
+            .iter()
+            .zip(versioned_hashes)
+            .position(|(commitment, versioned_hash)| {
+                kzg_to_versioned_hash(commitment).as_slice() != versioned_hash.as_slice()
+            })
+    {
+        return Err(MismatchAt { index });
+    }
+
+    if commitments.len() != versioned_hashes.len() {
+        return Err(MismatchAt {
+            index: commitments.len().min(versioned_hashes.len()),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_powers() {
+        assert!(compute_powers(&Scalar::from(3), 0).is_empty());
+        assert_eq!(
+            compute_powers(&Scalar::from(3), 5),
+            [1, 3, 9, 27, 81].map(Scalar::from)
+        );
+    }
+
+    #[test]
+    fn test_batch_inversion() {
+        let values = (0..7u64)
+            .map(|i| Scalar::from(i * i + 2))
+            .collect::<Vec<_>>();
+        let inverses = batch_inversion(&values).unwrap();
+        for (value, inverse) in values.iter().zip(&inverses) {
+            assert_eq!(value * inverse, Scalar::one());
+        }
+        assert!(batch_inversion(&[]).unwrap().is_empty());
+
+        let mut with_zeros = values.clone();
+        with_zeros[0] = Scalar::zero();
+        with_zeros[4] = Scalar::zero();
+        assert!(batch_inversion(&with_zeros).is_err());
+        let (skipped, zeros) = batch_inversion_skip_zeros(&with_zeros);
+        assert_eq!(zeros, [0, 4]);
+        assert_eq!(skipped[0], Scalar::zero());
+        assert_eq!(skipped[4], Scalar::zero());
+        assert_eq!(skipped[3], inverses[3]);
+        assert_eq!(skipped[6], inverses[6]);
+        assert_eq!(
+            batch_inversion_skip_zeros(&[Scalar::zero()]),
+            (vec![Scalar::zero()], vec![0])
+        );
+    }
+
+    #[test]
+    fn test_kzg_to_versioned_hash() {
+        // The commitment to the zero blob is the point at infinity
+        let mut commitment = [0u8; 48];
+        commitment[0] = 0xc0;
+        assert_eq!(
+            hex::encode(kzg_to_versioned_hash(&commitment.into()).as_slice()),
+            "010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014"
+        );
+    }
+
+    #[test]
+    fn test_verify_versioned_hashes() {
+        let commitments = (0..4u8).map(|i| Bytes48::from([i; 48])).collect::<Vec<_>>();
+        let mut hashes = commitments
+            .iter()
+            .map(kzg_to_versioned_hash)
+            .collect::<Vec<_>>();
+        assert_eq!(verify_versioned_hashes(&commitments, &hashes), Ok(()));
+        assert_eq!(verify_versioned_hashes(&[], &[]), Ok(()));
+
+        assert_eq!(
+            verify_versioned_hashes(&commitments, &hashes[..3]),
+            Err(MismatchAt { index: 3 })
+        );
+
+        hashes.swap(1, 2);
+        assert_eq!(
+            verify_versioned_hashes(&commitments, &hashes),
+            Err(MismatchAt { index: 1 })
+        );
+    }
+}
@@ -0,0 +1,160 @@
+//! Settings for verifiers that never commit or prove.
+//!
+//! Verifying an opening only needs `[τ]G2` of the trusted setup, and verifying a blob proof the
+//! roots of unity to evaluate the blob at the challenge. [`VerifierSettings`] holds just those,
+//! borrowed from the embedded setup or a loaded [`KzgSettings`], so a verifier built on it does
+//! not reference the G1 points of the setup and zkVM guests do not carry them.
+
+use crate::domains::DomainSeparators;
+use crate::dtypes::*;
+use crate::enums::KzgError;
+use crate::fft::EvaluationDomain;
+use crate::kzg_proof::KzgProof;
+#[cfg(feature = "prover")]
+use crate::kzg_proof::{verify_blob_kzg_proof_batch_controlled, verify_blob_kzg_proof_impl};
+use crate::limits::Limits;
+use crate::subgroup::SubgroupCheck;
+use crate::trusted_setup::{get_g2_points, KzgSettings};
+use crate::NUM_FIELD_ELEMENTS_PER_BLOB;
+
+use bls12_381::{G2Affine, Scalar};
+
+/// The part of the trusted setup that verification uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifierSettings {
+    /// The G2 generator and `[τ]G2`
+    pub g2_points: &'static [G2Affine],
+    /// Roots of unity of the evaluation domain, in bit-reversed order
+    pub roots_of_unity: &'static [Scalar],
+    /// Subgroup check applied to the points passed to the verification functions
+    pub subgroup_check: SubgroupCheck,
+    /// Domain separators of the Fiat-Shamir challenges
+    pub domain_separators: DomainSeparators,
+    /// Largest inputs accepted by the batch functions
+    pub limits: Limits,
+}
+
+impl VerifierSettings {
+    /// Returns the settings of the embedded mainnet trusted setup. The roots of unity are
+    /// computed on first use rather than embedded.
+    pub fn mainnet() -> Result<Self, KzgError> {
+        Ok(Self {
+            g2_points: &get_g2_points()[..2],
+            roots_of_unity: &EvaluationDomain::get(NUM_FIELD_ELEMENTS_PER_BLOB)?.bit_reversed_roots,
+            subgroup_check: SubgroupCheck::default(),
+            domain_separators: DomainSeparators::default(),
+            limits: Limits::default(),
+        })
+    }
+
+    /// Returns `[τ]G2`
+    pub fn g2_tau(&self) -> G2Affine {
+        self.g2_points[1]
+    }
+
+    /// Returns settings without G1 points that the [`KzgProof`] verification functions accept
+    fn as_kzg_settings(&self) -> KzgSettings {
+        KzgSettings {
+            roots_of_unity: self.roots_of_unity,
+            g1_points: &[],
+            g2_points: self.g2_points,
+            g1_monomial_points: &[],
+            subgroup_check: self.subgroup_check,
+            domain_separators: self.domain_separators,
+            limits: self.limits,
+            g1_lagrange_table: None,
+        }
+    }
+
+    /// Verifies that `proof_bytes` opens `commitment_bytes` to `y_bytes` at `z_bytes`, see
+    /// [`KzgProof::verify_kzg_proof`]
+    pub fn verify_kzg_proof(
+        &self,
+        commitment_bytes: &Bytes48,
+        z_bytes: &Bytes32,
+        y_bytes: &Bytes32,
+        proof_bytes: &Bytes48,
+    ) -> Result<bool, KzgError> {
+        KzgProof::verify_kzg_proof(
+            commitment_bytes,
+            z_bytes,
+            y_bytes,
+            proof_bytes,
+            &self.as_kzg_settings(),
+        )
+    }
+
+    /// Verifies a blob proof, see [`KzgProof::verify_blob_kzg_proof`]
+    #[cfg(feature = "prover")]
+    pub fn verify_blob_kzg_proof(
+        &self,
+        blob: &Blob,
+        commitment_bytes: &Bytes48,
+        proof_bytes: &Bytes48,
+    ) -> Result<bool, KzgError> {
+        verify_blob_kzg_proof_impl(blob, commitment_bytes, proof_bytes, &self.as_kzg_settings())
+    }
+
+    /// Verifies blob proofs in a batch, see [`KzgProof::verify_blob_kzg_proof_batch`]
+    #[cfg(feature = "prover")]
+    pub fn verify_blob_kzg_proof_batch(
+        &self,
+        blobs: &[Blob],
+        commitments_bytes: &[Bytes48],
+        proofs_bytes: &[Bytes48],
+    ) -> Result<bool, KzgError> {
+        verify_blob_kzg_proof_batch_controlled(
+            blobs,
+            commitments_bytes,
+            proofs_bytes,
+            &self.as_kzg_settings(),
+            &mut |_| {},
+            None,
+        )
+    }
+}
+
+impl From<&KzgSettings> for VerifierSettings {
+    fn from(kzg_settings: &KzgSettings) -> Self {
+        Self {
+            g2_points: &kzg_settings.g2_points[..2],
+            roots_of_unity: kzg_settings.roots_of_unity,
+            subgroup_check: kzg_settings.subgroup_check,
+            domain_separators: kzg_settings.domain_separators,
+            limits: kzg_settings.limits,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "prover"))]
+mod tests {
+    use super::*;
+    use crate::kzg_proof::tests::valid_blob_cases;
+
+    #[test]
+    fn test_verifier_settings() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let verifier = VerifierSettings::mainnet().unwrap();
+        assert_eq!(verifier, VerifierSettings::from(&kzg_settings));
+        assert_eq!(verifier.g2_tau(), kzg_settings.g2_points[1]);
+
+        let (blobs, commitments, proofs) = valid_blob_cases();
+        for ((blob, commitment), proof) in blobs.iter().zip(&commitments).zip(&proofs) {
+            assert!(verifier
+                .verify_blob_kzg_proof(blob, commitment, proof)
+                .unwrap());
+        }
+        assert!(verifier
+            .verify_blob_kzg_proof_batch(&blobs, &commitments, &proofs)
+            .unwrap());
+        assert!(!verifier
+            .verify_blob_kzg_proof(&blobs[0], &commitments[0], &proofs[1])
+            .unwrap_or(false));
+
+        let z = Bytes32::from([7; 32]);
+        let opening = KzgProof::compute_kzg_proof(&blobs[0], &z, &kzg_settings).unwrap();
+        assert!(verifier
+            .verify_kzg_proof(&commitments[0], &z, &opening.y, &opening.proof)
+            .unwrap());
+    }
+}
@@ -0,0 +1,112 @@
+//! Eager initialization of the precomputed values the verification and proving functions
+//! otherwise build on first use.
+//!
+//! A node that loads its settings at startup can call [`KzgSettings::warm_up`] right after, so
+//! the first blob or cell verification after a restart does not pay for deriving the monomial
+//! points, preparing the G2 points for the pairing, or ordering the extended domain.
+
+use crate::trusted_setup::{get_g2_prepared, KzgSettings};
+
+use std::thread::{self, JoinHandle};
+
+/// Selects what [`KzgSettings::warm_up`] precomputes. Everything is selected by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WarmUpOptions {
+    /// Derive the G1 monomial points used as MSM bases by the cell and multiproof functions,
+    /// unless the setup already includes them, and the FK20 table for the cell proofs
+    pub msm_tables: bool,
+    /// Prepare the G2 points shared by all pairing checks
+    pub prepared_pairings: bool,
+    /// Compute the extended domain of the cells in bit-reversed order
+    pub bit_reversed_orderings: bool,
+}
+
+impl Default for WarmUpOptions {
+    fn default() -> Self {
+        Self {
+            msm_tables: true,
+            prepared_pairings: true,
+            bit_reversed_orderings: true,
+        }
+    }
+}
+
+/// Background threads started by [`KzgSettings::warm_up`]. Dropping it lets them finish
+/// detached.
+#[derive(Debug)]
+pub struct WarmUp {
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl WarmUp {
+    /// Returns `true` once every precomputation has completed
+    pub fn is_finished(&self) -> bool {
+        self.threads.iter().all(|thread| thread.is_finished())
+    }
+
+    /// Blocks until every precomputation has completed
+    pub fn wait(self) {
+        for thread in self.threads {
+            // The precomputations only panic on settings the functions would reject anyway
+            let _ = thread.join();
+        }
+    }
+}
+
+impl KzgSettings {
+    /// Starts building the values selected by `options` on background threads, one per kind,
+    /// and returns without waiting for them
+    pub fn warm_up(&self, options: WarmUpOptions) -> WarmUp {
+        let mut threads = Vec::new();
+
+        #[cfg(feature = "prover")]
+        if options.msm_tables && !self.g1_points.is_empty() {
+            let settings = self.clone();
+            threads.push(thread::spawn(move || {
+                let _ = crate::fk20::fk20_table(&settings, crate::FIELD_ELEMENTS_PER_CELL);
+            }));
+        }
+        if options.prepared_pairings {
+            threads.push(thread::spawn(|| {
+                get_g2_prepared();
+            }));
+        }
+        #[cfg(feature = "prover")]
+        if options.bit_reversed_orderings {
+            threads.push(thread::spawn(|| {
+                crate::cells::extended_roots_of_unity();
+            }));
+        }
+
+        WarmUp { threads }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warm_up() {
+        let kzg_settings = KzgSettings::load_trusted_setup_file().unwrap();
+        let warm_up = kzg_settings.warm_up(WarmUpOptions::default());
+        warm_up.wait();
+
+        assert_eq!(get_g2_prepared().len(), 3);
+        #[cfg(feature = "prover")]
+        {
+            // The derived points are cached, so later calls borrow the same slice
+            let first = kzg_settings.g1_monomial().unwrap();
+            let second = kzg_settings.g1_monomial().unwrap();
+            assert!(core::ptr::eq(first.as_ptr(), second.as_ptr()));
+            assert!(matches!(first, alloc::borrow::Cow::Borrowed(_)));
+        }
+
+        let nothing = WarmUpOptions {
+            msm_tables: false,
+            prepared_pairings: false,
+            bit_reversed_orderings: false,
+        };
+        assert!(kzg_settings.warm_up(nothing).is_finished());
+    }
+}